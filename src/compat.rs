@@ -16,8 +16,11 @@
 //! - `Arc` - 原子引用计数智能指针
 //! - `String` - 动态字符串
 //! - `vec!` - 创建 Vec 的宏
+//! - [`Shared`] - 同步原语句柄共享内部状态的指针
+//!
+//! 除 `Shared` 外的类型只在启用 `alloc` feature（或测试）时可用。
 
-#[cfg(not(test))]
+#[cfg(all(feature = "alloc", not(test)))]
 pub use alloc::{
     boxed::Box,
     collections::VecDeque,
@@ -37,3 +40,63 @@ pub use std::{
     vec::Vec,
 };
 
+/// 同步原语句柄共享的内部状态
+///
+/// 堆上的内部状态（`Shared::new`，需要 `alloc`）按引用计数释放；
+/// 静态对象（如 `StaticSignal`）的内部状态以 `&'static` 引用共享，不需要分配内存。
+pub struct Shared<T> {
+    repr: SharedRepr<T>,
+}
+
+enum SharedRepr<T> {
+    #[cfg(feature = "alloc")]
+    Heap(Arc<T>),
+    /// 来自 `&'static T`
+    Static(core::ptr::NonNull<T>),
+}
+
+// 与 Arc 相同：共享的内部状态可以被多个任务同时访问
+unsafe impl<T: Send + Sync> Send for Shared<T> {}
+unsafe impl<T: Send + Sync> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    /// 在堆上创建内部状态
+    #[cfg(feature = "alloc")]
+    pub fn new(value: T) -> Self {
+        Self { repr: SharedRepr::Heap(Arc::new(value)) }
+    }
+
+    /// 共享静态对象的内部状态
+    pub const fn from_static(value: &'static T) -> Self {
+        Self { repr: SharedRepr::Static(core::ptr::NonNull::from_ref(value)) }
+    }
+
+    /// 内部状态的地址，用作同步对象的唯一标识
+    pub fn as_ptr(this: &Self) -> *const T {
+        &**this
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            #[cfg(feature = "alloc")]
+            SharedRepr::Heap(inner) => SharedRepr::Heap(inner.clone()),
+            SharedRepr::Static(inner) => SharedRepr::Static(*inner),
+        };
+        Self { repr }
+    }
+}
+
+impl<T> core::ops::Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.repr {
+            #[cfg(feature = "alloc")]
+            SharedRepr::Heap(inner) => inner,
+            // SAFETY: 指针来自 `&'static T`
+            SharedRepr::Static(inner) => unsafe { inner.as_ref() },
+        }
+    }
+}
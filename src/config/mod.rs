@@ -1,8 +1,23 @@
 // 操作系统任务栈大小配置
 pub const STACK_SIZE: usize = 4096; // 4KB的栈大小
+pub const MIN_STACK_SIZE: usize = 256; // 自定义任务栈的最小大小，需容纳初始异常帧
 pub const MAX_TASKS: usize = 10;
+pub const STACK_POOL_SIZE: usize = MAX_TASKS; // 静态栈池中 STACK_SIZE 栈的数量，只分配给使用默认栈的任务
+pub const PRIORITY_LEVELS: usize = 5; // 优先级级数（5-256），命名优先级按级数均匀分布
+pub const DEFAULT_TIME_SLICE: usize = 10; // 同优先级任务轮转的默认时间片（节拍），0 表示不轮转
 pub const MAX_SIGNALS: usize = 10;
 pub const MAX_TIMERS: usize = 10;
 pub const MAX_MUTEXES: usize = 10;
+pub const MAX_HELD_LOCKS: usize = 8; // 每个任务可同时持有并被追踪的锁数量
+pub const MAX_TASK_LOCALS: usize = 4; // 每个任务的本地存储槽位数量
 pub const MAX_MQS: usize = 10;
+pub const ISR_DEFER_QUEUE_SIZE: usize = 16; // 中断延迟处理队列的容量（2 的幂）
 pub const HEAP_SIZE: usize = 8 * 1024;  // 8KB - 适合 64KB RAM 的嵌入式设备
+
+// 关闭 alloc feature 时使用的固定容量
+pub const TASK_FN_WORDS: usize = 8; // 任务闭包的内联存储大小（字）
+pub const MAX_ASYNC_WAITERS: usize = 16; // 每个同步对象可登记的异步等待者数量
+pub const MAX_ASYNC_TASKS: usize = 8; // 执行器就绪队列的容量
+pub const MAX_IPC_QUEUES: usize = 8; // Ipc 消息队列的数量
+pub const IPC_QUEUE_CAPACITY: usize = 8; // 每个 Ipc 消息队列的最大消息数
+pub const IPC_MESSAGE_WORDS: usize = 4; // Ipc 消息的内联存储大小（字）
@@ -4,12 +4,18 @@ pub enum RtosError {
     TaskNotFound,
     TaskSlotsFull,
     InvalidTaskState,
+    TaskLocalSlotsFull,
+    NotificationPending,
+    WaitAborted,
     
     // 同步相关 - Mutex
     MutexNotOwned,
     MutexSlotsFull,
     MutexPoisoned,
+    OwnerDied,
     MutexLockFailed,
+    PriorityCeilingViolated,
+    TooManyHeldLocks,
     
     // 同步相关 - Signal
     SignalSlotsFull,
@@ -52,12 +58,18 @@ impl core::fmt::Display for RtosError {
             RtosError::TaskNotFound => write!(f, "Task not found"),
             RtosError::TaskSlotsFull => write!(f, "Task slots full"),
             RtosError::InvalidTaskState => write!(f, "Invalid task state"),
+            RtosError::TaskLocalSlotsFull => write!(f, "Task-local storage slots full"),
+            RtosError::NotificationPending => write!(f, "Task notification already pending"),
+            RtosError::WaitAborted => write!(f, "Wait aborted by task suspension"),
             
             // Mutex
             RtosError::MutexNotOwned => write!(f, "Mutex not owned by current task"),
             RtosError::MutexSlotsFull => write!(f, "Mutex slots full"),
             RtosError::MutexPoisoned => write!(f, "Mutex poisoned"),
+            RtosError::OwnerDied => write!(f, "Lock owner died while holding the lock"),
             RtosError::MutexLockFailed => write!(f, "Failed to acquire mutex lock"),
+            RtosError::PriorityCeilingViolated => write!(f, "Task priority above mutex priority ceiling"),
+            RtosError::TooManyHeldLocks => write!(f, "Task holds too many locks"),
             
             // Signal
             RtosError::SignalSlotsFull => write!(f, "Signal slots full"),
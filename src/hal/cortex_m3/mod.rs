@@ -1,8 +1,7 @@
 use crate::kernel::scheduler::Scheduler;
 use crate::kernel::time::systick::Systick;
-use crate::kernel::task::Task;
+use crate::kernel::task::{Priority, Task};
 use crate::kernel::time::timer::Timer;
-use crate::utils::task_exit_error;
 use core::mem::size_of;
 use cortex_m::peripheral::SCB;
 use cortex_m::register::psp;
@@ -10,6 +9,11 @@ use cortex_m_rt::ExceptionFrame;
 use cortex_m_rt::exception;
 use crate::{info, error, warn, debug, trace};
 
+/// 任务入口的返回地址（LR），`task_wrapper_entry` 不会返回，正常情况下不会执行到这里
+fn task_exit_error() -> ! {
+    loop {}
+}
+
 pub(crate) fn init_task_stack(top_of_stack: &mut usize, func: fn(usize), p_args: usize) {
     unsafe {
         *top_of_stack &= !7;
@@ -53,6 +57,7 @@ pub(crate) fn start_first_task() {
 #[exception]
 unsafe fn SysTick() {
     Systick::systick_inc();
+    Scheduler::tick();
     Timer::timer_check_and_send_event();
     trigger_schedule();
 }
@@ -72,7 +77,9 @@ pub(crate) fn init_idle_task() {
             cortex_m::asm::wfi();
         }
     }
-    let task = Task::new("idle", idle_task).unwrap();
+    let mut task = Task::new("idle", idle_task).unwrap();
+    // 空闲任务使用最低优先级，运行时间统计据此计算空闲份额
+    task.set_priority(Priority::Idle);
 }
 
 // 注意：panic_handler 已移至用户代码或使用 default_panic_handler! 宏
@@ -20,6 +20,14 @@
 //! # 或
 //! neon-rtos2 = { version = "0.1", features = ["riscv"] }
 //! ```
+//!
+//! 默认启用的 `alloc` 会让嵌入式目标使用 embedded-alloc 作为全局分配器。
+//! 不使用动态内存时关闭默认 features：
+//!
+//! ```toml
+//! [dependencies]
+//! neon-rtos2 = { version = "0.1", default-features = false, features = ["cortex_m3"] }
+//! ```
 
 pub mod traits;
 
@@ -13,7 +13,38 @@ pub(crate) fn start_first_task() {
 }
 
 pub(crate) fn trigger_schedule() {
+    #[cfg(test)]
+    run_schedule_hook();
+}
+
+#[cfg(test)]
+std::thread_local! {
+    /// `trigger_schedule` 时调用的钩子
+    static SCHEDULE_HOOK: core::cell::RefCell<Option<std::boxed::Box<dyn FnMut()>>> =
+        const { core::cell::RefCell::new(None) };
+}
+
+/// 设置 `trigger_schedule` 时调用的钩子
+///
+/// 测试中没有真正的任务切换，用钩子模拟当前任务阻塞期间发生的事情（例如 SysTick 中断）。
+#[cfg(test)]
+pub(crate) fn set_schedule_hook(hook: Option<std::boxed::Box<dyn FnMut()>>) {
+    SCHEDULE_HOOK.with(|slot| *slot.borrow_mut() = hook);
+}
 
+/// 调用钩子，钩子执行期间再次触发调度不会重入
+#[cfg(test)]
+fn run_schedule_hook() {
+    let hook = SCHEDULE_HOOK.with(|slot| slot.borrow_mut().take());
+    if let Some(mut hook) = hook {
+        hook();
+        SCHEDULE_HOOK.with(|slot| {
+            let mut slot = slot.borrow_mut();
+            if slot.is_none() {
+                *slot = Some(hook);
+            }
+        });
+    }
 }
 
 pub(crate) fn init_idle_task() {
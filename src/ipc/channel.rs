@@ -1,25 +1,49 @@
+//! 类型擦除的 IPC 消息队列
+//!
+//! 启用 `alloc` 时消息装箱保存，队列数量和容量不限；关闭时消息内联保存
+//! （最大 `config::IPC_MESSAGE_WORDS` 个字），最多 `config::MAX_IPC_QUEUES` 个队列，
+//! 每个队列最多 `config::IPC_QUEUE_CAPACITY` 条消息。
+
 use crate::kernel::task::Task;
-use crate::{sync::event::Event, kernel::scheduler::Scheduler};
+use crate::{sync::event::Event, sync::wait_queue::WaitQueue, kernel::scheduler::Scheduler};
+use crate::sync::signal::WaiterList;
+#[cfg(feature = "alloc")]
 use crate::compat::{Box, Vec, VecDeque};
+#[cfg(not(feature = "alloc"))]
+use crate::config::{IPC_MESSAGE_WORDS, IPC_QUEUE_CAPACITY, MAX_IPC_QUEUES};
+#[cfg(not(feature = "alloc"))]
+use crate::mem::{inline::InlineBox, ring::RingBuffer};
 
+#[cfg(feature = "alloc")]
 use core::any::Any;
+#[cfg(not(feature = "alloc"))]
+use core::any::TypeId;
+#[cfg(feature = "alloc")]
 use core::sync::atomic::{AtomicUsize, Ordering};
 use spin::Mutex;
 
 // IPC句柄类型
+//
+// 由队列槽位和槽位的代数组成，队列销毁后槽位被新队列重用时，
+// 旧句柄的操作返回 `IpcError::InvalidHandle`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct IpcHandle(usize);
+pub struct IpcHandle {
+    slot: usize,
+    generation: u32,
+}
 
 // 消息类型，使用 Box<dyn Any> 进行类型安全的类型擦除
+#[cfg(feature = "alloc")]
 struct Message {
     data: Box<dyn Any + Send>,
 }
 
+#[cfg(feature = "alloc")]
 impl Message {
-    fn new<T: 'static + Send>(data: T) -> Self {
-        Self {
+    fn new<T: 'static + Send>(data: T) -> Result<Self, IpcError> {
+        Ok(Self {
             data: Box::new(data),
-        }
+        })
     }
 
     fn try_into<T: 'static>(self) -> Result<T, Self> {
@@ -34,21 +58,70 @@ impl Message {
     }
 }
 
+// 消息类型，关闭 alloc 时内联保存，用 TypeId 检查类型
+#[cfg(not(feature = "alloc"))]
+struct Message {
+    type_id: TypeId,
+    data: InlineBox<IPC_MESSAGE_WORDS>,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Message {
+    fn new<T: 'static + Send>(data: T) -> Result<Self, IpcError> {
+        let data = InlineBox::new(data).map_err(|_| IpcError::MessageTooLarge)?;
+        Ok(Self {
+            type_id: TypeId::of::<T>(),
+            data,
+        })
+    }
+
+    fn try_into<T: 'static>(self) -> Result<T, Self> {
+        if self.is_type::<T>() {
+            // SAFETY: 类型与放入时一致
+            Ok(unsafe { self.data.take::<T>() })
+        } else {
+            Err(self)
+        }
+    }
+
+    fn is_type<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+}
+
 // 消息队列结构
 struct MessageQueue {
+    #[cfg(feature = "alloc")]
     queue: VecDeque<Message>,
+    #[cfg(not(feature = "alloc"))]
+    queue: RingBuffer<Message, IPC_QUEUE_CAPACITY>,
     capacity: usize,
-    waiting_senders: Vec<Task>,
-    waiting_receivers: Vec<Task>,
+    /// 创建时槽位的代数
+    generation: u32,
+    waiting_senders: WaiterList,
+    waiting_receivers: WaiterList,
 }
 
 impl MessageQueue {
-    fn new(capacity: usize) -> Self {
+    #[cfg(feature = "alloc")]
+    fn new(capacity: usize, generation: u32) -> Self {
         MessageQueue {
             queue: VecDeque::with_capacity(capacity),
             capacity,
-            waiting_senders: Vec::new(),
-            waiting_receivers: Vec::new(),
+            generation,
+            waiting_senders: WaiterList::new(),
+            waiting_receivers: WaiterList::new(),
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn new(capacity: usize, generation: u32) -> Self {
+        MessageQueue {
+            queue: RingBuffer::new(),
+            capacity: capacity.min(IPC_QUEUE_CAPACITY),
+            generation,
+            waiting_senders: WaiterList::new(),
+            waiting_receivers: WaiterList::new(),
         }
     }
 
@@ -59,54 +132,101 @@ impl MessageQueue {
     fn is_empty(&self) -> bool {
         self.queue.is_empty()
     }
+
+    /// 在队尾加入消息，调用者需要先检查容量
+    #[cfg(feature = "alloc")]
+    fn push(&mut self, message: Message) {
+        self.queue.push_back(message);
+    }
+
+    /// 在队尾加入消息，调用者需要先检查容量（不超过 `IPC_QUEUE_CAPACITY`）
+    #[cfg(not(feature = "alloc"))]
+    fn push(&mut self, message: Message) {
+        let _ = self.queue.push_back(message);
+    }
 }
 
 // 全局IPC管理器
 struct IpcManager {
+    #[cfg(feature = "alloc")]
     queues: Vec<Option<MessageQueue>>,
+    /// 下标 0 不使用（0 表示无效句柄）
+    #[cfg(not(feature = "alloc"))]
+    queues: [Option<MessageQueue>; MAX_IPC_QUEUES + 1],
+    /// 每个槽位的代数，销毁队列时加一（启用 alloc 时槽位不会重用，不需要）
+    #[cfg(not(feature = "alloc"))]
+    generations: [u32; MAX_IPC_QUEUES + 1],
+    #[cfg(feature = "alloc")]
     next_handle: AtomicUsize,
 }
 
 impl IpcManager {
     const fn new() -> Self {
         IpcManager {
+            #[cfg(feature = "alloc")]
             queues: Vec::new(),
+            #[cfg(not(feature = "alloc"))]
+            queues: [const { None }; MAX_IPC_QUEUES + 1],
+            #[cfg(not(feature = "alloc"))]
+            generations: [0; MAX_IPC_QUEUES + 1],
+            #[cfg(feature = "alloc")]
             next_handle: AtomicUsize::new(1), // 从1开始，0表示无效句柄
         }
     }
 
+    /// 关闭 alloc 时使用空闲的槽位，没有空闲槽位时返回无效句柄
+    #[cfg(not(feature = "alloc"))]
     fn create_queue(&mut self, capacity: usize) -> IpcHandle {
-        let handle_id = self.next_handle.fetch_add(1, Ordering::Relaxed);
-        let queue = MessageQueue::new(capacity);
+        match (1..self.queues.len()).find(|&i| self.queues[i].is_none()) {
+            Some(slot) => {
+                let generation = self.generations[slot];
+                self.queues[slot] = Some(MessageQueue::new(capacity, generation));
+                IpcHandle { slot, generation }
+            }
+            // 槽位 0 不使用，是无效句柄
+            None => IpcHandle { slot: 0, generation: 0 },
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn create_queue(&mut self, capacity: usize) -> IpcHandle {
+        let slot = self.next_handle.fetch_add(1, Ordering::Relaxed);
+        let queue = MessageQueue::new(capacity, 0);
 
         // 扩展队列数组以容纳新的句柄
-        while self.queues.len() <= handle_id {
+        while self.queues.len() <= slot {
             self.queues.push(None);
         }
 
-        self.queues[handle_id] = Some(queue);
-        IpcHandle(handle_id)
+        self.queues[slot] = Some(queue);
+        IpcHandle { slot, generation: 0 }
     }
 
-    fn send_message<T: 'static + Send>(&mut self, handle: IpcHandle, data: T) -> Result<(), IpcError> {
-        let queue_opt = self.queues.get_mut(handle.0);
+    /// 查找句柄对应的队列，队列已销毁或槽位已被新队列重用时返回 `None`
+    fn queue_mut(&mut self, handle: IpcHandle) -> Option<&mut MessageQueue> {
+        self.queues
+            .get_mut(handle.slot)?
+            .as_mut()
+            .filter(|queue| queue.generation == handle.generation)
+    }
 
-        match queue_opt {
-            Some(Some(queue)) => {
+    fn send_message<T: 'static + Send>(&mut self, handle: IpcHandle, data: T) -> Result<(), IpcError> {
+        match self.queue_mut(handle) {
+            Some(queue) => {
                 if queue.is_full() {
                     // 队列满，阻塞当前任务
                     let mut current_task = Scheduler::get_current_task();
-                    queue.waiting_senders.push(current_task);
-                    current_task.block(Event::Mq(handle.0));
+                    queue.waiting_senders.push(current_task.get_taskid());
+                    current_task.block(Event::Mq(handle.slot));
 
                     Err(IpcError::QueueFull)
                 } else {
-                    let message = Message::new(data);
-                    queue.queue.push_back(message);
+                    let message = Message::new(data)?;
+                    queue.push(message);
 
                     // 唤醒等待接收的任务
                     if let Some(waiting_task) = queue.waiting_receivers.pop() {
-                        Event::wake_task(Event::Mq(handle.0));
+                        WaitQueue::wake_task(Task::from_id(waiting_task));
                     }
 
                     Ok(())
@@ -117,31 +237,29 @@ impl IpcManager {
     }
 
     fn receive_message<T: 'static>(&mut self, handle: IpcHandle) -> Result<T, IpcError> {
-        let queue_opt = self.queues.get_mut(handle.0);
-
-        match queue_opt {
-            Some(Some(queue)) => {
+        match self.queue_mut(handle) {
+            Some(queue) => {
                 if queue.is_empty() {
                     // 队列空，阻塞当前任务
                     let mut current_task = Scheduler::get_current_task();
-                    queue.waiting_receivers.push(current_task);
-                    current_task.block(Event::Mq(handle.0));
+                    queue.waiting_receivers.push(current_task.get_taskid());
+                    current_task.block(Event::Mq(handle.slot));
 
                     Err(IpcError::QueueEmpty)
                 } else {
                     // 先检查类型是否匹配，不要立即消费消息
-                    if let Some(front_message) = queue.queue.front() {
-                        if !front_message.is_type::<T>() {
-                            return Err(IpcError::TypeMismatch);
-                        }
+                    if let Some(front_message) = queue.queue.front()
+                        && !front_message.is_type::<T>()
+                    {
+                        return Err(IpcError::TypeMismatch);
                     }
 
                     // 类型匹配，现在可以安全地消费消息
                     let message = queue.queue.pop_front().unwrap();
 
                     // 唤醒等待发送的任务
-                    if let Some(_waiting_task) = queue.waiting_senders.pop() {
-                        Event::wake_task(Event::Mq(handle.0));
+                    if let Some(waiting_task) = queue.waiting_senders.pop() {
+                        WaitQueue::wake_task(Task::from_id(waiting_task));
                     }
 
                     match message.try_into::<T>() {
@@ -155,12 +273,15 @@ impl IpcManager {
     }
 
     fn destroy_queue(&mut self, handle: IpcHandle) -> Result<(), IpcError> {
-        if handle.0 < self.queues.len() {
-            self.queues[handle.0] = None;
-            Ok(())
-        } else {
-            Err(IpcError::InvalidHandle)
+        if self.queue_mut(handle).is_none() {
+            return Err(IpcError::InvalidHandle);
         }
+        self.queues[handle.slot] = None;
+        #[cfg(not(feature = "alloc"))]
+        {
+            self.generations[handle.slot] = self.generations[handle.slot].wrapping_add(1);
+        }
+        Ok(())
     }
 }
 
@@ -174,6 +295,8 @@ pub enum IpcError {
     QueueFull,
     QueueEmpty,
     TypeMismatch,
+    /// 消息超出内联存储（关闭 alloc 时）
+    MessageTooLarge,
 }
 
 // 公共API
@@ -181,6 +304,9 @@ pub struct Ipc;
 
 impl Ipc {
     /// 创建一个新的消息队列
+    ///
+    /// 关闭 `alloc` 时容量不超过 `config::IPC_QUEUE_CAPACITY`，
+    /// 已有 `config::MAX_IPC_QUEUES` 个队列时返回无效句柄，之后的操作返回 `IpcError::InvalidHandle`。
     pub fn create_queue(capacity: usize) -> IpcHandle {
         IPC_MANAGER.lock().create_queue(capacity)
     }
@@ -196,6 +322,8 @@ impl Ipc {
     }
 
     /// 销毁消息队列
+    ///
+    /// 销毁后句柄失效，再次使用（包括再次销毁）返回 `IpcError::InvalidHandle`。
     pub fn destroy_queue(handle: IpcHandle) -> Result<(), IpcError> {
         IPC_MANAGER.lock().destroy_queue(handle)
     }
@@ -246,5 +374,9 @@ mod tests {
         assert_eq!(Ipc::receive::<&str>(queue).unwrap(), "hello");
 
         Ipc::destroy_queue(queue).unwrap();
+
+        // 销毁后句柄失效
+        assert_eq!(Ipc::send(queue, 1u32), Err(IpcError::InvalidHandle));
+        assert_eq!(Ipc::destroy_queue(queue), Err(IpcError::InvalidHandle));
     }
 }
@@ -1,12 +1,19 @@
 use crate::config::MAX_MQS;
-use crate::sync::event::Event;
+use crate::sync::wait_queue::WaitQueue;
 use crate::kernel::task::Task;
 use crate::error::{Result, RtosError};
 use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 // 全局变量数组，用于给 mq 分配 id
 static mut MQ_LIST: [Option<QueueInner>; MAX_MQS] = [None; MAX_MQS];
 
+// 每个 mq 槽位的等待队列（Mq 本身可以被移动，等待队列需要固定地址）
+static MQ_WAITERS: [WaitQueue; MAX_MQS] = [const { WaitQueue::new() }; MAX_MQS];
+
+// 每个 mq 槽位的代数，槽位被释放时加一，使之前的 Mq 失效
+static MQ_GENERATIONS: [AtomicU32; MAX_MQS] = [const { AtomicU32::new(0) }; MAX_MQS];
+
 #[derive(Copy, Clone)]
 struct QueueInner {
     id: usize,
@@ -15,6 +22,9 @@ struct QueueInner {
 /// 消息队列
 /// 
 /// 仿照 mutex，实现任务间的阻塞机制
+/// 
+/// 槽位被 `Mq::init` 回收后 Mq 失效，`push` 返回 `false`、`pop` 返回 `None`，
+/// 不会唤醒或释放重用该槽位的新队列。
 pub struct Mq<T, const N: usize> {
     buffer: [MaybeUninit<T>; N],
     head: usize,
@@ -23,6 +33,7 @@ pub struct Mq<T, const N: usize> {
     locked: bool,
     owner: Option<Task>,
     id: usize,
+    generation: u32,
 }
 
 impl<T, const N: usize> Mq<T, N>
@@ -56,6 +67,7 @@ where
             locked: false,
             owner: None,
             id,
+            generation: MQ_GENERATIONS[id].load(Ordering::Acquire),
         })
     }
 
@@ -64,6 +76,7 @@ where
         unsafe {
             for i in 0..MAX_MQS {
                 MQ_LIST[i] = None;
+                MQ_GENERATIONS[i].fetch_add(1, Ordering::AcqRel);
             }
         }
     }
@@ -72,12 +85,14 @@ where
     /// 
     /// # 返回值
     /// - `true` - 成功推送
-    /// - `false` - 队列已满或被锁定
+    /// - `false` - 队列已满、被锁定或已失效
     pub fn push(&mut self, data: T) -> bool {
+        if !self.is_valid() {
+            return false;
+        }
         if self.locked {
-            if let Some(mut owner) = self.owner {
-                owner.block(Event::Mq(self.id));
-            }
+            // 队列被锁定，等待解锁
+            let _ = MQ_WAITERS[self.id].wait(None);
             return false;
         }
 
@@ -96,7 +111,7 @@ where
         self.owner = None;
         self.locked = false;
         // 唤醒被阻塞的 task
-        Event::wake_task(Event::Mq(self.id));
+        MQ_WAITERS[self.id].wake_all();
         true
     }
 
@@ -104,12 +119,14 @@ where
     /// 
     /// # 返回值
     /// - `Some(T)` - 成功弹出数据
-    /// - `None` - 队列为空或被锁定
+    /// - `None` - 队列为空、被锁定或已失效
     pub fn pop(&mut self) -> Option<T> {
+        if !self.is_valid() {
+            return None;
+        }
         if self.locked {
-            if let Some(mut owner) = self.owner {
-                owner.block(Event::Mq(self.id));
-            }
+            // 队列被锁定，等待解锁
+            let _ = MQ_WAITERS[self.id].wait(None);
             return None;
         }
 
@@ -128,7 +145,7 @@ where
         self.owner = None;
         self.locked = false;
         // 唤醒被阻塞的 task
-        Event::wake_task(Event::Mq(self.id));
+        MQ_WAITERS[self.id].wake_all();
         ret
     }
 
@@ -146,6 +163,18 @@ where
     pub fn is_full(&self) -> bool {
         self.count == N
     }
+
+    /// 获取等待队列（内部使用）
+    pub(crate) fn wait_queue(&self) -> &'static WaitQueue {
+        &MQ_WAITERS[self.id]
+    }
+}
+
+impl<T, const N: usize> Mq<T, N> {
+    /// 检查 Mq 是否仍然占有创建时的槽位
+    pub fn is_valid(&self) -> bool {
+        MQ_GENERATIONS[self.id].load(Ordering::Acquire) == self.generation
+    }
 }
 
 impl<T, const N: usize> Drop for Mq<T, N> {
@@ -153,9 +182,13 @@ impl<T, const N: usize> Drop for Mq<T, N> {
     ///
     /// 这允许槽位被后续的 Mq::new() 重用
     fn drop(&mut self) {
+        if !self.is_valid() {
+            return;
+        }
         unsafe {
             MQ_LIST[self.id] = None;
         }
+        MQ_GENERATIONS[self.id].fetch_add(1, Ordering::AcqRel);
     }
 }
 #[cfg(test)]
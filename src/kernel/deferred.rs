@@ -0,0 +1,171 @@
+//! 中断延迟处理
+//!
+//! 中断处理函数不能自旋等待任务持有的锁（包括调度器的锁）：被打断的任务在中断返回前
+//! 无法释放锁，中断会永远等下去。`*_from_isr` 接口因此只修改原子变量，
+//! 把需要加锁的后续工作（合并状态、唤醒等待者）放入这个队列，
+//! 由 SysTick（`Timer::timer_check_and_send_event`）和任务切换（`Scheduler::task_switch`）执行。
+//!
+//! 这两个执行点本身也在中断中（SysTick 和 PendSV），所以登记的调用同样不能自旋等待：
+//! 对象状态、等待队列、任务状态和调度器的锁都只用 `try_lock` 获取
+//! （唤醒任务使用 `Scheduler::try_wake_task`），任何一个锁被占用时把剩下的工作重新登记，
+//! 留到下一次执行。任务上下文中的加锁不需要屏蔽中断。
+//!
+//! 队列是固定容量（`config::ISR_DEFER_QUEUE_SIZE`）的多生产者多消费者环形队列，
+//! 入队和出队只使用原子操作，不会等待其他上下文，可以在任意中断优先级中调用。
+
+use crate::config::ISR_DEFER_QUEUE_SIZE;
+use crate::error::{Result, RtosError};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+const _: () = assert!(ISR_DEFER_QUEUE_SIZE.is_power_of_two(), "ISR_DEFER_QUEUE_SIZE 必须是 2 的幂");
+
+/// 延迟执行的调用
+#[derive(Clone, Copy)]
+pub(crate) struct Deferred {
+    /// 在任务或节拍上下文中执行的函数
+    pub(crate) func: fn([usize; 4]),
+    /// 传给 `func` 的参数
+    pub(crate) args: [usize; 4],
+}
+
+struct Slot {
+    /// 槽位序号：等于入队位置时可写入，等于入队位置 + 1 时可取出
+    seq: AtomicUsize,
+    call: UnsafeCell<Option<Deferred>>,
+}
+
+struct DeferredQueue {
+    slots: [Slot; ISR_DEFER_QUEUE_SIZE],
+    /// 下一个出队位置
+    head: AtomicUsize,
+    /// 下一个入队位置
+    tail: AtomicUsize,
+}
+
+// SAFETY: 槽位的内容只由通过序号取得该槽位的一方访问
+unsafe impl Sync for DeferredQueue {}
+
+static QUEUE: DeferredQueue = DeferredQueue::new();
+
+impl DeferredQueue {
+    const fn new() -> Self {
+        let mut slots = [const { Slot { seq: AtomicUsize::new(0), call: UnsafeCell::new(None) } }; ISR_DEFER_QUEUE_SIZE];
+        let mut i = 0;
+        while i < ISR_DEFER_QUEUE_SIZE {
+            slots[i].seq = AtomicUsize::new(i);
+            i += 1;
+        }
+        Self { slots, head: AtomicUsize::new(0), tail: AtomicUsize::new(0) }
+    }
+
+    fn push(&self, call: Deferred) -> Result<()> {
+        let mut pos = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos % ISR_DEFER_QUEUE_SIZE];
+            let diff = slot.seq.load(Ordering::Acquire).wrapping_sub(pos) as isize;
+            if diff < 0 {
+                // 槽位还没有被取出：队列已满
+                return Err(RtosError::QueueFull);
+            }
+            if diff > 0 {
+                // 其他生产者已经占用了这个位置
+                pos = self.tail.load(Ordering::Relaxed);
+                continue;
+            }
+            match self.tail.compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => {
+                    // SAFETY: 序号保证只有占用该位置的生产者写入槽位
+                    unsafe { *slot.call.get() = Some(call) };
+                    slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                    return Ok(());
+                }
+                Err(current) => pos = current,
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<Deferred> {
+        let mut pos = self.head.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos % ISR_DEFER_QUEUE_SIZE];
+            let diff = slot.seq.load(Ordering::Acquire).wrapping_sub(pos.wrapping_add(1)) as isize;
+            if diff < 0 {
+                // 队列为空，或生产者还没有写完（留到下一次处理）
+                return None;
+            }
+            if diff > 0 {
+                // 其他消费者已经取出了这个位置
+                pos = self.head.load(Ordering::Relaxed);
+                continue;
+            }
+            match self.head.compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => {
+                    // SAFETY: 序号保证只有取得该位置的消费者读取槽位
+                    let call = unsafe { (*slot.call.get()).take() };
+                    slot.seq.store(pos.wrapping_add(ISR_DEFER_QUEUE_SIZE), Ordering::Release);
+                    return call;
+                }
+                Err(current) => pos = current,
+            }
+        }
+    }
+}
+
+/// 在中断中登记一个延迟执行的调用
+///
+/// # 返回值
+/// - `Ok(())`: 登记成功
+/// - `Err(RtosError::QueueFull)`: 队列已满
+pub(crate) fn defer(call: Deferred) -> Result<()> {
+    QUEUE.push(call)
+}
+
+/// 执行已登记的延迟调用（在任务或节拍上下文中调用）
+///
+/// 最多执行一轮队列容量的调用，执行期间中断新登记的调用留到下一次处理。
+pub(crate) fn run_deferred() {
+    for _ in 0..ISR_DEFER_QUEUE_SIZE {
+        match QUEUE.pop() {
+            Some(call) => (call.func)(call.args),
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::sync::Mutex;
+
+    static CALLS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+    fn record(args: [usize; 4]) {
+        CALLS.lock().unwrap().push(args[0]);
+    }
+
+    fn call(value: usize) -> Deferred {
+        Deferred { func: record, args: [value, 0, 0, 0] }
+    }
+
+    #[test]
+    #[serial]
+    fn test_deferred_fifo_and_full() {
+        run_deferred();
+        CALLS.lock().unwrap().clear();
+
+        for i in 0..ISR_DEFER_QUEUE_SIZE {
+            defer(call(i)).unwrap();
+        }
+        assert_eq!(defer(call(99)).err(), Some(RtosError::QueueFull));
+
+        run_deferred();
+        assert_eq!(*CALLS.lock().unwrap(), (0..ISR_DEFER_QUEUE_SIZE).collect::<Vec<_>>());
+
+        // 取出之后槽位可以继续使用
+        defer(call(7)).unwrap();
+        run_deferred();
+        assert_eq!(CALLS.lock().unwrap().last(), Some(&7));
+    }
+}
@@ -2,3 +2,4 @@ pub mod task;
 pub mod scheduler;
 pub mod time;
 pub mod power;
+pub(crate) mod deferred;
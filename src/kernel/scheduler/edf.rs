@@ -0,0 +1,201 @@
+//! 最早截止时间优先（EDF）调度
+//!
+//! 设置了截止时间的任务是 EDF 任务，在优先级调度中位于 [`EDF_BAND`] 优先级：
+//!
+//! ```text
+//!  Critical ┐
+//!           ├─ 固定优先级任务：可以抢占所有 EDF 任务
+//!  EDF_BAND ┼─ EDF 任务：按截止时间排序，截止时间最早的先运行
+//!           │  （同优先级但没有截止时间的固定优先级任务排在 EDF 任务之后）
+//!           ├─ 固定优先级任务：只在没有 EDF 任务就绪时运行
+//!  Idle     ┘
+//! ```
+//!
+//! - 截止时间是绝对时间（SysTick 节拍）
+//! - EDF 仅在启用优先级调度（`Scheduler::enable_priority_scheduling`）时生效，
+//!   默认的 `RoundRobinPolicy` 下截止时间会被记录但不影响调度
+//! - 基础优先级高于 `EDF_BAND` 的任务设置截止时间后保持自身优先级（该优先级内按 FIFO 顺序）
+//! - 清除截止时间后任务回到自己的固定优先级
+//! - 持有锁的 EDF 任务被优先级继承或天花板提升到 `EDF_BAND` 之上时，以提升后的优先级调度
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::task::Task;
+//! use neon_rtos2::kernel::scheduler::Scheduler;
+//!
+//! let control = Task::builder("control")
+//!     .deadline(10)
+//!     .spawn(|_| {})
+//!     .unwrap();
+//!
+//! Scheduler::enable_priority_scheduling();
+//! Scheduler::start();
+//! ```
+
+use crate::kernel::task::{Priority, Task};
+
+/// EDF 任务所在的优先级频带
+///
+/// 高于该优先级的固定优先级任务可以抢占 EDF 任务。
+pub const EDF_BAND: Priority = Priority::High;
+
+/// 任务参与调度的优先级
+///
+/// EDF 任务为 `EDF_BAND`，基础优先级高于频带或被优先级继承、天花板提升到频带之上时
+/// 为自身（提升后的）优先级；其余任务为自身（包括提升后的）优先级。
+pub(crate) fn sched_priority(task: Task) -> Priority {
+    let priority = task.get_priority();
+    if task.get_deadline().is_none() {
+        return priority;
+    }
+    priority.max(EDF_BAND)
+}
+
+/// 截止时间排序键（没有截止时间的任务排在最后）
+pub(crate) fn deadline_key(task: Task) -> usize {
+    task.get_deadline().unwrap_or(usize::MAX)
+}
+
+/// 在 EDF 频带内 `next` 是否应该抢占 `current`
+pub(crate) fn preempts(next: Task, current: Task) -> bool {
+    sched_priority(next) == EDF_BAND
+        && sched_priority(current) == EDF_BAND
+        && deadline_key(next) < deadline_key(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::scheduler::Scheduler;
+    use crate::kernel::task::TaskState;
+    use crate::sync::event::Event;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_edf_runs_earliest_deadline_first() {
+        kernel_init();
+        Task::builder("main").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut late = Task::builder("late").deadline(100).spawn(|_| {}).unwrap();
+        let mut early = Task::builder("early").deadline(50).spawn(|_| {}).unwrap();
+        let normal = Task::new("normal", |_| {}).unwrap();
+
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), early);
+
+        early.block(Event::Signal(1));
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), late);
+
+        // 所有 EDF 任务阻塞后，回到固定优先级任务
+        late.block(Event::Signal(1));
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), normal);
+    }
+
+    #[test]
+    #[serial]
+    fn test_edf_coexists_with_fixed_priority_band() {
+        kernel_init();
+        let edf = Task::builder("edf").deadline(10).spawn(|_| {}).unwrap();
+        let mut critical = Task::builder("critical")
+            .priority(Priority::Critical)
+            .spawn(|_| {})
+            .unwrap();
+        let _same_band = Task::builder("same_band").priority(EDF_BAND).spawn(|_| {}).unwrap();
+
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+
+        // 更高的固定优先级抢占 EDF 任务
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), critical);
+
+        // 同频带没有截止时间的任务排在 EDF 任务之后
+        critical.block(Event::Signal(1));
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), edf);
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_deadline_preempts() {
+        kernel_init();
+        let current = Task::builder("current").deadline(100).spawn(|_| {}).unwrap();
+        let mut other = Task::new("other", |_| {}).unwrap();
+
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+        assert_eq!(sched_priority(other), Priority::Normal);
+
+        other.set_deadline(Some(20));
+        assert_eq!(other.get_deadline(), Some(20));
+        assert_eq!(sched_priority(other), EDF_BAND);
+        Scheduler::preempt_check();
+        assert_eq!(Scheduler::get_current_task(), other);
+        assert_eq!(current.get_state(), TaskState::Ready);
+
+        // 清除截止时间后回到固定优先级
+        other.set_deadline(None);
+        assert_eq!(sched_priority(other), Priority::Normal);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), current);
+    }
+
+    #[test]
+    #[serial]
+    fn test_edf_task_keeps_priority_boost() {
+        use crate::sync::mutex::StaticMutex;
+
+        static CEILING: StaticMutex<u32> = StaticMutex::with_priority_ceiling(0, Priority::Critical);
+
+        kernel_init();
+        let holder = Task::builder("holder").deadline(100).spawn(|_| {}).unwrap();
+        let mut urgent = Task::new("urgent", |_| {}).unwrap();
+
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), holder);
+
+        // 天花板提升到 EDF 频带之上，截止时间更早的 EDF 任务不能抢占
+        let mutex = CEILING.handle();
+        let guard = mutex.lock().unwrap();
+        assert_eq!(sched_priority(holder), Priority::Critical);
+        urgent.set_deadline(Some(10));
+        Scheduler::preempt_check();
+        assert_eq!(Scheduler::get_current_task(), holder);
+
+        // 释放锁后回到频带内，按截止时间调度
+        drop(guard);
+        assert_eq!(sched_priority(holder), EDF_BAND);
+        Scheduler::preempt_check();
+        assert_eq!(Scheduler::get_current_task(), urgent);
+    }
+
+    #[test]
+    #[serial]
+    fn test_deadline_keeps_priority_above_band() {
+        kernel_init();
+        let main = Task::builder("main").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut critical = Task::builder("critical")
+            .priority(Priority::Critical)
+            .spawn(|_| {})
+            .unwrap();
+        let _edf = Task::builder("edf").deadline(10).spawn(|_| {}).unwrap();
+
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), main);
+
+        // 截止时间不会把频带之上的任务降到 EDF_BAND
+        critical.set_deadline(Some(100));
+        assert_eq!(sched_priority(critical), Priority::Critical);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), critical);
+    }
+}
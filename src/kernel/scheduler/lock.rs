@@ -0,0 +1,193 @@
+//! 调度器锁（禁止抢占）
+//!
+//! 持有调度器锁期间 `Scheduler::task_switch` 和 `Scheduler::preempt_check`
+//! 不会切换任务，但中断保持开启，适合比临界区更长、又不能被其他任务打断的代码段。
+//! 锁期间请求的任务切换在锁完全释放时执行。
+//!
+//! - 支持嵌套：`lock` 多少次就需要 `unlock` 多少次
+//! - 持有调度器锁时不能阻塞（等待信号量、延时等），否则当前任务会一直占用 CPU
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::scheduler::Scheduler;
+//!
+//! {
+//!     let _guard = Scheduler::lock_guard();
+//!     // 不会被其他任务抢占，中断仍然可以响应
+//! } // 释放锁，执行期间被推迟的任务切换
+//! ```
+
+use super::Scheduler;
+use crate::hal::trigger_schedule;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// 调度器锁的嵌套深度
+static LOCK_DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+/// 锁期间是否有被推迟的任务切换
+static SWITCH_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// 重置调度器锁（调度器初始化时调用）
+pub(crate) fn reset() {
+    LOCK_DEPTH.store(0, Ordering::Release);
+    SWITCH_PENDING.store(false, Ordering::Release);
+}
+
+/// 调度器锁住时推迟任务切换
+///
+/// 返回 `true` 表示调用者应该放弃本次切换。
+pub(crate) fn defer_switch() -> bool {
+    if LOCK_DEPTH.load(Ordering::Acquire) == 0 {
+        return false;
+    }
+    SWITCH_PENDING.store(true, Ordering::Release);
+    true
+}
+
+/// 调度器锁守卫
+///
+/// 创建时锁住调度器，离开作用域时自动解锁。
+#[must_use = "调度器锁在守卫被丢弃时立即释放"]
+pub struct SchedulerLockGuard {
+    _private: (),
+}
+
+impl Drop for SchedulerLockGuard {
+    fn drop(&mut self) {
+        Scheduler::unlock();
+    }
+}
+
+impl Scheduler {
+    /// 锁住调度器，禁止任务切换
+    ///
+    /// 可以嵌套调用，每次调用都需要对应一次 `unlock`。
+    pub fn lock() {
+        LOCK_DEPTH.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// 解锁调度器
+    ///
+    /// 最外层解锁时，如果锁期间有任务切换被推迟，触发一次调度，
+    /// 由随后的 `task_switch` 保存当前任务的上下文并选择下一个任务。
+    /// 调度器没有被锁住时调用不做任何事。
+    pub fn unlock() {
+        let released = LOCK_DEPTH
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |depth| depth.checked_sub(1))
+            .is_ok_and(|depth| depth == 1);
+
+        if released && SWITCH_PENDING.swap(false, Ordering::AcqRel) {
+            trigger_schedule();
+        }
+    }
+
+    /// 锁住调度器并返回守卫，守卫被丢弃时解锁
+    pub fn lock_guard() -> SchedulerLockGuard {
+        Self::lock();
+        SchedulerLockGuard { _private: () }
+    }
+
+    /// 检查调度器是否被锁住
+    pub fn is_locked() -> bool {
+        LOCK_DEPTH.load(Ordering::Acquire) != 0
+    }
+
+    /// 获取调度器锁的嵌套深度
+    pub fn lock_depth() -> usize {
+        LOCK_DEPTH.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::{Priority, Task};
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_scheduler_lock_defers_switch() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let other = Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        Scheduler::lock();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), main);
+        assert_eq!(other.get_switch_count(), 0);
+        Scheduler::unlock();
+        assert!(!Scheduler::is_locked());
+
+        // 锁释放后可以正常切换
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), other);
+    }
+
+    #[test]
+    #[serial]
+    fn test_scheduler_lock_nested_guard() {
+        kernel_init();
+        let main = Task::builder("main").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut urgent = Task::builder("urgent").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        urgent.suspend().unwrap();
+        Scheduler::start();
+
+        {
+            let _outer = Scheduler::lock_guard();
+            {
+                let _inner = Scheduler::lock_guard();
+                assert_eq!(Scheduler::lock_depth(), 2);
+                urgent.resume().unwrap();
+                assert_eq!(Scheduler::get_current_task(), main);
+            }
+            // 内层解锁后仍然被锁住
+            assert_eq!(Scheduler::lock_depth(), 1);
+            Scheduler::preempt_check();
+            assert_eq!(Scheduler::get_current_task(), main);
+        }
+
+        // 最外层解锁时触发被推迟的抢占，在任务切换时执行
+        assert_eq!(Scheduler::lock_depth(), 0);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), urgent);
+
+        // 未锁住时解锁不做任何事
+        Scheduler::unlock();
+        assert_eq!(Scheduler::lock_depth(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_scheduler_unlock_only_pends_switch() {
+        use crate::hal::test::set_schedule_hook;
+
+        kernel_init();
+        let main = Task::builder("main").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut urgent = Task::builder("urgent").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        urgent.suspend().unwrap();
+        Scheduler::start();
+
+        Scheduler::lock();
+        urgent.resume().unwrap();
+
+        // 解锁只触发调度，当前任务在 task_switch 保存上下文之前保持不变
+        let pended = std::rc::Rc::new(core::cell::Cell::new(false));
+        let flag = pended.clone();
+        set_schedule_hook(Some(Box::new(move || {
+            assert_eq!(Scheduler::get_current_task(), main);
+            flag.set(true);
+        })));
+        Scheduler::unlock();
+        set_schedule_hook(None);
+        assert!(pended.get());
+        assert_eq!(Scheduler::get_current_task(), main);
+
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), urgent);
+    }
+}
@@ -1,11 +1,77 @@
 use crate::kernel::task::{Task, TaskState, Priority};
-use crate::hal::init_idle_task;
-use crate::config::MAX_TASKS;
-use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use crate::hal::{init_idle_task, trigger_schedule};
+use crate::kernel::deferred;
+use crate::config::{DEFAULT_TIME_SLICE, MAX_TASKS, PRIORITY_LEVELS};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use spin::{Once, RwLock, Mutex};
 
-/// 优先级数量（对应 Priority 枚举的变体数）
-const PRIORITY_COUNT: usize = 5;
+mod stats;
+mod edf;
+mod policy;
+mod lock;
+pub use stats::{RuntimeClock, RuntimeStats, TaskRuntime};
+pub use edf::EDF_BAND;
+pub use policy::{PriorityPolicy, RoundRobinPolicy, SchedulingPolicy};
+pub use lock::SchedulerLockGuard;
+
+/// 优先级数量（由 `config::PRIORITY_LEVELS` 配置）
+const PRIORITY_COUNT: usize = PRIORITY_LEVELS;
+
+/// 就绪位图的字数（每个字 32 位）
+const BITMAP_WORDS: usize = PRIORITY_COUNT.div_ceil(32);
+
+/// 就绪位图
+/// 
+/// 第 i 位为 1 表示优先级 i 有就绪任务。
+/// 不超过 32 级时只有一个字，查找最高优先级为 O(1)；
+/// 更多级数时按字从高到低查找，每个字内使用 `leading_zeros()`。
+#[derive(Debug)]
+struct ReadyBitmap<const WORDS: usize> {
+    words: [u32; WORDS],
+}
+
+impl<const WORDS: usize> ReadyBitmap<WORDS> {
+    const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    /// 标记优先级有就绪任务 - O(1)
+    #[inline]
+    fn set(&mut self, prio_idx: usize) {
+        self.words[prio_idx / 32] |= 1 << (prio_idx % 32);
+    }
+
+    /// 清除优先级的就绪标记 - O(1)
+    #[inline]
+    fn clear(&mut self, prio_idx: usize) {
+        self.words[prio_idx / 32] &= !(1 << (prio_idx % 32));
+    }
+
+    /// 查找最高的就绪优先级
+    #[inline]
+    fn highest(&self) -> Option<usize> {
+        self.highest_below(WORDS * 32)
+    }
+
+    /// 查找低于 `limit` 的最高就绪优先级
+    fn highest_below(&self, limit: usize) -> Option<usize> {
+        if limit == 0 {
+            return None;
+        }
+        let top = limit - 1;
+        for word_idx in (0..=top / 32).rev() {
+            let mut word = self.words[word_idx];
+            if word_idx == top / 32 && top % 32 != 31 {
+                // 屏蔽不低于 limit 的位
+                word &= (1 << (top % 32 + 1)) - 1;
+            }
+            if word != 0 {
+                return Some(word_idx * 32 + 31 - word.leading_zeros() as usize);
+            }
+        }
+        None
+    }
+}
 
 /// 就绪队列 - 每个优先级一个队列
 /// 
@@ -44,28 +110,6 @@ impl ReadyQueue {
         true
     }
     
-    /// 出队 - O(1)
-    #[inline]
-    fn pop(&mut self) -> Option<usize> {
-        if self.count == 0 {
-            return None;
-        }
-        let task_id = self.tasks[self.head];
-        self.head = (self.head + 1) % MAX_TASKS;
-        self.count -= 1;
-        Some(task_id)
-    }
-    
-    /// 查看队首元素 - O(1)
-    #[inline]
-    fn peek(&self) -> Option<usize> {
-        if self.count == 0 {
-            None
-        } else {
-            Some(self.tasks[self.head])
-        }
-    }
-    
     /// 从队列中移除指定任务 - O(n)
     /// 
     /// 注意：这个操作较慢，仅在任务阻塞时使用
@@ -127,165 +171,179 @@ impl ReadyQueue {
     }
 }
 
-/// 调度器内部状态
+/// 就绪队列集合
+/// 
+/// 每个优先级一个 FIFO 队列，配合就绪位图查找最高优先级。
+/// 调度策略通过它管理就绪任务，见 [`SchedulingPolicy`]。
 /// 
 /// ## 优化说明
 /// 
 /// 使用位图 + 优先级队列实现 O(1) 调度：
-/// - `ready_bitmap`: 位图标记哪些优先级有就绪任务
-/// - `ready_queues`: 每个优先级一个 FIFO 队列
-/// - 查找最高优先级：O(1) - 使用 `leading_zeros()` 指令
+/// - `bitmap`: 位图标记哪些优先级有就绪任务
+/// - `queues`: 每个优先级一个 FIFO 队列
+/// - 查找最高优先级：O(1)（不超过 32 级时）- 使用 `leading_zeros()` 指令
 /// - 入队/出队：O(1)
-struct SchedulerInner {
-    /// 当前运行的任务
-    current_task: Option<Task>,
-    /// 下一个要运行的任务（用于上下文切换）
-    next_task: Option<Task>,
+#[derive(Debug)]
+pub struct ReadyQueues {
     /// 就绪位图：第 i 位为 1 表示优先级 i 有就绪任务
-    /// 位 0 = Idle, 位 1 = Low, 位 2 = Normal, 位 3 = High, 位 4 = Critical
-    ready_bitmap: u8,
+    bitmap: ReadyBitmap<BITMAP_WORDS>,
     /// 每个优先级的就绪队列
-    ready_queues: [ReadyQueue; PRIORITY_COUNT],
+    queues: [ReadyQueue; PRIORITY_COUNT],
 }
 
-impl SchedulerInner {
+impl ReadyQueues {
     const fn new() -> Self {
         Self {
-            current_task: None,
-            next_task: None,
-            ready_bitmap: 0,
-            ready_queues: [
-                ReadyQueue::new(),
-                ReadyQueue::new(),
-                ReadyQueue::new(),
-                ReadyQueue::new(),
-                ReadyQueue::new(),
-            ],
+            bitmap: ReadyBitmap::new(),
+            queues: [const { ReadyQueue::new() }; PRIORITY_COUNT],
         }
     }
     
-    /// 将任务加入就绪队列 - O(1)
-    fn enqueue_ready(&mut self, task_id: usize, priority: Priority) {
+    /// 将任务加入指定优先级队列的尾部 - O(1)
+    /// 
+    /// 任务已在该队列中时不会重复入队。
+    pub fn push(&mut self, task: Task, priority: Priority) {
         let prio_idx = priority.as_u8() as usize;
         if prio_idx < PRIORITY_COUNT {
             // 避免重复入队
-            if !self.ready_queues[prio_idx].contains(task_id) {
-                self.ready_queues[prio_idx].push(task_id);
+            if !self.queues[prio_idx].contains(task.get_taskid()) {
+                self.queues[prio_idx].push(task.get_taskid());
                 // 设置位图
-                self.ready_bitmap |= 1 << prio_idx;
+                self.bitmap.set(prio_idx);
             }
         }
     }
     
-    /// 从就绪队列移除任务 - O(n)
-    fn dequeue_task(&mut self, task_id: usize, priority: Priority) {
+    /// 从指定优先级队列移除任务 - O(n)
+    pub fn remove_at(&mut self, task: Task, priority: Priority) {
         let prio_idx = priority.as_u8() as usize;
         if prio_idx < PRIORITY_COUNT {
-            self.ready_queues[prio_idx].remove(task_id);
+            self.queues[prio_idx].remove(task.get_taskid());
             // 如果队列为空，清除位图
-            if self.ready_queues[prio_idx].is_empty() {
-                self.ready_bitmap &= !(1 << prio_idx);
+            if self.queues[prio_idx].is_empty() {
+                self.bitmap.clear(prio_idx);
             }
         }
     }
     
     /// 从所有队列中移除任务（当不知道优先级时使用）
-    fn remove_task_from_all_queues(&mut self, task_id: usize) {
+    /// 
+    /// 返回任务是否在某个队列中
+    pub fn remove(&mut self, task: Task) -> bool {
         for prio_idx in 0..PRIORITY_COUNT {
-            if self.ready_queues[prio_idx].remove(task_id) {
-                if self.ready_queues[prio_idx].is_empty() {
-                    self.ready_bitmap &= !(1 << prio_idx);
+            if self.queues[prio_idx].remove(task.get_taskid()) {
+                if self.queues[prio_idx].is_empty() {
+                    self.bitmap.clear(prio_idx);
                 }
-                break;
+                return true;
             }
         }
+        false
+    }
+
+    /// 检查任务是否在某个队列中
+    pub fn contains(&self, task: Task) -> bool {
+        self.queues.iter().any(|queue| queue.contains(task.get_taskid()))
     }
     
-    /// 获取最高优先级的就绪任务 - O(1) 平均，最坏 O(n)
+    /// 取出最高优先级的就绪任务 - O(1) 平均，最坏 O(n)
     /// 
-    /// 使用位图快速查找最高优先级。
+    /// 使用位图快速查找最高优先级，并将选中的任务移出就绪队列。
     /// 会验证任务状态，跳过已阻塞的任务。
-    fn get_highest_priority_ready_task(&mut self) -> Option<usize> {
-        // 从最高优先级开始查找
-        for prio_idx in (0..PRIORITY_COUNT).rev() {
-            if (self.ready_bitmap & (1 << prio_idx)) == 0 {
-                continue;
-            }
-            
-            // 遍历该优先级队列，找到真正就绪的任务
-            loop {
-                if let Some(task_id) = self.ready_queues[prio_idx].pop() {
-                    // 验证任务状态
-                    let task = Task(task_id);
-                    if task.get_state() == TaskState::Ready {
-                        // 如果队列为空，清除位图
-                        if self.ready_queues[prio_idx].is_empty() {
-                            self.ready_bitmap &= !(1 << prio_idx);
-                        }
-                        return Some(task_id);
-                    }
-                    // 任务不是就绪状态，继续查找下一个
-                } else {
-                    // 队列为空，清除位图
-                    self.ready_bitmap &= !(1 << prio_idx);
-                    break;
-                }
-            }
-        }
-        
-        None
+    pub fn pop_highest(&mut self) -> Option<Task> {
+        let (task, priority) = self.peek_highest()?;
+        self.remove_at(task, priority);
+        Some(task)
     }
     
     /// 查看最高优先级的就绪任务（不移除）
     /// 
     /// 会验证任务状态，跳过已阻塞的任务。
-    fn peek_highest_priority_ready_task(&self) -> Option<(usize, Priority)> {
+    /// 在 EDF 频带内选择截止时间最早的就绪任务，截止时间相同时按 FIFO 顺序。
+    pub fn peek_highest(&self) -> Option<(Task, Priority)> {
         // 从最高优先级开始查找
-        for prio_idx in (0..PRIORITY_COUNT).rev() {
-            if (self.ready_bitmap & (1 << prio_idx)) == 0 {
-                continue;
-            }
+        let mut next = self.bitmap.highest();
+        while let Some(prio_idx) = next {
+            next = self.bitmap.highest_below(prio_idx);
+            let priority = Priority::saturating_from_u8(prio_idx as u8);
             
             // 遍历该优先级队列，找到真正就绪的任务
-            let queue = &self.ready_queues[prio_idx];
-            let mut idx = queue.head;
-            for _ in 0..queue.count {
-                let task_id = queue.tasks[idx];
-                let task = Task(task_id);
-                if task.get_state() == TaskState::Ready {
-                    return Some((task_id, Priority::from_u8(prio_idx as u8).unwrap_or(Priority::Normal)));
-                }
-                idx = (idx + 1) % MAX_TASKS;
+            let mut ready = self.iter_at(priority).filter(|task| task.get_state() == TaskState::Ready);
+            let found = if priority == EDF_BAND {
+                ready.min_by_key(|task| edf::deadline_key(*task))
+            } else {
+                ready.next()
+            };
+            if let Some(task) = found {
+                return Some((task, priority));
             }
         }
         
         None
     }
+
+    /// 按 FIFO 顺序遍历指定优先级队列中的任务（包括尚未移出的非就绪任务）
+    pub fn iter_at(&self, priority: Priority) -> impl Iterator<Item = Task> + '_ {
+        self.queues.get(priority.as_u8() as usize).into_iter().flat_map(|queue| {
+            (0..queue.count).map(move |i| Task::from_id(queue.tasks[(queue.head + i) % MAX_TASKS]))
+        })
+    }
     
-    /// 获取指定优先级的就绪任务数量
-    fn ready_count_at_priority(&self, priority: Priority) -> usize {
+    /// 获取指定优先级队列中的任务数量
+    pub fn len_at(&self, priority: Priority) -> usize {
         let prio_idx = priority.as_u8() as usize;
         if prio_idx < PRIORITY_COUNT {
-            self.ready_queues[prio_idx].len()
+            self.queues[prio_idx].len()
         } else {
             0
         }
     }
     
-    /// 获取总就绪任务数量
-    fn total_ready_count(&self) -> usize {
-        self.ready_queues.iter().map(|q| q.len()).sum()
+    /// 获取所有队列中的任务数量
+    pub fn len(&self) -> usize {
+        self.queues.iter().map(|q| q.len()).sum()
+    }
+
+    /// 检查所有队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 调度器内部状态
+struct SchedulerInner {
+    /// 当前运行的任务
+    current_task: Option<Task>,
+    /// 就绪队列
+    ready: ReadyQueues,
+}
+
+impl SchedulerInner {
+    const fn new() -> Self {
+        Self {
+            current_task: None,
+            ready: ReadyQueues::new(),
+        }
     }
 }
 
 /// 全局调度器状态
 static SCHEDULER_INNER: Once<Mutex<SchedulerInner>> = Once::new();
 static SCHEDULER_RUNNING: AtomicBool = AtomicBool::new(false);
-static SCHEDULER_USE_PRIORITY: AtomicBool = AtomicBool::new(false);
+
+/// 当前的调度策略
+static SCHEDULING_POLICY: RwLock<&'static dyn SchedulingPolicy> = RwLock::new(&RoundRobinPolicy);
 
 /// 当前任务 ID（原子变量，用于快速访问）
 static CURRENT_TASK_ID: AtomicUsize = AtomicUsize::new(0);
 
+/// 同优先级轮转的全局时间片（节拍），0 表示不轮转
+static TIME_SLICE: AtomicUsize = AtomicUsize::new(DEFAULT_TIME_SLICE);
+/// 当前任务在本时间片内已运行的节拍数
+static SLICE_USED: AtomicUsize = AtomicUsize::new(0);
+/// 当前任务的时间片是否已用完
+static SLICE_EXPIRED: AtomicBool = AtomicBool::new(false);
+
 fn get_scheduler_inner() -> &'static Mutex<SchedulerInner> {
     SCHEDULER_INNER.call_once(|| Mutex::new(SchedulerInner::new()))
 }
@@ -300,14 +358,39 @@ impl Scheduler {
             *inner = SchedulerInner::new();
         }
         SCHEDULER_RUNNING.store(false, Ordering::Release);
-        SCHEDULER_USE_PRIORITY.store(false, Ordering::Release);
+        *SCHEDULING_POLICY.write() = &RoundRobinPolicy;
         CURRENT_TASK_ID.store(0, Ordering::Release);
+        TIME_SLICE.store(DEFAULT_TIME_SLICE, Ordering::Release);
+        Self::reset_time_slice();
+        stats::restart();
+        lock::reset();
         
         init_idle_task();
     }
 
+    /// 设置调度策略
+    ///
+    /// 所有策略共享同一组就绪队列，可以在运行时切换。
+    /// 默认使用 [`RoundRobinPolicy`]。
+    ///
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::scheduler::{PriorityPolicy, Scheduler};
+    ///
+    /// Scheduler::set_policy(&PriorityPolicy);
+    /// ```
+    pub fn set_policy(policy: &'static dyn SchedulingPolicy) {
+        *SCHEDULING_POLICY.write() = policy;
+    }
+
+    /// 获取当前的调度策略
+    pub fn policy() -> &'static dyn SchedulingPolicy {
+        *SCHEDULING_POLICY.read()
+    }
+
     /// 启用优先级调度
     ///
+    /// 等价于 `Scheduler::set_policy(&PriorityPolicy)`，
     /// 启用后，调度器会优先选择优先级最高的就绪任务运行。
     /// 
     /// ## 性能说明
@@ -316,19 +399,74 @@ impl Scheduler {
     /// - 查找最高优先级任务：O(1)
     /// - 任务入队/出队：O(1)
     pub fn enable_priority_scheduling() {
-        SCHEDULER_USE_PRIORITY.store(true, Ordering::Release);
+        Self::set_policy(&PriorityPolicy);
     }
 
     /// 禁用优先级调度
     ///
+    /// 等价于 `Scheduler::set_policy(&RoundRobinPolicy)`，
     /// 禁用后，调度器使用轮转调度算法。
     pub fn disable_priority_scheduling() {
-        SCHEDULER_USE_PRIORITY.store(false, Ordering::Release);
+        Self::set_policy(&RoundRobinPolicy);
     }
 
-    /// 检查是否启用优先级调度
+    /// 检查当前是否使用优先级调度策略
     pub fn is_priority_scheduling_enabled() -> bool {
-        SCHEDULER_USE_PRIORITY.load(Ordering::Acquire)
+        Self::policy().name() == PriorityPolicy::NAME
+    }
+
+    /// 设置同优先级轮转的全局时间片
+    ///
+    /// 启用优先级调度时，任务连续运行 `ticks` 个节拍后，
+    /// 如果有同优先级的就绪任务，当前任务会被轮转到其就绪队列尾部。
+    /// 任务可以通过 `Task::set_time_slice` 设置自己的时间片。
+    ///
+    /// # 参数
+    /// - `ticks`: 时间片长度（节拍），0 表示关闭同优先级轮转
+    pub fn set_time_slice(ticks: usize) {
+        TIME_SLICE.store(ticks, Ordering::Release);
+    }
+
+    /// 获取全局时间片（节拍）
+    pub fn time_slice() -> usize {
+        TIME_SLICE.load(Ordering::Acquire)
+    }
+
+    /// 当前任务的时间片是否已用完
+    pub fn time_slice_expired() -> bool {
+        SLICE_EXPIRED.load(Ordering::Acquire)
+    }
+
+    /// SysTick 处理：调用调度策略的 `tick`
+    ///
+    /// 在 SysTick 中断中调用。优先级策略在这里统计当前任务的时间片，
+    /// 时间片用完后，下一次 `task_switch` 会在有同优先级就绪任务时切换过去。
+    pub fn tick() {
+        if !Self::is_running() {
+            return;
+        }
+        Self::policy().tick(Self::get_current_task());
+    }
+
+    /// 统计当前任务用掉的一个节拍，时间片用完时标记为过期
+    fn consume_time_slice(current: Task) {
+        let quantum = match current.get_time_slice() {
+            0 => Self::time_slice(),
+            ticks => ticks,
+        };
+        if quantum == 0 {
+            return;
+        }
+
+        if SLICE_USED.fetch_add(1, Ordering::AcqRel) + 1 >= quantum {
+            SLICE_EXPIRED.store(true, Ordering::Release);
+        }
+    }
+
+    /// 为当前任务开始新的时间片
+    fn reset_time_slice() {
+        SLICE_USED.store(0, Ordering::Release);
+        SLICE_EXPIRED.store(false, Ordering::Release);
     }
 
     /// 检查调度器是否正在运行
@@ -343,34 +481,86 @@ impl Scheduler {
     /// ## 性能
     /// - 时间复杂度：O(1)
     pub fn enqueue_ready_task(task: &Task) {
+        let policy = Self::policy();
         let mut inner = get_scheduler_inner().lock();
-        inner.enqueue_ready(task.get_taskid(), task.get_priority());
+        policy.enqueue(&mut inner.ready, *task);
     }
     
+    /// 唤醒阻塞的任务并放入就绪队列，不等待任何锁
+    ///
+    /// 供中断延迟处理使用：被打断的任务可能持有调度器或任务状态的锁，
+    /// 任何一个锁被占用时不做修改并返回 `None`，由调用者稍后重试。
+    ///
+    /// # 返回值
+    /// - `Some(true)`: 任务已放入就绪队列
+    /// - `Some(false)`: 任务不处于阻塞状态
+    /// - `None`: 锁被占用
+    pub(crate) fn try_wake_task(task: &Task) -> Option<bool> {
+        let policy = *SCHEDULING_POLICY.try_read()?;
+        let mut inner = get_scheduler_inner().try_lock()?;
+        let woken = task.try_ready()?;
+        if woken {
+            policy.enqueue(&mut inner.ready, *task);
+        }
+        Some(woken)
+    }
+
     /// 从就绪队列移除任务
     /// 
     /// 当任务进入阻塞状态时调用。
     /// 
     /// ## 性能
-    /// - 时间复杂度：O(n)，n 为该优先级队列中的任务数
+    /// - 时间复杂度：O(n)，n 为就绪队列中的任务数
     pub fn dequeue_task(task: &Task) {
+        let policy = Self::policy();
         let mut inner = get_scheduler_inner().lock();
-        inner.dequeue_task(task.get_taskid(), task.get_priority());
+        policy.dequeue(&mut inner.ready, *task);
+    }
+
+    /// 从所有优先级的就绪队列中移除任务（内部使用）
+    /// 
+    /// 用于删除任务：不依赖任务当前记录的优先级。
+    pub(crate) fn remove_task(task: &Task) {
+        let policy = Self::policy();
+        let mut inner = get_scheduler_inner().lock();
+        policy.dequeue(&mut inner.ready, *task);
+        inner.ready.remove(*task);
+    }
+
+    /// 修改影响调度优先级的任务属性，并把任务移到对应的就绪队列（内部使用）
+    /// 
+    /// `update` 在持有调度器锁时执行。
+    pub(crate) fn requeue_task(task: &Task, update: impl FnOnce()) {
+        let policy = Self::policy();
+        let mut inner = get_scheduler_inner().lock();
+        let queued = inner.ready.contains(*task);
+        policy.dequeue(&mut inner.ready, *task);
+        update();
+        if queued || task.get_state() == TaskState::Ready {
+            policy.enqueue(&mut inner.ready, *task);
+        }
     }
 
     /// 基于优先级的调度 - O(1)
     ///
-    /// 选择优先级最高的就绪任务运行。
-    /// 如果有多个相同优先级的任务，按 FIFO 顺序选择。
+    /// 不论当前注册的是哪个调度策略，都使用 [`PriorityPolicy`] 调度一次：
+    /// 选择优先级最高的就绪任务运行，如果有多个相同优先级的任务，按 FIFO 顺序选择。
     /// 
     /// ## 优化说明
     /// 
     /// 使用位图快速查找最高优先级：
-    /// - `ready_bitmap` 的每一位表示对应优先级是否有就绪任务
+    /// - 位图的每一位表示对应优先级是否有就绪任务
     /// - 使用 `leading_zeros()` 指令在 O(1) 时间内找到最高优先级
     pub fn schedule_by_priority() {
+        Self::reschedule(&PriorityPolicy);
+    }
+
+    /// 由调度策略选择下一个任务并切换
+    /// 
+    /// 调度器被锁住时推迟到解锁时进行。
+    fn reschedule(policy: &dyn SchedulingPolicy) {
         // 如果调度器未运行，直接返回
-        if !Self::is_running() {
+        if !Self::is_running() || lock::defer_switch() {
             return;
         }
 
@@ -380,75 +570,94 @@ impl Scheduler {
             Some(task) => task,
             None => return,
         };
-        
-        let current_priority = current_task.get_priority();
         let current_state = current_task.get_state();
 
-        // 查看最高优先级的就绪任务
-        if let Some((next_task_id, next_priority)) = inner.peek_highest_priority_ready_task() {
-            // 只有当找到的任务优先级更高，或者当前任务不是运行状态时才切换
-            if next_task_id != current_task.get_taskid() && 
-               (next_priority > current_priority || current_state != TaskState::Running) {
-                // 从队列中取出任务
-                let next_task_id = inner.get_highest_priority_ready_task().unwrap();
-                let mut next_task = Task(next_task_id);
-                
-                // 如果当前任务正在运行，将其设为就绪并重新入队
+        match policy.pick_next(&mut inner.ready, current_task) {
+            Some(mut next_task) if next_task != current_task => {
+                // 如果当前任务正在运行，将其设为就绪并移到队列尾部
                 if current_state == TaskState::Running {
                     let mut current = current_task;
                     current.ready();
-                    inner.enqueue_ready(current.get_taskid(), current_priority);
+                    policy.dequeue(&mut inner.ready, current);
+                    policy.enqueue(&mut inner.ready, current);
                 }
 
                 // 运行下一个任务
                 next_task.run();
                 inner.current_task = Some(next_task);
-                CURRENT_TASK_ID.store(next_task_id, Ordering::Release);
-                return;
+                CURRENT_TASK_ID.store(next_task.get_taskid(), Ordering::Release);
+                Self::reset_time_slice();
+            }
+            _ => {
+                // 当前任务继续运行，时间片已用完时开始新的时间片
+                if Self::time_slice_expired() {
+                    Self::reset_time_slice();
+                }
+                if current_state == TaskState::Ready {
+                    let mut current = current_task;
+                    current.run();
+                    inner.current_task = Some(current);
+                }
             }
-        }
-        
-        // 没找到更高优先级的任务
-        if current_state == TaskState::Ready {
-            let mut current = current_task;
-            current.run();
-            inner.current_task = Some(current);
         }
     }
 
-    /// 抢占式调度检查 - O(1)
+    /// 抢占式调度检查
     ///
-    /// 如果有更高优先级的任务就绪，触发任务切换。
-    /// 通常在 SysTick 中断或任务唤醒时调用。
+    /// 如果调度策略认为应该抢占当前任务（如优先级策略中有更高优先级的任务就绪），
+    /// 立即切换当前任务。调度器被锁住时切换推迟到 `Scheduler::unlock`。
+    ///
+    /// 切换在调用处完成，只能在保存任务上下文的调度上下文中调用；
+    /// 任务中唤醒其他任务后使用 [`request_preempt`](Self::request_preempt)。
     /// 
     /// ## 性能
-    /// - 时间复杂度：O(1) - 只需检查位图
+    /// - 优先级策略：O(1) - 只需检查位图
     pub fn preempt_check() {
-        // 如果调度器未运行或未启用优先级调度，直接返回
-        if !Self::is_running() || !Self::is_priority_scheduling_enabled() {
+        // 如果调度器未运行，直接返回
+        if !Self::is_running() {
             return;
         }
 
+        let policy = Self::policy();
         let inner = get_scheduler_inner().lock();
         
         let current_task = match inner.current_task {
             Some(task) => task,
             None => return,
         };
-        let current_priority = current_task.get_priority();
 
-        // O(1) 检查是否有更高优先级的就绪任务
-        if let Some((_, highest_priority)) = inner.peek_highest_priority_ready_task() {
-            if highest_priority > current_priority {
-                drop(inner); // 释放锁
-                Self::schedule_by_priority();
+        if policy.preempt(&inner.ready, current_task) {
+            drop(inner); // 释放锁
+            Self::reschedule(policy);
+        }
+    }
+
+    /// 请求抢占（任务上下文）
+    ///
+    /// 与 `preempt_check` 使用相同的判断，但不在调用处切换：需要抢占时只触发调度，
+    /// 由随后的 `task_switch` 保存当前任务的上下文并选择下一个任务。
+    /// 调度器被锁住时切换推迟到 `Scheduler::unlock`。
+    pub fn request_preempt() {
+        if !Self::is_running() {
+            return;
+        }
+
+        let should_preempt = {
+            let inner = get_scheduler_inner().lock();
+            match inner.current_task {
+                Some(current) => Self::policy().preempt(&inner.ready, current),
+                None => false,
             }
+        };
+
+        if should_preempt && !lock::defer_switch() {
+            trigger_schedule();
         }
     }
 
     /// 任务切换
     ///
-    /// 根据调度策略选择下一个任务运行。
+    /// 由当前注册的调度策略选择下一个任务运行，调度器被锁住时推迟到 `Scheduler::unlock`。
     /// - 优先级调度：选择最高优先级的就绪任务
     /// - 轮转调度：选择下一个就绪任务
     pub fn task_switch() {
@@ -457,108 +666,44 @@ impl Scheduler {
             return;
         }
 
-        // 如果启用了优先级调度，使用优先级调度算法
-        if Self::is_priority_scheduling_enabled() {
-            Self::schedule_by_priority();
-            return;
-        }
+        // 中断推迟的唤醒先放回就绪队列，参与这一次选择
+        deferred::run_deferred();
 
-        // 否则使用轮转调度算法（使用快照迭代器减少锁竞争）
-        Self::round_robin_schedule();
-    }
-    
-    /// 轮转调度算法
-    /// 
-    /// 使用快照迭代器减少锁持有时间
-    fn round_robin_schedule() {
-        let current_task_id = CURRENT_TASK_ID.load(Ordering::Acquire);
-        
-        // 使用快照迭代器获取任务状态（只获取一次锁）
-        let snapshot_iter = Task::snapshot_iter();
-        
-        // 查找下一个就绪任务
-        let mut next_task_id: Option<usize> = None;
-        let mut current_task_ready = false;
-        
-        // 从当前任务之后开始查找
-        for snapshot in snapshot_iter {
-            if snapshot.task_id == current_task_id {
-                current_task_ready = snapshot.state == TaskState::Ready;
-                continue;
-            }
-            if snapshot.state == TaskState::Ready && snapshot.task_id > current_task_id && next_task_id.is_none() {
-                next_task_id = Some(snapshot.task_id);
-                break;
-            }
-        }
-        
-        // 如果没找到，从头开始查找
-        if next_task_id.is_none() {
-            for snapshot in Task::snapshot_iter() {
-                if snapshot.task_id == current_task_id {
-                    continue;
-                }
-                if snapshot.state == TaskState::Ready && snapshot.task_id < current_task_id {
-                    next_task_id = Some(snapshot.task_id);
-                    break;
-                }
-            }
-        }
+        // 检查即将被切出的任务是否发生栈溢出，并统计其运行时间
+        let outgoing = Self::get_current_task();
+        outgoing.check_stack();
+        stats::charge(outgoing);
 
-        // 执行任务切换
-        let mut inner = get_scheduler_inner().lock();
-        let current_task = match inner.current_task {
-            Some(task) => task,
-            None => return,
-        };
-        
-        match (next_task_id, current_task.get_state()) {
-            // 找到了下一个准备好的任务
-            (Some(next_id), _) => {
-                let mut next = Task(next_id);
-                
-                // 如果当前任务正在运行，将其设为就绪状态
-                if current_task.get_state() == TaskState::Running {
-                    let mut current = current_task;
-                    current.ready();
-                }
-
-                // 运行下一个任务
-                next.run();
-                inner.current_task = Some(next);
-                CURRENT_TASK_ID.store(next_id, Ordering::Release);
-            }
-
-            // 没找到其他任务，但当前任务就绪
-            (None, TaskState::Ready) => {
-                let mut current = current_task;
-                current.run();
-                inner.current_task = Some(current);
-            }
+        Self::reschedule(Self::policy());
 
-            // 其他情况保持不变
-            _ => {}
+        let incoming = Self::get_current_task();
+        if incoming != outgoing {
+            incoming.record_switch_in();
         }
     }
 
     pub fn start() {
         // 初始化就绪队列：将所有就绪任务加入队列
         {
+            let policy = Self::policy();
             let mut inner = get_scheduler_inner().lock();
             
             // 遍历所有任务，将就绪任务加入队列
             for snapshot in Task::snapshot_iter() {
                 if snapshot.state == TaskState::Ready {
-                    inner.enqueue_ready(snapshot.task_id, snapshot.priority);
+                    policy.enqueue(&mut inner.ready, Task::from_id(snapshot.task_id));
                 }
             }
             
             // 设置第一个任务为当前任务
-            inner.current_task = Some(Task(0));
+            inner.current_task = Some(Task::from_id(0));
         }
         
-        Task(0).run();
+        Task::from_id(0).run();
+        Task::from_id(0).record_switch_in();
         CURRENT_TASK_ID.store(0, Ordering::Release);
+        Self::reset_time_slice();
+        stats::restart();
         SCHEDULER_RUNNING.store(true, Ordering::Release);
         
         // 触发当前架构的任务切换
@@ -574,7 +719,7 @@ impl Scheduler {
     /// 
     /// 使用原子变量快速获取当前任务 ID，避免锁竞争
     pub fn get_current_task() -> Task {
-        Task(CURRENT_TASK_ID.load(Ordering::Acquire))
+        Task::from_id(CURRENT_TASK_ID.load(Ordering::Acquire))
     }
     
     /// 获取当前任务（从调度器内部状态）
@@ -588,14 +733,10 @@ impl Scheduler {
     pub fn ready_task_stats() -> ReadyTaskStats {
         let inner = get_scheduler_inner().lock();
         ReadyTaskStats {
-            total: inner.total_ready_count(),
-            by_priority: [
-                inner.ready_count_at_priority(Priority::Idle),
-                inner.ready_count_at_priority(Priority::Low),
-                inner.ready_count_at_priority(Priority::Normal),
-                inner.ready_count_at_priority(Priority::High),
-                inner.ready_count_at_priority(Priority::Critical),
-            ],
+            total: inner.ready.len(),
+            by_priority: core::array::from_fn(|prio_idx| {
+                inner.ready.len_at(Priority::saturating_from_u8(prio_idx as u8))
+            }),
         }
     }
 }
@@ -605,7 +746,7 @@ impl Scheduler {
 pub struct ReadyTaskStats {
     /// 总就绪任务数
     pub total: usize,
-    /// 各优先级的就绪任务数 [Idle, Low, Normal, High, Critical]
+    /// 各优先级的就绪任务数（下标为优先级数值）
     pub by_priority: [usize; PRIORITY_COUNT],
 }
 
@@ -1033,4 +1174,152 @@ mod tests {
         // 轮转调度下，应该切换到下一个任务
         assert_eq!(Scheduler::get_current_task().get_taskid(), task2.get_taskid());
     }
+
+    #[test]
+    #[serial]
+    fn test_time_slice_rotates_same_priority() {
+        kernel_init();
+        
+        let task1 = Task::new("normal1", |_| {}).unwrap();
+        let task2 = Task::new("normal2", |_| {}).unwrap();
+        let task3 = Task::new("normal3", |_| {}).unwrap();
+        
+        Scheduler::enable_priority_scheduling();
+        Scheduler::set_time_slice(2);
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), task1);
+        
+        // 时间片未用完时不切换
+        Scheduler::tick();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task1);
+        
+        // 时间片用完后依次轮转，当前任务移到队列尾部
+        Scheduler::tick();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task2);
+        
+        Scheduler::tick();
+        Scheduler::tick();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task3);
+        
+        Scheduler::tick();
+        Scheduler::tick();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task1);
+        assert_eq!(task3.get_state(), TaskState::Ready);
+    }
+
+    #[test]
+    #[serial]
+    fn test_time_slice_per_task_and_disabled() {
+        kernel_init();
+        
+        let task1 = Task::builder("short")
+            .time_slice(1)
+            .spawn(|_| {})
+            .unwrap();
+        let task2 = Task::new("normal", |_| {}).unwrap();
+        let _low = Task::builder("low")
+            .priority(Priority::Low)
+            .spawn(|_| {})
+            .unwrap();
+        
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+        assert_eq!(task1.get_time_slice(), 1);
+        
+        // 使用任务自己的时间片
+        Scheduler::tick();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task2);
+        
+        // 关闭轮转后不再切换，低优先级任务也不会被选中
+        Scheduler::set_time_slice(0);
+        for _ in 0..crate::config::DEFAULT_TIME_SLICE {
+            Scheduler::tick();
+        }
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), task2);
+    }
+
+    #[test]
+    fn test_ready_bitmap_multi_word() {
+        // 256 级优先级使用 8 个字
+        let mut bitmap = ReadyBitmap::<8>::new();
+        assert_eq!(bitmap.highest(), None);
+        
+        bitmap.set(3);
+        bitmap.set(40);
+        bitmap.set(255);
+        assert_eq!(bitmap.highest(), Some(255));
+        assert_eq!(bitmap.highest_below(255), Some(40));
+        assert_eq!(bitmap.highest_below(40), Some(3));
+        assert_eq!(bitmap.highest_below(32), Some(3));
+        assert_eq!(bitmap.highest_below(3), None);
+        
+        bitmap.clear(255);
+        assert_eq!(bitmap.highest(), Some(40));
+    }
+
+    #[test]
+    #[serial]
+    fn test_priority_scheduling_numeric_levels() {
+        kernel_init();
+        
+        let low = Task::builder("low")
+            .priority(Priority::from_u8(1).unwrap())
+            .spawn(|_| {})
+            .unwrap();
+        let top = Task::builder("top")
+            .priority(Priority::MAX)
+            .spawn(|_| {})
+            .unwrap();
+        
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), low);
+        
+        let stats = Scheduler::ready_task_stats();
+        assert_eq!(stats.by_priority.len(), Priority::LEVELS);
+        assert_eq!(stats.by_priority[Priority::MAX.as_u8() as usize], 1);
+        
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), top);
+    }
+
+    #[test]
+    #[serial]
+    fn test_try_wake_task_does_not_wait_for_locks() {
+        kernel_init();
+        Task::builder("main").priority(Priority::Normal).spawn(|_| {}).unwrap();
+        let mut worker = Task::builder("worker").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+
+        worker.block(Event::Signal(1));
+        Scheduler::dequeue_task(&worker);
+        let queued = || Scheduler::ready_task_stats().by_priority[Priority::High.as_u8() as usize];
+        assert_eq!(queued(), 0);
+
+        // 被打断的任务持有调度器或策略的锁时不做任何修改
+        {
+            let _inner = get_scheduler_inner().lock();
+            assert_eq!(Scheduler::try_wake_task(&worker), None);
+        }
+        {
+            let _policy = SCHEDULING_POLICY.write();
+            assert_eq!(Scheduler::try_wake_task(&worker), None);
+        }
+        assert_eq!(worker.get_state(), TaskState::Blocked(Event::Signal(1)));
+        assert_eq!(queued(), 0);
+
+        // 锁释放后重试成功，不在阻塞状态的任务不会被重复放入就绪队列
+        assert_eq!(Scheduler::try_wake_task(&worker), Some(true));
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert_eq!(queued(), 1);
+        assert_eq!(Scheduler::try_wake_task(&worker), Some(false));
+        assert_eq!(queued(), 1);
+    }
 }
@@ -0,0 +1,250 @@
+//! 可插拔的调度策略
+//!
+//! 调度器负责任务切换的公共部分（栈检查、运行时间统计、任务状态和当前任务的更新），
+//! 选择哪个任务运行则交给通过 `Scheduler::set_policy` 注册的 [`SchedulingPolicy`]：
+//!
+//! | 钩子 | 调用时机 |
+//! |------|----------|
+//! | `enqueue` | 任务变为就绪、被抢占的任务放回队列 |
+//! | `dequeue` | 任务阻塞、挂起、删除，或调度属性改变前 |
+//! | `pick_next` | 每次 `Scheduler::task_switch` |
+//! | `tick` | 每个 SysTick 节拍（`Scheduler::tick`） |
+//! | `preempt` | `Scheduler::preempt_check` |
+//!
+//! 内置两个策略：
+//! - [`RoundRobinPolicy`]：按任务 ID 依次轮转（默认）
+//! - [`PriorityPolicy`]：位图优先级调度，同优先级按时间片轮转，EDF 任务按截止时间调度
+//!
+//! ## 自定义策略
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::scheduler::{ReadyQueues, Scheduler, SchedulingPolicy};
+//! use neon_rtos2::kernel::task::Task;
+//!
+//! /// 总是运行就绪队列中最先就绪的任务，任务主动让出前不会被切换
+//! struct FifoPolicy;
+//!
+//! impl SchedulingPolicy for FifoPolicy {
+//!     fn name(&self) -> &'static str {
+//!         "fifo"
+//!     }
+//!
+//!     fn pick_next(&self, queues: &mut ReadyQueues, current: Task) -> Option<Task> {
+//!         if current.get_state() == neon_rtos2::kernel::task::TaskState::Running {
+//!             return None;
+//!         }
+//!         queues.pop_highest()
+//!     }
+//! }
+//!
+//! static FIFO: FifoPolicy = FifoPolicy;
+//! Scheduler::set_policy(&FIFO);
+//! ```
+
+use super::{edf, ReadyQueues, Scheduler};
+use crate::kernel::task::{Task, TaskState};
+
+/// 调度策略
+///
+/// 所有钩子都在持有调度器锁时调用，不能再调用 `Scheduler` 中会加锁的方法
+/// （如 `enqueue_ready_task`、`preempt_check`、`task_switch`）。
+pub trait SchedulingPolicy: Sync {
+    /// 策略名称
+    fn name(&self) -> &'static str;
+
+    /// 将就绪任务加入就绪队列
+    ///
+    /// 默认按任务的调度优先级（EDF 任务为 `EDF_BAND`）加入对应队列的尾部。
+    fn enqueue(&self, queues: &mut ReadyQueues, task: Task) {
+        queues.push(task, edf::sched_priority(task));
+    }
+
+    /// 将任务移出就绪队列
+    fn dequeue(&self, queues: &mut ReadyQueues, task: Task) {
+        queues.remove(task);
+    }
+
+    /// 选择下一个运行的任务
+    ///
+    /// 返回的任务需要已被移出就绪队列，调度器会把它设为运行状态；
+    /// 返回 `None` 或当前任务表示当前任务继续运行。
+    /// 当前任务不处于运行状态（阻塞、挂起、结束或主动让出）时应尽量返回其他就绪任务。
+    fn pick_next(&self, queues: &mut ReadyQueues, current: Task) -> Option<Task>;
+
+    /// SysTick 节拍回调
+    fn tick(&self, _current: Task) {}
+
+    /// 是否应该立即抢占当前任务
+    ///
+    /// 返回 `true` 时 `Scheduler::preempt_check` 会立即调用 `pick_next` 进行调度。
+    fn preempt(&self, _queues: &ReadyQueues, _current: Task) -> bool {
+        false
+    }
+}
+
+/// 轮转调度策略
+///
+/// 从当前任务之后按任务 ID 依次查找下一个就绪任务，不考虑优先级。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundRobinPolicy;
+
+impl RoundRobinPolicy {
+    /// 策略名称
+    pub const NAME: &'static str = "round-robin";
+}
+
+impl SchedulingPolicy for RoundRobinPolicy {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn pick_next(&self, _queues: &mut ReadyQueues, current: Task) -> Option<Task> {
+        let current_task_id = current.get_taskid();
+
+        // 从当前任务之后开始查找，找不到时从头开始（使用快照迭代器减少锁竞争）
+        let mut wrapped = None;
+        for snapshot in Task::snapshot_iter() {
+            if snapshot.task_id == current_task_id || snapshot.state != TaskState::Ready {
+                continue;
+            }
+            if snapshot.task_id > current_task_id {
+                return Some(Task::from_id(snapshot.task_id));
+            }
+            if wrapped.is_none() {
+                wrapped = Some(Task::from_id(snapshot.task_id));
+            }
+        }
+        wrapped
+    }
+}
+
+/// 优先级调度策略
+///
+/// - 选择优先级最高的就绪任务，同优先级按 FIFO 顺序
+/// - 当前任务的时间片用完且有同优先级就绪任务时轮转到队列尾部
+/// - EDF 任务按截止时间调度，不参与时间片轮转
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityPolicy;
+
+impl PriorityPolicy {
+    /// 策略名称
+    pub const NAME: &'static str = "priority";
+}
+
+impl SchedulingPolicy for PriorityPolicy {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn pick_next(&self, queues: &mut ReadyQueues, current: Task) -> Option<Task> {
+        let current_priority = edf::sched_priority(current);
+        let slice_expired = Scheduler::time_slice_expired() && current.get_deadline().is_none();
+
+        // 查看最高优先级的就绪任务
+        let (next, next_priority) = queues.peek_highest()?;
+
+        // 只有当找到的任务优先级更高、截止时间更早、当前任务不是运行状态，
+        // 或者当前任务时间片用完且有同优先级任务时才切换
+        if next != current
+            && (next_priority > current_priority
+                || edf::preempts(next, current)
+                || current.get_state() != TaskState::Running
+                || (slice_expired && next_priority == current_priority))
+        {
+            queues.remove_at(next, next_priority);
+            return Some(next);
+        }
+        None
+    }
+
+    fn tick(&self, current: Task) {
+        Scheduler::consume_time_slice(current);
+    }
+
+    fn preempt(&self, queues: &ReadyQueues, current: Task) -> bool {
+        // O(1) 检查是否有更高优先级（或截止时间更早）的就绪任务
+        queues.peek_highest().is_some_and(|(next, next_priority)| {
+            next_priority > edf::sched_priority(current) || edf::preempts(next, current)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::Priority;
+    use crate::utils::kernel_init;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use serial_test::serial;
+
+    static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+    /// 总是选择任务 ID 最大的就绪任务
+    struct HighestIdPolicy;
+
+    impl SchedulingPolicy for HighestIdPolicy {
+        fn name(&self) -> &'static str {
+            "highest-id"
+        }
+
+        fn pick_next(&self, queues: &mut ReadyQueues, current: Task) -> Option<Task> {
+            let next = (0..Priority::LEVELS)
+                .flat_map(|prio| queues.iter_at(Priority::saturating_from_u8(prio as u8)))
+                .filter(|task| *task != current && task.get_state() == TaskState::Ready)
+                .max_by_key(|task| task.get_taskid())?;
+            if current.get_state() == TaskState::Running && current.get_taskid() > next.get_taskid() {
+                return None;
+            }
+            queues.remove(next);
+            Some(next)
+        }
+
+        fn tick(&self, _current: Task) {
+            TICKS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn preempt(&self, _queues: &ReadyQueues, _current: Task) -> bool {
+            true
+        }
+    }
+
+    static HIGHEST_ID: HighestIdPolicy = HighestIdPolicy;
+
+    #[test]
+    #[serial]
+    fn test_builtin_policies() {
+        kernel_init();
+        assert_eq!(Scheduler::policy().name(), RoundRobinPolicy::NAME);
+        Scheduler::enable_priority_scheduling();
+        assert_eq!(Scheduler::policy().name(), PriorityPolicy::NAME);
+        Scheduler::set_policy(&RoundRobinPolicy);
+        assert!(!Scheduler::is_priority_scheduling_enabled());
+    }
+
+    #[test]
+    #[serial]
+    fn test_custom_policy() {
+        kernel_init();
+        let low = Task::new("low", |_| {}).unwrap();
+        let mid = Task::new("mid", |_| {}).unwrap();
+        let high = Task::new("high", |_| {}).unwrap();
+        Scheduler::set_policy(&HIGHEST_ID);
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        // 自定义策略的 preempt 钩子总是要求抢占
+        Scheduler::preempt_check();
+        assert_eq!(Scheduler::get_current_task(), high);
+        assert_eq!(low.get_state(), TaskState::Ready);
+
+        TICKS.store(0, Ordering::SeqCst);
+        Scheduler::tick();
+        assert_eq!(TICKS.load(Ordering::SeqCst), 1);
+
+        // 当前任务阻塞后选择剩下的 ID 最大的任务
+        let mut current = high;
+        current.block(crate::sync::event::Event::Signal(1));
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), mid);
+    }
+}
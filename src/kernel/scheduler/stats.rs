@@ -0,0 +1,248 @@
+//! 任务运行时间统计
+//!
+//! 调度器在每次 `task_switch` 时读取运行时间时钟，
+//! 将与上一次切换之间的差值计入被切出的任务，并记录被切入任务的切换次数。
+//!
+//! - 默认使用 SysTick 节拍作为时钟，精度为一个节拍
+//! - 可通过 `Scheduler::set_runtime_clock` 换成更高精度的计数器（如 DWT CYCCNT）
+//! - 空闲份额统计所有优先级为 `Priority::Idle` 的任务
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::scheduler::Scheduler;
+//!
+//! let stats = Scheduler::runtime_stats();
+//! for task in stats.iter() {
+//!     // 打印: 任务名、CPU 占用百分比、切换次数
+//!     let _ = (task.name, task.cpu_percent, task.switch_count);
+//! }
+//! let _idle = stats.idle_percent();
+//! ```
+
+use super::Scheduler;
+use crate::config::MAX_TASKS;
+use crate::kernel::task::{Priority, Task};
+use crate::kernel::time::systick::Systick;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use spin::Mutex;
+
+/// 运行时间时钟类型
+///
+/// 返回单调递增的计数值，允许回绕
+pub type RuntimeClock = fn() -> usize;
+
+/// 自定义运行时间时钟（`None` 表示使用 SysTick 节拍）
+static RUNTIME_CLOCK: Mutex<Option<RuntimeClock>> = Mutex::new(None);
+
+/// 上一次任务切换时的时钟值
+static LAST_SWITCH_TIME: AtomicUsize = AtomicUsize::new(0);
+
+/// 读取运行时间时钟
+fn now() -> usize {
+    match *RUNTIME_CLOCK.lock() {
+        Some(clock) => clock(),
+        None => Systick::get_current_time(),
+    }
+}
+
+/// 以当前时间作为统计起点（调度器初始化和启动时调用）
+pub(crate) fn restart() {
+    LAST_SWITCH_TIME.store(now(), Ordering::Release);
+}
+
+/// 将上一次切换以来的时间计入任务
+pub(crate) fn charge(task: Task) {
+    let now = now();
+    let last = LAST_SWITCH_TIME.swap(now, Ordering::AcqRel);
+    task.add_run_time(now.wrapping_sub(last));
+}
+
+/// 单个任务的运行时间统计
+#[derive(Debug, Clone, Copy)]
+pub struct TaskRuntime {
+    /// 任务 ID
+    pub task_id: usize,
+    /// 任务名称
+    pub name: &'static str,
+    /// 任务优先级
+    pub priority: Priority,
+    /// 累计运行时间（运行时间时钟计数）
+    pub run_time: usize,
+    /// 被切换进来运行的次数
+    pub switch_count: usize,
+    /// CPU 占用百分比（0-100）
+    pub cpu_percent: u32,
+}
+
+/// 运行时间统计信息
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeStats {
+    /// 所有任务的累计运行时间之和
+    pub total_time: usize,
+    /// 空闲任务的累计运行时间
+    pub idle_time: usize,
+    /// 各任务的统计信息（前 `count` 项有效）
+    pub tasks: [Option<TaskRuntime>; MAX_TASKS],
+    /// 有效任务数量
+    pub count: usize,
+}
+
+impl RuntimeStats {
+    /// 空闲任务的 CPU 占用百分比（0-100）
+    pub fn idle_percent(&self) -> u32 {
+        percent(self.idle_time, self.total_time)
+    }
+
+    /// 遍历各任务的统计信息
+    pub fn iter(&self) -> impl Iterator<Item = &TaskRuntime> {
+        self.tasks.iter().flatten()
+    }
+}
+
+/// 计算百分比，`total` 为 0 时返回 0
+fn percent(part: usize, total: usize) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    ((part as u64 * 100) / total as u64) as u32
+}
+
+impl Scheduler {
+    /// 设置运行时间时钟
+    ///
+    /// 默认使用 SysTick 节拍。运行时间短于一个节拍的任务在默认时钟下
+    /// 可能统计不到，此时可以换成周期计数器。
+    ///
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::scheduler::Scheduler;
+    ///
+    /// fn cycle_counter() -> usize {
+    ///     // 读取 DWT CYCCNT 等硬件计数器
+    ///     0
+    /// }
+    ///
+    /// Scheduler::set_runtime_clock(cycle_counter);
+    /// ```
+    pub fn set_runtime_clock(clock: RuntimeClock) {
+        *RUNTIME_CLOCK.lock() = Some(clock);
+        restart();
+    }
+
+    /// 恢复使用 SysTick 节拍作为运行时间时钟
+    pub fn clear_runtime_clock() {
+        *RUNTIME_CLOCK.lock() = None;
+        restart();
+    }
+
+    /// 获取各任务的运行时间统计
+    ///
+    /// 当前任务尚未结束的时间片会先计入其运行时间。
+    pub fn runtime_stats() -> RuntimeStats {
+        if Self::is_running() {
+            charge(Self::get_current_task());
+        }
+
+        let mut tasks = [None; MAX_TASKS];
+        let mut count = 0;
+        let mut total_time = 0usize;
+        let mut idle_time = 0usize;
+
+        for snapshot in Task::snapshot_iter() {
+            total_time = total_time.wrapping_add(snapshot.run_time);
+            if snapshot.priority.is_idle() {
+                idle_time = idle_time.wrapping_add(snapshot.run_time);
+            }
+            tasks[count] = Some(TaskRuntime {
+                task_id: snapshot.task_id,
+                name: snapshot.name,
+                priority: snapshot.priority,
+                run_time: snapshot.run_time,
+                switch_count: snapshot.switch_count,
+                cpu_percent: 0,
+            });
+            count += 1;
+        }
+
+        for task in tasks.iter_mut().flatten() {
+            task.cpu_percent = percent(task.run_time, total_time);
+        }
+
+        RuntimeStats { total_time, idle_time, tasks, count }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_runtime_charged_on_switch() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+        assert_eq!(main.get_switch_count(), 1);
+
+        Systick::add_current_time(30);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), worker);
+        assert_eq!(main.get_run_time(), 30);
+        assert_eq!(worker.get_switch_count(), 1);
+
+        Systick::add_current_time(10);
+        let stats = Scheduler::runtime_stats();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.total_time, 40);
+        let worker_stats = stats.iter().find(|t| t.task_id == worker.get_taskid()).unwrap();
+        assert_eq!(worker_stats.run_time, 10);
+        assert_eq!(worker_stats.cpu_percent, 25);
+
+        let snapshot = Task::snapshot_iter().find(|s| s.task_id == main.get_taskid()).unwrap();
+        assert_eq!(snapshot.run_time, 30);
+        assert_eq!(snapshot.switch_count, 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_runtime_idle_share() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut idle = Task::new("idle", |_| {}).unwrap();
+        idle.set_priority(Priority::Idle);
+        Scheduler::start();
+
+        Systick::add_current_time(20);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), idle);
+
+        Systick::add_current_time(80);
+        let stats = Scheduler::runtime_stats();
+        assert_eq!(stats.idle_time, 80);
+        assert_eq!(stats.idle_percent(), 80);
+    }
+
+    #[test]
+    #[serial]
+    fn test_runtime_custom_clock() {
+        static CYCLES: AtomicUsize = AtomicUsize::new(0);
+        fn cycles() -> usize {
+            CYCLES.load(Ordering::Acquire)
+        }
+
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+        Scheduler::set_runtime_clock(cycles);
+
+        CYCLES.store(1234, Ordering::Release);
+        Scheduler::runtime_stats();
+        assert_eq!(main.get_run_time(), 1234);
+
+        Scheduler::clear_runtime_clock();
+    }
+}
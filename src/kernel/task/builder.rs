@@ -1,8 +1,17 @@
 //! 任务构建器
 //!
-//! 提供链式 API 创建任务，支持设置优先级和栈大小。
+//! 提供链式 API 创建任务，支持设置优先级、时间片和栈大小，
+//! 以及通过 `periodic` 创建由内核按周期释放的周期任务。
+//!
+//! ## 栈分配
+//!
+//! | 配置 | 栈来源 |
+//! |------|--------|
+//! | 默认 | 静态栈池中与槽位对应的栈（`config::STACK_SIZE`） |
+//! | `stack_size(n)` | 从堆上分配 `n` 字节，任务槽位回收时释放（需要 `alloc`） |
+//! | `stack(buf)` | 调用者提供的 `&'static mut [u8]` 缓冲区 |
 
-use super::{Task, TaskFunction};
+use super::{PeriodicTaskBuilder, StackSpec, Task, TaskFunction};
 use super::priority::Priority;
 use crate::config::STACK_SIZE;
 use crate::error::Result;
@@ -34,11 +43,13 @@ pub struct TaskBuilder {
     name: &'static str,
     priority: Priority,
     /// 栈大小配置
-    /// 
-    /// 注意：当前版本使用固定栈大小（由 `config::STACK_SIZE` 定义），
-    /// 此字段预留给未来动态栈分配功能扩展使用。
-    #[allow(dead_code)]
     stack_size: usize,
+    /// 调用者提供的栈缓冲区
+    stack_buffer: Option<&'static mut [u8]>,
+    /// 时间片（节拍），0 表示使用全局时间片
+    time_slice: usize,
+    /// 绝对截止时间（SysTick 节拍），`None` 表示不是 EDF 任务
+    deadline: Option<usize>,
 }
 
 impl TaskBuilder {
@@ -50,11 +61,16 @@ impl TaskBuilder {
     /// # 默认值
     /// - 优先级: `Priority::Normal`
     /// - 栈大小: `STACK_SIZE` (配置文件中定义)
+    /// - 时间片: 使用调度器的全局时间片
+    /// - 截止时间: 无（固定优先级调度）
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
             priority: Priority::default(),
             stack_size: STACK_SIZE,
+            stack_buffer: None,
+            time_slice: 0,
+            deadline: None,
         }
     }
 
@@ -76,6 +92,70 @@ impl TaskBuilder {
         self
     }
 
+    /// 设置任务的时间片
+    ///
+    /// 启用优先级调度时，任务连续运行 `ticks` 个节拍后让给同优先级的其他就绪任务。
+    ///
+    /// # 参数
+    /// - `ticks`: 时间片长度（节拍），0 表示使用全局时间片
+    ///
+    /// # 示例
+    /// ```rust
+    /// use neon_rtos2::kernel::task::Task;
+    ///
+    /// Task::builder("short_slice_task")
+    ///     .time_slice(2)
+    ///     .spawn(|_| {});
+    /// ```
+    pub fn time_slice(mut self, ticks: usize) -> Self {
+        self.time_slice = ticks;
+        self
+    }
+
+    /// 设置任务的绝对截止时间，使其成为 EDF 任务
+    ///
+    /// EDF 仅在启用优先级调度（`Scheduler::enable_priority_scheduling`）时生效，
+    /// 默认的 `RoundRobinPolicy` 下截止时间会被记录但不影响调度。
+    ///
+    /// # 参数
+    /// - `deadline`: 绝对截止时间（SysTick 节拍）
+    ///
+    /// # 示例
+    /// ```rust
+    /// use neon_rtos2::kernel::task::Task;
+    ///
+    /// Task::builder("control_loop")
+    ///     .deadline(10)
+    ///     .spawn(|_| {});
+    /// ```
+    pub fn deadline(mut self, deadline: usize) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// 创建周期任务
+    ///
+    /// 内核每 `period` 个节拍释放一次任务体，任务体返回后等待下一次释放。
+    /// 每次释放的绝对截止时间为释放时刻加 `deadline`，周期任务按 EDF 调度。
+    ///
+    /// # 参数
+    /// - `period`: 周期（节拍）
+    /// - `deadline`: 相对截止时间（节拍），不大于周期
+    ///
+    /// # 示例
+    /// ```rust
+    /// use neon_rtos2::kernel::task::Task;
+    ///
+    /// Task::builder("sensor")
+    ///     .periodic(10, 5)
+    ///     .spawn(|_| {
+    ///         // 每个周期的工作
+    ///     });
+    /// ```
+    pub fn periodic(self, period: usize, deadline: usize) -> PeriodicTaskBuilder {
+        PeriodicTaskBuilder::new(self, period, deadline)
+    }
+
     /// 设置栈大小
     ///
     /// # 参数
@@ -83,6 +163,8 @@ impl TaskBuilder {
     ///
     /// # 注意
     /// 栈大小应该是 8 字节对齐的，如果不是，会自动向上对齐。
+    /// 与默认的 `STACK_SIZE` 不同时，栈会在 `spawn` 时从堆上分配；
+    /// 关闭 `alloc` 时 `spawn` 返回 `RtosError::OutOfMemory`，请改用 [`stack`](Self::stack)。
     ///
     /// # 示例
     /// ```rust
@@ -98,6 +180,30 @@ impl TaskBuilder {
         self
     }
 
+    /// 使用调用者提供的静态缓冲区作为任务栈
+    ///
+    /// 缓冲区会被对齐到 8 字节，对齐后的大小即为任务栈大小，
+    /// 设置后 `stack_size` 配置将被忽略。
+    ///
+    /// # 参数
+    /// - `buffer`: 栈缓冲区
+    ///
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    ///
+    /// static mut DSP_STACK: [u8; 16384] = [0; 16384];
+    ///
+    /// Task::builder("dsp")
+    ///     .stack(unsafe { &mut *core::ptr::addr_of_mut!(DSP_STACK) })
+    ///     .spawn(|_| {});
+    /// ```
+    pub fn stack(mut self, buffer: &'static mut [u8]) -> Self {
+        self.stack_size = buffer.len() & !7;
+        self.stack_buffer = Some(buffer);
+        self
+    }
+
     /// 获取配置的任务名称
     pub fn get_name(&self) -> &str {
         self.name
@@ -113,6 +219,16 @@ impl TaskBuilder {
         self.stack_size
     }
 
+    /// 获取配置的时间片
+    pub fn get_time_slice(&self) -> usize {
+        self.time_slice
+    }
+
+    /// 获取配置的截止时间
+    pub fn get_deadline(&self) -> Option<usize> {
+        self.deadline
+    }
+
     /// 创建并启动任务
     ///
     /// # 参数
@@ -121,6 +237,8 @@ impl TaskBuilder {
     /// # 返回值
     /// - `Ok(Task)`: 成功创建的任务句柄
     /// - `Err(RtosError::TaskSlotsFull)`: 没有可用的任务槽位
+    /// - `Err(RtosError::OutOfMemory)`: 堆上分配栈失败
+    /// - `Err(RtosError::InvalidArgument)`: 栈小于 `config::MIN_STACK_SIZE`
     ///
     /// # 示例
     /// ```rust
@@ -138,9 +256,19 @@ impl TaskBuilder {
     where
         F: TaskFunction,
     {
-        // 创建任务并设置优先级
-        let mut task = Task::new(self.name, func)?;
-        task.set_priority(self.priority);
+        let stack = match self.stack_buffer {
+            Some(buffer) => StackSpec::Static(buffer),
+            None if self.stack_size == STACK_SIZE => StackSpec::Pool,
+            None => StackSpec::Heap(self.stack_size),
+        };
+
+        // 创建任务并设置优先级和时间片
+        let mut task = Task::new_with_stack(self.name, func, stack)?;
+        task.try_set_priority(self.priority)?;
+        task.try_set_time_slice(self.time_slice)?;
+        if self.deadline.is_some() {
+            task.try_set_deadline(self.deadline)?;
+        }
         Ok(task)
     }
 }
@@ -148,7 +276,9 @@ impl TaskBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compat::{Box, vec};
     use crate::utils::kernel_init;
+    use serial_test::serial;
 
     #[test]
     fn test_task_builder_default() {
@@ -218,6 +348,69 @@ mod tests {
         assert_eq!(task.get_priority(), Priority::High);
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_task_builder_spawn_heap_stack() {
+        kernel_init();
+
+        let small = Task::builder("blinky")
+            .stack_size(512)
+            .spawn(|_| {})
+            .unwrap();
+        let large = Task::builder("dsp")
+            .stack_size(16384)
+            .spawn(|_| {})
+            .unwrap();
+        let default = Task::builder("default").spawn(|_| {}).unwrap();
+
+        assert_eq!(small.get_stack_size(), 512);
+        assert_eq!(large.get_stack_size(), 16384);
+        assert_eq!(default.get_stack_size(), STACK_SIZE);
+        assert_eq!(small.get_stack_base() % 8, 0);
+        assert!(small.get_stack_top() <= small.get_stack_base() + 512);
+        assert!(small.get_stack_top() > small.get_stack_base());
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_builder_spawn_static_stack() {
+        kernel_init();
+
+        let buffer: &'static mut [u8] = Box::leak(vec![0u8; 1024].into_boxed_slice());
+        let start = buffer.as_ptr() as usize;
+        let task = Task::builder("static_stack")
+            .stack(buffer)
+            .spawn(|_| {})
+            .unwrap();
+
+        assert!(task.get_stack_base() >= start);
+        assert!(task.get_stack_base() + task.get_stack_size() <= start + 1024);
+        assert!(task.get_stack_size() >= 1016);
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    #[serial]
+    fn test_task_builder_stack_size_without_alloc() {
+        kernel_init();
+
+        // 没有堆时只能使用栈池或静态缓冲区
+        let result = Task::builder("dsp").stack_size(16384).spawn(|_| {});
+        assert_eq!(result.err(), Some(crate::error::RtosError::OutOfMemory));
+        let default = Task::builder("default").spawn(|_| {}).unwrap();
+        assert_eq!(default.get_stack_size(), STACK_SIZE);
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_builder_stack_too_small() {
+        kernel_init();
+
+        let result = Task::builder("tiny").stack_size(64).spawn(|_| {});
+        assert_eq!(result.err(), Some(crate::error::RtosError::InvalidArgument));
+    }
+
     #[test]
     fn test_task_builder_spawn_with_closure() {
         kernel_init();
@@ -0,0 +1,222 @@
+//! 任务本地存储
+//!
+//! 每个任务在 TCB 中拥有 `config::MAX_TASK_LOCALS` 个 `usize` 槽位，
+//! 通过全局分配的 [`TaskLocalKey`] 访问。
+//!
+//! - 槽位值随任务槽位一起管理：任务槽位被复用时值会被清零，
+//!   不会像以 `Task::get_taskid()` 为索引的旁路表那样残留旧任务的数据
+//! - 键可以注册析构函数，任务结束（正常退出或被删除）时对非零值调用
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::compat::Box;
+//! use neon_rtos2::kernel::task::TaskLocalKey;
+//!
+//! fn free_context(value: usize) {
+//!     // Safety: 值由 Box::into_raw 产生
+//!     drop(unsafe { Box::from_raw(value as *mut u32) });
+//! }
+//!
+//! let key = TaskLocalKey::with_destructor(free_context).unwrap();
+//! key.set(Box::into_raw(Box::new(42u32)) as usize);
+//! assert_ne!(key.get(), 0);
+//! ```
+
+use super::{get_task_list, Task};
+use crate::config::MAX_TASK_LOCALS;
+use crate::error::{Result, RtosError};
+use crate::kernel::scheduler::Scheduler;
+use spin::Mutex;
+
+/// 任务本地存储析构函数类型
+///
+/// 参数为任务结束时槽位中的值（仅对非零值调用）
+pub type TaskLocalDestructor = fn(usize);
+
+/// 已分配的键
+#[derive(Clone, Copy)]
+struct KeyEntry {
+    destructor: Option<TaskLocalDestructor>,
+}
+
+/// 键分配表
+static TASK_LOCAL_KEYS: Mutex<[Option<KeyEntry>; MAX_TASK_LOCALS]> =
+    Mutex::new([None; MAX_TASK_LOCALS]);
+
+/// 任务本地存储键
+///
+/// 同一个键在不同任务中对应各自独立的槽位。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskLocalKey(usize);
+
+impl TaskLocalKey {
+    /// 分配一个没有析构函数的键
+    ///
+    /// # 返回值
+    /// - `Ok(TaskLocalKey)`: 分配成功
+    /// - `Err(RtosError::TaskLocalSlotsFull)`: 没有可用的键
+    pub fn new() -> Result<Self> {
+        Self::allocate(None)
+    }
+
+    /// 分配一个带析构函数的键
+    ///
+    /// 任务结束时，若该键的槽位值非零，会以该值调用 `destructor`。
+    ///
+    /// # 返回值
+    /// - `Ok(TaskLocalKey)`: 分配成功
+    /// - `Err(RtosError::TaskLocalSlotsFull)`: 没有可用的键
+    pub fn with_destructor(destructor: TaskLocalDestructor) -> Result<Self> {
+        Self::allocate(Some(destructor))
+    }
+
+    fn allocate(destructor: Option<TaskLocalDestructor>) -> Result<Self> {
+        let mut keys = TASK_LOCAL_KEYS.lock();
+        let index = keys
+            .iter()
+            .position(|entry| entry.is_none())
+            .ok_or(RtosError::TaskLocalSlotsFull)?;
+        keys[index] = Some(KeyEntry { destructor });
+        Ok(TaskLocalKey(index))
+    }
+
+    /// 释放键
+    ///
+    /// 所有任务中该键的槽位被清零，不会调用析构函数。
+    pub fn delete(self) {
+        let mut keys = TASK_LOCAL_KEYS.lock();
+        for tcb in get_task_list().iter() {
+            tcb.locals.lock()[self.0] = 0;
+        }
+        keys[self.0] = None;
+    }
+
+    /// 获取键的索引
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// 读取当前任务中该键的值（未设置时为 0）
+    pub fn get(&self) -> usize {
+        // 当前任务的句柄总是有效的
+        Scheduler::get_current_task().get_local(*self)
+    }
+
+    /// 设置当前任务中该键的值
+    pub fn set(&self, value: usize) {
+        Scheduler::get_current_task().set_local(*self, value);
+    }
+}
+
+impl Task {
+    /// 读取任务本地存储的值（未设置或句柄已失效时为 0）
+    pub fn get_local(&self, key: TaskLocalKey) -> usize {
+        self.try_get_local(key).unwrap_or(0)
+    }
+
+    /// 读取任务本地存储的值，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_local(&self, key: TaskLocalKey) -> Result<usize> {
+        Ok(self.tcb()?.locals.lock()[key.0])
+    }
+
+    /// 设置任务本地存储的值
+    ///
+    /// 覆盖旧值时不会调用析构函数。句柄已失效时不做任何操作。
+    pub fn set_local(&self, key: TaskLocalKey, value: usize) {
+        let _ = self.try_set_local(key, value);
+    }
+
+    /// 设置任务本地存储的值，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_set_local(&self, key: TaskLocalKey, value: usize) -> Result<()> {
+        self.tcb()?.locals.lock()[key.0] = value;
+        Ok(())
+    }
+
+    /// 调用任务本地存储的析构函数并清空槽位（任务结束时调用）
+    pub(crate) fn destroy_locals(&self) {
+        let values = core::mem::replace(&mut *get_task_list()[self.id].locals.lock(), [0; MAX_TASK_LOCALS]);
+        let keys = *TASK_LOCAL_KEYS.lock();
+        for (value, entry) in values.iter().zip(keys.iter()) {
+            if *value == 0 {
+                continue;
+            }
+            if let Some(KeyEntry { destructor: Some(destructor) }) = entry {
+                destructor(*value);
+            }
+        }
+    }
+}
+
+/// 释放所有键（内核初始化时调用）
+pub(crate) fn reset_keys() {
+    *TASK_LOCAL_KEYS.lock() = [None; MAX_TASK_LOCALS];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::TaskState;
+    use crate::utils::kernel_init;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use serial_test::serial;
+
+    static DESTROYED: AtomicUsize = AtomicUsize::new(0);
+
+    fn record_destroy(value: usize) {
+        DESTROYED.fetch_add(value, Ordering::SeqCst);
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_local_per_task_values() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        let key = TaskLocalKey::new().unwrap();
+        key.set(7);
+        worker.set_local(key, 9);
+        assert_eq!(key.get(), 7);
+        assert_eq!(main.get_local(key), 7);
+        assert_eq!(worker.get_local(key), 9);
+
+        key.delete();
+        assert_eq!(worker.get_local(key), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_local_keys_exhausted() {
+        kernel_init();
+        for _ in 0..MAX_TASK_LOCALS {
+            TaskLocalKey::new().unwrap();
+        }
+        assert_eq!(TaskLocalKey::new(), Err(RtosError::TaskLocalSlotsFull));
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_local_destructor_on_terminate() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+        DESTROYED.store(0, Ordering::SeqCst);
+
+        let key = TaskLocalKey::with_destructor(record_destroy).unwrap();
+        let plain = TaskLocalKey::new().unwrap();
+        worker.set_local(key, 5);
+        worker.set_local(plain, 3);
+        worker.delete().unwrap();
+        assert_eq!(worker.get_state(), TaskState::Terminated);
+        assert_eq!(DESTROYED.load(Ordering::SeqCst), 5);
+
+        // 复用槽位的新任务看不到旧值
+        let respawned = Task::new("worker", |_| {}).unwrap();
+        assert_eq!(respawned.get_taskid(), worker.get_taskid());
+        assert_eq!(respawned.get_local(key), 0);
+        assert_eq!(respawned.get_local(plain), 0);
+    }
+}
@@ -1,14 +1,25 @@
 use crate::hal::init_task_stack;
-use crate::config::MAX_TASKS;
-use crate::config::STACK_SIZE;
+use crate::config::{MAX_HELD_LOCKS, MAX_TASK_LOCALS, MAX_TASKS};
+#[cfg(not(feature = "alloc"))]
+use crate::config::TASK_FN_WORDS;
+use crate::config::{STACK_POOL_SIZE, STACK_SIZE};
 use crate::sync::event::Event;
 use crate::error::{Result, RtosError};
+#[cfg(feature = "alloc")]
 use crate::compat::Box;
+#[cfg(not(feature = "alloc"))]
+use crate::mem::inline::InlineBox;
+use crate::hal::trigger_schedule;
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::time::systick::Systick;
+use crate::kernel::time::timer::Timer;
+use crate::sync::signal::WaiterList;
+use crate::sync::wait_queue::WaitQueue;
 use core::cmp::PartialEq;
 use core::fmt::Debug;
 use core::prelude::rust_2024::*;
 use core::ptr::addr_of;
-use core::sync::atomic::{AtomicUsize, AtomicU8, Ordering};
+use core::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicUsize, AtomicU8, Ordering};
 
 use spin::{Once, Mutex};
 
@@ -16,11 +27,22 @@ use spin::{Once, Mutex};
 pub mod priority;
 pub mod builder;
 pub mod state;
+pub mod stack;
+pub mod local;
+pub mod notify;
+pub mod periodic;
 
 // 重新导出
 pub use priority::Priority;
 pub use builder::TaskBuilder;
-pub use state::{TypedTask, TypedTaskBuilder, TaskStateMarker, Created, Ready, Running, Blocked};
+pub use state::{TypedTask, TypedTaskBuilder, TaskStateMarker, Created, Ready, Running, Blocked, Suspended};
+pub use stack::{set_stack_overflow_hook, clear_stack_overflow_hook, StackOverflowHook};
+pub(crate) use stack::{OwnedStack, PoolStack, StackAlloc, StackSpec};
+pub use local::{TaskLocalDestructor, TaskLocalKey};
+pub use notify::NotifyAction;
+use notify::NotifyState;
+pub use periodic::{set_deadline_miss_hook, clear_deadline_miss_hook, DeadlineMissHook, PeriodicTaskBuilder};
+use periodic::PeriodicState;
 
 // ============================================================================
 // 任务状态编码（用于原子操作）
@@ -32,11 +54,15 @@ pub use state::{TypedTask, TypedTaskBuilder, TaskStateMarker, Created, Ready, Ru
 /// - 0: Uninit
 /// - 1: Ready
 /// - 2: Running
-/// - 3+: Blocked (高位存储事件类型，需要额外存储事件 ID)
+/// - 3: Blocked (事件信息单独存储在 `blocked_event` 中)
+/// - 4: Terminated (任务已结束，槽位可被重新分配)
+/// - 5: Suspended (任务被挂起，只能通过 `resume` 恢复)
 const STATE_UNINIT: u8 = 0;
 const STATE_READY: u8 = 1;
 const STATE_RUNNING: u8 = 2;
 const STATE_BLOCKED: u8 = 3;
+const STATE_TERMINATED: u8 = 4;
+const STATE_SUSPENDED: u8 = 5;
 
 // ============================================================================
 // 全局任务列表（优化后的细粒度锁版本）
@@ -64,11 +90,11 @@ static TASK_LIST: Once<[TaskControlBlock; MAX_TASKS]> = Once::new();
 static TASK_ALLOC_LOCK: Once<Mutex<()>> = Once::new();
 
 #[unsafe(no_mangle)]
-static mut TASK_STACKS: [Stack; MAX_TASKS] = [const {
+static mut TASK_STACKS: [Stack; STACK_POOL_SIZE] = [const {
     Stack {
         data: [0; STACK_SIZE],
     }
-}; MAX_TASKS];
+}; STACK_POOL_SIZE];
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TaskState {
@@ -76,6 +102,10 @@ pub enum TaskState {
     Ready,
     Running,
     Blocked(Event),
+    /// 任务已结束（正常返回或调用 `Task::exit`），等待被 join 或重新分配
+    Terminated,
+    /// 任务被挂起，不参与调度，也不会被事件唤醒，直到调用 `Task::resume`
+    Suspended,
 }
 
 impl TaskState {
@@ -87,6 +117,8 @@ impl TaskState {
             TaskState::Ready => STATE_READY,
             TaskState::Running => STATE_RUNNING,
             TaskState::Blocked(_) => STATE_BLOCKED,
+            TaskState::Terminated => STATE_TERMINATED,
+            TaskState::Suspended => STATE_SUSPENDED,
         }
     }
     
@@ -98,6 +130,8 @@ impl TaskState {
             STATE_READY => TaskState::Ready,
             STATE_RUNNING => TaskState::Running,
             STATE_BLOCKED => TaskState::Blocked(Event::None),
+            STATE_TERMINATED => TaskState::Terminated,
+            STATE_SUSPENDED => TaskState::Suspended,
             _ => TaskState::Uninit,
         }
     }
@@ -109,8 +143,34 @@ pub struct Stack {
     pub data: [u8; STACK_SIZE],
 }
 
+/// 被强制结束（`Task::delete` / `Task::kill`）的任务的退出码
+pub const TASK_KILLED_EXIT_CODE: i32 = -1;
+
+/// 表示没有截止时间的哨兵值
+const NO_DEADLINE: usize = usize::MAX;
+
+/// 任务持有（或正在等待）的锁记录
+/// 
+/// 任务结束或被删除时，通过 `release` 处理仍被持有的锁（写锁标记为 OwnerDied，读锁直接释放），
+/// 避免锁永远无法被其他任务获取。
+/// 任务的有效优先级由基础优先级和所持有锁的 `boost` 共同决定，
+/// 优先级继承沿着 `owner` 传递给等待链上的每个持有者。
+/// 
+/// 所有函数调用时 `addr` 指向的锁对象必须仍然有效。
+#[derive(Clone, Copy)]
+pub(crate) struct HeldLock {
+    /// 锁对象地址（同时作为唯一标识）
+    pub(crate) addr: usize,
+    /// 持有者死亡时调用的释放函数
+    pub(crate) release: unsafe fn(usize),
+    /// 锁要求持有者至少具有的优先级（继承的等待者优先级或天花板）
+    pub(crate) boost: unsafe fn(usize) -> Option<Priority>,
+    /// 锁当前的持有者
+    pub(crate) owner: unsafe fn(usize) -> Option<Task>,
+}
+
 pub trait TaskFunction: Send + 'static + Sync {
-    fn call(self: Box<Self>, task_id: usize);
+    fn call(self, task_id: usize);
 }
 
 // 为闭包实现TaskFunction
@@ -118,11 +178,51 @@ impl<F> TaskFunction for F
 where
     F: FnOnce(usize) + Send + 'static + Sync,
 {
-    fn call(self: Box<Self>, task_id: usize) {
-        (*self)(task_id);
+    fn call(self, task_id: usize) {
+        self(task_id);
     }
 }
 
+/// 保存在 TCB 中、尚未执行的任务函数
+///
+/// 启用 `alloc` 时装箱保存；关闭时内联保存，闭包最大为 `config::TASK_FN_WORDS` 个字。
+pub(crate) struct TaskEntry {
+    #[cfg(feature = "alloc")]
+    func: Box<dyn FnOnce(usize) + Send + Sync>,
+    #[cfg(not(feature = "alloc"))]
+    func: InlineBox<TASK_FN_WORDS>,
+    /// 按放入时的类型取出并调用 `func`
+    #[cfg(not(feature = "alloc"))]
+    call: unsafe fn(InlineBox<TASK_FN_WORDS>, usize),
+}
+
+impl TaskEntry {
+    #[cfg(feature = "alloc")]
+    fn new<F: TaskFunction>(func: F) -> Result<Self> {
+        Ok(Self { func: Box::new(move |task_id| func.call(task_id)) })
+    }
+
+    /// 闭包超出内联存储时返回 `RtosError::OutOfMemory`
+    #[cfg(not(feature = "alloc"))]
+    fn new<F: TaskFunction>(func: F) -> Result<Self> {
+        let func = InlineBox::new(func).map_err(|_| RtosError::OutOfMemory)?;
+        Ok(Self { func, call: call_inline::<F> })
+    }
+
+    fn call(self, task_id: usize) {
+        #[cfg(feature = "alloc")]
+        (self.func)(task_id);
+        // SAFETY: call 与放入 func 的类型对应
+        #[cfg(not(feature = "alloc"))]
+        unsafe { (self.call)(self.func, task_id) };
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+unsafe fn call_inline<F: TaskFunction>(func: InlineBox<TASK_FN_WORDS>, task_id: usize) {
+    unsafe { func.take::<F>() }.call(task_id);
+}
+
 /// 任务控制块 - 优化后的细粒度锁版本
 /// 
 /// ## 内存布局优化
@@ -136,9 +236,20 @@ where
 /// 
 /// - `stack_top`: 原子操作（上下文切换时频繁访问）
 /// - `state`: 原子操作 + 事件锁（状态切换频繁）
-/// - `priority`: 原子操作（优先级调度时访问）
+/// - `priority`, `base_priority`: 原子操作（优先级调度时访问）
 /// - `name`, `taskid`: 不变字段，无需锁
 /// - `task_fn`: 细粒度锁（仅任务启动时访问一次）
+/// - `exit_code`: 原子操作（任务结束时写入，join 时读取）
+/// - `stack_base`, `stack_size`: 原子操作（创建时写入）
+/// - `owned_stack`: 细粒度锁（仅创建和回收时访问）
+/// - `wait_list`: 原子操作（加入/离开等待队列时访问）
+/// - `held_locks`, `blocked_on`: 细粒度锁（获取/释放/等待被追踪的锁时访问）
+/// - `locals`: 细粒度锁（访问任务本地存储时访问）
+/// - `notify`: 细粒度锁（发送/等待任务通知时访问）
+/// - `run_time`, `switch_count`: 原子操作（任务切换时更新）
+/// - `time_slice`: 原子操作（SysTick 中断中读取）
+/// - `deadline`: 原子操作（调度器选择任务时读取）
+/// - `periodic`: 细粒度锁（周期任务每次释放时访问）
 #[repr(C)]
 pub struct TaskControlBlock {
     // ========== 热数据（调度器频繁访问）==========
@@ -147,12 +258,15 @@ pub struct TaskControlBlock {
     pub(crate) stack_top: AtomicUsize,
     
     /// 任务状态（原子编码）
-    /// 使用 u8 编码：0=Uninit, 1=Ready, 2=Running, 3=Blocked
+    /// 使用 u8 编码：0=Uninit, 1=Ready, 2=Running, 3=Blocked, 4=Terminated, 5=Suspended
     pub(crate) state_atomic: AtomicU8,
     
-    /// 任务优先级（原子操作）
+    /// 任务的有效优先级（原子操作）
     pub(crate) priority_atomic: AtomicU8,
     
+    /// 任务的基础优先级（不含优先级继承和天花板提升）
+    base_priority: AtomicU8,
+    
     // ========== 阻塞事件（需要锁保护）==========
     /// 阻塞事件 - 仅在 Blocked 状态时有效
     /// 使用细粒度锁，因为事件信息无法用原子操作存储
@@ -167,11 +281,77 @@ pub struct TaskControlBlock {
     
     /// 任务函数 - 仅启动时访问一次
     /// 使用细粒度锁
-    pub(crate) task_fn: Mutex<Option<Box<dyn TaskFunction>>>,
+    pub(crate) task_fn: Mutex<Option<TaskEntry>>,
+    
+    /// 退出码 - 仅在 Terminated 状态时有效
+    pub(crate) exit_code: AtomicI32,
+    
+    /// 栈底地址（最低地址）
+    pub(crate) stack_base: AtomicUsize,
+    
+    /// 栈大小（字节）
+    pub(crate) stack_size: AtomicUsize,
+    
+    /// 堆上分配的栈 - 槽位回收时释放
+    owned_stack: Mutex<Option<OwnedStack>>,
+    
+    /// 占用的池化栈 - 槽位回收时归还栈池
+    pool_stack: Mutex<Option<PoolStack>>,
+    
+    /// 任务当前所在的等待队列（`*const Mutex<WaiterList>`，0 表示无）
+    /// 任务被删除时据此将其移出等待队列
+    wait_list: AtomicUsize,
+
+    /// 阻塞中的等待是否被 `suspend` 打断，开始新的等待时清除
+    wait_aborted: AtomicBool,
+    
+    /// 任务当前持有的锁
+    held_locks: Mutex<[Option<HeldLock>; MAX_HELD_LOCKS]>,
+    
+    /// 任务正在等待的锁（用于传递优先级继承）
+    blocked_on: Mutex<Option<HeldLock>>,
+    
+    /// 任务本地存储槽位（0 表示未设置）
+    locals: Mutex<[usize; MAX_TASK_LOCALS]>,
+    
+    /// 任务通知值与待处理标志
+    notify: Mutex<NotifyState>,
+    
+    /// 在 `notify_wait` 上阻塞的任务（只会是任务自己）
+    notify_waiters: WaitQueue,
+    
+    /// join 该任务的任务，任务结束时全部唤醒
+    joiners: WaitQueue,
+    
+    /// 累计运行时间（运行时间时钟的计数单位，默认为 SysTick 节拍）
+    run_time: AtomicUsize,
+    
+    /// 被切换进来运行的次数
+    switch_count: AtomicUsize,
+    
+    /// 任务自己的时间片（节拍），0 表示使用调度器的全局时间片
+    time_slice: AtomicUsize,
+    
+    /// 绝对截止时间（SysTick 节拍），`usize::MAX` 表示不是 EDF 任务
+    deadline: AtomicUsize,
+    
+    /// 周期任务状态
+    periodic: Mutex<PeriodicState>,
+    
+    /// 槽位的代数，每次回收槽位时加一，使指向旧任务的句柄失效
+    generation: AtomicU32,
 }
 
+/// 任务句柄
+/// 
+/// 由槽位下标和创建时槽位的代数组成。任务结束后槽位被回收给新任务时代数加一，
+/// 旧句柄随之失效：`get_state` 返回 `Uninit`，`join` / `delete` / `notify` 等返回
+/// `RtosError::TaskNotFound`，不会作用到重用槽位的新任务上。
 #[derive(Clone, PartialEq, Copy, Debug)]
-pub struct Task(pub usize);
+pub struct Task {
+    id: usize,
+    generation: u32,
+}
 
 /// 获取任务列表（优化后的版本）
 /// 
@@ -188,6 +368,8 @@ fn get_alloc_lock() -> &'static Mutex<()> {
 }
 
 // 关键：统一的包装器函数，���固定的入口地址
+//
+// 任务函数返回后视为以退出码 0 正常结束，不会再落入 `task_exit_error`
 fn task_wrapper_entry(task_id: usize) {
     let task_list = get_task_list();
     // 使用细粒度锁获取任务函数
@@ -195,6 +377,7 @@ fn task_wrapper_entry(task_id: usize) {
     if let Some(func) = task_fn {
         func.call(task_id);
     }
+    Task::exit(0);
 }
 
 impl TaskControlBlock {
@@ -204,22 +387,49 @@ impl TaskControlBlock {
             stack_top: AtomicUsize::new(0),
             state_atomic: AtomicU8::new(STATE_UNINIT),
             priority_atomic: AtomicU8::new(Priority::Normal.as_u8()),
+            base_priority: AtomicU8::new(Priority::Normal.as_u8()),
             blocked_event: Mutex::new(None),
             name: "noinit",
             taskid: 0,
             task_fn: Mutex::new(None),
+            exit_code: AtomicI32::new(0),
+            stack_base: AtomicUsize::new(0),
+            stack_size: AtomicUsize::new(0),
+            owned_stack: Mutex::new(None),
+            pool_stack: Mutex::new(None),
+            wait_list: AtomicUsize::new(0),
+            wait_aborted: AtomicBool::new(false),
+            held_locks: Mutex::new([None; MAX_HELD_LOCKS]),
+            blocked_on: Mutex::new(None),
+            locals: Mutex::new([0; MAX_TASK_LOCALS]),
+            notify: Mutex::new(NotifyState { value: 0, pending: false }),
+            notify_waiters: WaitQueue::new(),
+            joiners: WaitQueue::new(),
+            run_time: AtomicUsize::new(0),
+            switch_count: AtomicUsize::new(0),
+            time_slice: AtomicUsize::new(0),
+            deadline: AtomicUsize::new(NO_DEADLINE),
+            periodic: Mutex::new(PeriodicState { period: 0, deadline: 0, release: 0, missed: 0, overruns: 0 }),
+            generation: AtomicU32::new(0),
         }
     }
     
     /// 初始化任务（内部使用）
     /// 
     /// 注意：此方法假设调用者已经持有分配锁
-    fn init_unified<F>(&self, name: &'static str, func: F, taskid: usize, stack_top: usize)
-    where
-        F: TaskFunction,
-    {
+    fn init_unified(&self, name: &'static str, func: TaskEntry, taskid: usize, stack: StackAlloc) {
+        // 记录栈区域，堆上分配的栈由 TCB 持有
+        self.stack_base.store(stack.base, Ordering::Release);
+        self.stack_size.store(stack.size, Ordering::Release);
+        *self.owned_stack.lock() = stack.owned;
+        *self.pool_stack.lock() = stack.pool;
+        
+        // 填充栈并写入栈底金丝雀，用于溢出检测和高水位线统计
+        // SAFETY: 栈区域由分配锁保护，尚未被任何任务使用
+        unsafe { stack::prepare_guard(stack.base, stack.size) };
+        
         // 设置栈顶（需要可变引用用于 init_task_stack）
-        self.stack_top.store(stack_top, Ordering::Release);
+        self.stack_top.store(stack.base + stack.size, Ordering::Release);
         
         // 设置不变字段（通过 unsafe 因为我们需要修改 &self）
         // SAFETY: 我们持有分配锁，保证没有其他线程在访问这个 TCB
@@ -230,7 +440,7 @@ impl TaskControlBlock {
         }
         
         // 设置任务函数
-        *self.task_fn.lock() = Some(Box::new(func));
+        *self.task_fn.lock() = Some(func);
         
         // 初始化栈（需要获取栈顶的可变引用）
         let mut stack_top_val = self.stack_top.load(Ordering::Acquire);
@@ -243,11 +453,29 @@ impl TaskControlBlock {
     
     /// 重置 TCB 到未初始化状态
     fn reset(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
         self.state_atomic.store(STATE_UNINIT, Ordering::Release);
         self.stack_top.store(0, Ordering::Release);
         self.priority_atomic.store(Priority::Normal.as_u8(), Ordering::Release);
+        self.base_priority.store(Priority::Normal.as_u8(), Ordering::Release);
         *self.blocked_event.lock() = None;
         *self.task_fn.lock() = None;
+        self.exit_code.store(0, Ordering::Release);
+        self.stack_base.store(0, Ordering::Release);
+        self.stack_size.store(0, Ordering::Release);
+        *self.owned_stack.lock() = None;
+        *self.pool_stack.lock() = None;
+        self.wait_list.store(0, Ordering::Release);
+        self.wait_aborted.store(false, Ordering::Release);
+        *self.held_locks.lock() = [None; MAX_HELD_LOCKS];
+        *self.blocked_on.lock() = None;
+        *self.locals.lock() = [0; MAX_TASK_LOCALS];
+        *self.notify.lock() = NotifyState::default();
+        self.run_time.store(0, Ordering::Release);
+        self.switch_count.store(0, Ordering::Release);
+        self.time_slice.store(0, Ordering::Release);
+        self.deadline.store(NO_DEADLINE, Ordering::Release);
+        *self.periodic.lock() = PeriodicState::default();
     }
     
     // ========== 原子访问方法 ==========
@@ -269,9 +497,23 @@ impl TaskControlBlock {
     #[inline]
     fn set_ready(&self) {
         *self.blocked_event.lock() = None;
+        self.wait_list.store(0, Ordering::Release);
         self.state_atomic.store(STATE_READY, Ordering::Release);
     }
     
+    /// 阻塞状态时设置为 Ready，不等待锁
+    ///
+    /// 返回 `None` 表示事件信息的锁被占用。
+    fn try_set_ready(&self) -> Option<bool> {
+        if self.state_atomic.load(Ordering::Acquire) != STATE_BLOCKED {
+            return Some(false);
+        }
+        *self.blocked_event.try_lock()? = None;
+        self.wait_list.store(0, Ordering::Release);
+        self.state_atomic.store(STATE_READY, Ordering::Release);
+        Some(true)
+    }
+
     /// 设置状态为 Running（原子操作）- O(1)
     #[inline]
     fn set_running(&self) {
@@ -281,10 +523,32 @@ impl TaskControlBlock {
     /// 设置状态为 Blocked（需要锁保护事件）
     #[inline]
     fn set_blocked(&self, event: Event) {
+        self.wait_aborted.store(false, Ordering::Release);
         *self.blocked_event.lock() = Some(event);
         self.state_atomic.store(STATE_BLOCKED, Ordering::Release);
     }
     
+    /// 设置状态为 Terminated 并记录退出码
+    #[inline]
+    fn set_terminated(&self, code: i32) {
+        self.exit_code.store(code, Ordering::Release);
+        *self.blocked_event.lock() = None;
+        self.state_atomic.store(STATE_TERMINATED, Ordering::Release);
+    }
+    
+    /// 设置状态为 Suspended（清除阻塞事件）
+    #[inline]
+    fn set_suspended(&self) {
+        *self.blocked_event.lock() = None;
+        self.state_atomic.store(STATE_SUSPENDED, Ordering::Release);
+    }
+    
+    /// 检查槽位是否可以被重新分配（未初始化或已结束）
+    #[inline]
+    fn is_reclaimable(&self) -> bool {
+        matches!(self.state_atomic.load(Ordering::Acquire), STATE_UNINIT | STATE_TERMINATED)
+    }
+    
     /// 获取优先级（原子操作）- O(1)，无锁
     #[inline]
     fn get_priority(&self) -> Priority {
@@ -325,86 +589,847 @@ impl Task {
     /// - 只在分配槽位时获取分配锁
     /// - 任务初始化使用原子操作和细粒度锁
     /// - 不影响其他任务的并发访问
+    /// 
+    /// ## 槽位回收
+    /// 
+    /// 已结束（Terminated）的任务槽位和栈会被重新分配。
+    /// 正在运行的当前任务即使已结束也不会被回收，因为它仍在使用自己的栈，
+    /// 直到调度器切换走为止。
+    /// 
+    /// ## 任务函数的存储
+    /// 
+    /// 启用 `alloc` 时闭包被装箱；关闭时内联保存在 TCB 中，
+    /// 捕获的数据超过 `config::TASK_FN_WORDS` 个字时返回 `RtosError::OutOfMemory`。
     pub fn new<F>(name: &'static str, func: F) -> Result<Self>
     where
         F: TaskFunction,
     {
+        Self::new_with_stack(name, func, StackSpec::Pool)
+    }
+
+    /// 使用指定的栈来源创建新任务（内部使用）
+    /// 
+    /// 堆栈和任务函数在获取分配锁之前分配，避免在持锁期间调用分配器。
+    /// 
+    /// # 返回值
+    /// - `Ok(Task)`: 成功创建的任务句柄
+    /// - `Err(RtosError::TaskSlotsFull)`: 没有可用的任务槽位
+    /// - `Err(RtosError::OutOfMemory)`: 堆上分配栈失败、栈池已满，或关闭 `alloc` 时闭包超出内联存储
+    /// - `Err(RtosError::InvalidArgument)`: 栈小于 `config::MIN_STACK_SIZE`
+    pub(crate) fn new_with_stack<F>(name: &'static str, func: F, stack: StackSpec) -> Result<Self>
+    where
+        F: TaskFunction,
+    {
+        let stack = stack.prepare()?;
+        let func = TaskEntry::new(func)?;
         let task_list = get_task_list();
         
         // 获取分配锁，保证槽位分配的原子性
         let _alloc_guard = get_alloc_lock().lock();
         
-        // 查找空闲槽位
-        for i in 0..MAX_TASKS {
-            // 使用原子操作检查状态
-            if task_list[i].state_atomic.load(Ordering::Acquire) == STATE_UNINIT {
-                // SAFETY: TASK_STACKS 是静态数组，我们通过分配锁保证了
-                // 同一时间只有一个任务在初始化特定的栈槽位
-                let stack_top = unsafe { addr_of!(TASK_STACKS[i].data) as usize + STACK_SIZE };
-                task_list[i].init_unified(name, func, i, stack_top);
-                return Ok(Task(i));
+        let current_id = Scheduler::get_current_task().get_taskid();
+        let scheduler_running = Scheduler::is_running();
+        
+        // 查找空闲槽位（调度器运行时当前任务的槽位不能回收）
+        let mut free = task_list
+            .iter()
+            .enumerate()
+            .filter(|(i, tcb)| tcb.is_reclaimable() && !(scheduler_running && *i == current_id));
+        let (i, tcb, stack) = match stack {
+            Some(stack) => {
+                let (i, tcb) = free.next().ok_or(RtosError::TaskSlotsFull)?;
+                (i, tcb, stack)
+            }
+            None => {
+                // 先占用空闲的池化栈；栈池已满时使用已结束任务留下的池化栈
+                let (i, tcb, pool) = match PoolStack::acquire() {
+                    Some(pool) => {
+                        let (i, tcb) = free.next().ok_or(RtosError::TaskSlotsFull)?;
+                        (i, tcb, pool)
+                    }
+                    None => {
+                        let mut free = free.peekable();
+                        if free.peek().is_none() {
+                            return Err(RtosError::TaskSlotsFull);
+                        }
+                        free.find_map(|(i, tcb)| tcb.pool_stack.lock().take().map(|pool| (i, tcb, pool)))
+                            .ok_or(RtosError::OutOfMemory)?
+                    }
+                };
+                let stack = StackAlloc {
+                    // SAFETY: TASK_STACKS 是静态数组，占用的池化栈只属于这个任务
+                    base: unsafe { addr_of!(TASK_STACKS[pool.index()].data) as usize },
+                    size: STACK_SIZE,
+                    owned: None,
+                    pool: Some(pool),
+                };
+                (i, tcb, stack)
             }
+        };
+
+        // 栈已经到手才回收已结束任务的槽位，清除上一个任务残留的优先级、退出码等
+        if tcb.is_initialized() {
+            tcb.reset();
+        }
+        tcb.init_unified(name, func, i, stack);
+        Ok(Task::from_id(i))
+    }
+
+    /// 获取槽位 `id` 中当前任务的句柄（内部使用）
+    /// 
+    /// 用于从等待队列、就绪队列等只记录槽位下标的地方恢复句柄，
+    /// 这些地方的任务在离开队列之前不会被回收。
+    pub(crate) fn from_id(id: usize) -> Self {
+        Self {
+            id,
+            generation: get_task_list()[id].generation.load(Ordering::Acquire),
+        }
+    }
+
+    /// 句柄是否仍然指向创建时的任务
+    /// 
+    /// 任务结束后槽位被新任务重用时返回 `false`。
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        get_task_list()[self.id].generation.load(Ordering::Acquire) == self.generation
+    }
+
+    /// 句柄对应的 TCB
+    /// 
+    /// # 返回值
+    /// - `Ok(tcb)`: 句柄有效
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    fn tcb(&self) -> Result<&'static TaskControlBlock> {
+        if self.is_valid() {
+            Ok(&get_task_list()[self.id])
+        } else {
+            Err(RtosError::TaskNotFound)
         }
-        Err(RtosError::TaskSlotsFull)
     }
 
     /// 设置任务状态为 Running - O(1)，原子操作
+    /// 
+    /// 句柄已失效时不做任何操作。
     pub fn run(&mut self) {
-        get_task_list()[self.0].set_running();
+        if let Ok(tcb) = self.tcb() {
+            tcb.set_running();
+        }
     }
 
     /// 设置任务状态为 Ready - O(1)，原子操作
+    /// 
+    /// 句柄已失效时不做任何操作。
     pub fn ready(&mut self) {
-        get_task_list()[self.0].set_ready();
+        if let Ok(tcb) = self.tcb() {
+            tcb.set_ready();
+        }
+    }
+
+    /// 把阻塞的任务设置为 Ready，不等待任何锁（中断延迟处理使用）
+    ///
+    /// # 返回值
+    /// - `Some(true)`: 任务已设置为 Ready
+    /// - `Some(false)`: 任务不处于阻塞状态或句柄已失效
+    /// - `None`: 任务状态的锁被占用
+    pub(crate) fn try_ready(&self) -> Option<bool> {
+        match self.tcb() {
+            Ok(tcb) => tcb.try_set_ready(),
+            Err(_) => Some(false),
+        }
     }
 
     /// 设置任务状态为 Blocked - O(1)
+    /// 
+    /// 句柄已失效时不做任何操作。
     pub fn block(&mut self, reason: Event) {
-        get_task_list()[self.0].set_blocked(reason);
+        if let Ok(tcb) = self.tcb() {
+            tcb.set_blocked(reason);
+        }
+    }
+
+    /// 挂起任务
+    /// 
+    /// 将任务从调度器的就绪队列中移除，挂起期间任务不会被调度，
+    /// 也不会被 `Event::wake_task` 等事件唤醒，直到调用 `resume`。
+    /// 挂起当前任务会立即触发调度。
+    /// 
+    /// 挂起一个阻塞中的任务会使其放弃当前的等待：任务离开等待队列、超时定时器被取消，
+    /// 恢复后等待返回 `Err(RtosError::WaitAborted)`（`Delay::delay` 直接返回）。
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 挂起成功
+    /// - `Err(RtosError::TaskNotFound)`: 句柄指向的任务已不存在
+    /// - `Err(RtosError::InvalidTaskState)`: 任务已结束或已被挂起
+    /// 
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    /// 
+    /// let mut worker = Task::new("worker", |_| {}).unwrap();
+    /// worker.suspend().unwrap();
+    /// // ...
+    /// worker.resume().unwrap();
+    /// ```
+    pub fn suspend(&mut self) -> Result<()> {
+        match self.get_state() {
+            TaskState::Uninit => return Err(RtosError::TaskNotFound),
+            TaskState::Terminated | TaskState::Suspended => {
+                return Err(RtosError::InvalidTaskState);
+            }
+            TaskState::Blocked(_) => self.abort_wait(),
+            _ => {}
+        }
+        Scheduler::dequeue_task(self);
+        get_task_list()[self.id].set_suspended();
+        if Scheduler::is_running() && Scheduler::get_current_task() == *self {
+            trigger_schedule();
+        }
+        Ok(())
+    }
+
+    /// 放弃阻塞中的等待：离开等待队列和锁的等待链，取消超时定时器
+    fn abort_wait(&self) {
+        self.leave_wait_list();
+        self.leave_blocked_on();
+        Timer::cancel_for(*self);
+        get_task_list()[self.id].wait_aborted.store(true, Ordering::Release);
+    }
+
+    /// 当前的等待是否被 `suspend` 打断（内部使用）
+    ///
+    /// 等待方在被切换回来之后调用，返回 `true` 时应返回 `Err(RtosError::WaitAborted)`。
+    pub(crate) fn wait_aborted(&self) -> bool {
+        self.is_valid() && get_task_list()[self.id].wait_aborted.swap(false, Ordering::AcqRel)
+    }
+
+    /// 恢复被挂起的任务
+    /// 
+    /// 任务恢复为就绪状态并重新加入就绪队列。
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 恢复成功
+    /// - `Err(RtosError::TaskNotFound)`: 句柄指向的任务已不存在
+    /// - `Err(RtosError::InvalidTaskState)`: 任务未处于挂起状态
+    pub fn resume(&mut self) -> Result<()> {
+        match self.get_state() {
+            TaskState::Suspended => {}
+            TaskState::Uninit => return Err(RtosError::TaskNotFound),
+            _ => return Err(RtosError::InvalidTaskState),
+        }
+        self.ready();
+        Scheduler::enqueue_ready_task(self);
+        Scheduler::request_preempt();
+        Ok(())
+    }
+
+    /// 检查任务是否被挂起 - O(1)，原子操作
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        self.is_valid() && get_task_list()[self.id].state_atomic.load(Ordering::Acquire) == STATE_SUSPENDED
+    }
+
+    /// 结束当前任务
+    /// 
+    /// 将当前任务标记为 `Terminated` 并记录退出码，唤醒所有等待
+    /// join 该任务的任务，然后切换到其他任务，永不返回。
+    /// 任务函数正常返回等价于调用 `Task::exit(0)`。
+    /// 
+    /// 任务结束后，其槽位和栈可以被 `Task::new` 重新分配。
+    /// 
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    /// 
+    /// Task::new("worker", |_| {
+    ///     // 出错时提前结束并返回错误码
+    ///     Task::exit(-1);
+    /// }).unwrap();
+    /// ```
+    pub fn exit(code: i32) -> ! {
+        Scheduler::get_current_task().terminate(code);
+        loop {
+            trigger_schedule();
+        }
+    }
+
+    /// 将任务标记为已结束（内部使用）
+    /// 
+    /// 从就绪队列和等待队列中移除任务，将任务仍持有的锁标记为 OwnerDied，
+    /// 调用任务本地存储的析构函数，记录退出码并唤醒 join 该任务的任务。
+    pub(crate) fn terminate(&self, code: i32) {
+        let tcb = &get_task_list()[self.id];
+        Scheduler::remove_task(self);
+        self.leave_wait_list();
+        self.leave_blocked_on();
+        self.release_held_locks();
+        self.destroy_locals();
+        // 丢弃尚未执行的任务函数（任务从未被调度时）
+        drop(tcb.task_fn.lock().take());
+        tcb.set_terminated(code);
+        tcb.joiners.wake_all();
+    }
+
+    /// 删除任务
+    /// 
+    /// 将任务从所有就绪队列和等待队列中移除，将它持有的锁标记为 OwnerDied，
+    /// 以 `TASK_KILLED_EXIT_CODE` 结束任务并释放其槽位。
+    /// join 该任务的任务会被唤醒。
+    /// 
+    /// 删除当前任务时会先切换到其他任务，此时该方法不会返回；
+    /// 任务栈在切换完成前不会被重新分配。
+    /// 
+    /// 注意：被删除任务栈上的对象不会执行析构。
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 删除成功
+    /// - `Err(RtosError::TaskNotFound)`: 任务不存在
+    /// - `Err(RtosError::InvalidTaskState)`: 任务已经结束
+    /// 
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    /// 
+    /// let worker = Task::new("worker", |_| loop {}).unwrap();
+    /// // 监督任务发现 worker 异常后将其删除并重建
+    /// worker.delete().unwrap();
+    /// let worker = Task::new("worker", |_| loop {}).unwrap();
+    /// ```
+    pub fn delete(&self) -> Result<()> {
+        match self.get_state() {
+            TaskState::Uninit => return Err(RtosError::TaskNotFound),
+            TaskState::Terminated => return Err(RtosError::InvalidTaskState),
+            _ => {}
+        }
+        self.terminate(TASK_KILLED_EXIT_CODE);
+        if Scheduler::is_running() && Scheduler::get_current_task() == *self {
+            loop {
+                trigger_schedule();
+            }
+        }
+        Ok(())
+    }
+
+    /// 删除另一个任务
+    /// 
+    /// 等价于 `other.delete()`，详见 [`Task::delete`]。
+    pub fn kill(other: Task) -> Result<()> {
+        other.delete()
+    }
+
+    /// 记录任务所在的等待队列（内部使用）
+    /// 
+    /// 在任务加入等待队列后调用，任务被唤醒（`ready`）时自动清除。
+    pub(crate) fn set_wait_list(&self, list: &Mutex<WaiterList>) {
+        get_task_list()[self.id]
+            .wait_list
+            .store(list as *const Mutex<WaiterList> as usize, Ordering::Release);
+    }
+
+    /// 将任务移出其所在的等待队列
+    fn leave_wait_list(&self) {
+        let list = get_task_list()[self.id].wait_list.swap(0, Ordering::AcqRel);
+        if list != 0 {
+            // SAFETY: 阻塞中的任务持有等待对象的引用，等待队列在任务被唤醒前保持有效
+            let list = unsafe { &*(list as *const Mutex<WaiterList>) };
+            list.lock().remove(self.id);
+        }
+    }
+
+    /// 记录任务持有的锁（内部使用）
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 记录成功
+    /// - `Err(RtosError::TooManyHeldLocks)`: 追踪槽位已满（最多 `MAX_HELD_LOCKS` 个），调用者应放弃获取锁
+    pub(crate) fn track_lock(&self, lock: HeldLock) -> Result<()> {
+        let mut held = get_task_list()[self.id].held_locks.lock();
+        let slot = held
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(RtosError::TooManyHeldLocks)?;
+        *slot = Some(lock);
+        Ok(())
+    }
+
+    /// 取消记录任务持有的锁（内部使用）
+    pub(crate) fn untrack_lock(&self, addr: usize) {
+        let mut held = get_task_list()[self.id].held_locks.lock();
+        if let Some(slot) = held.iter_mut().find(|slot| matches!(slot, Some(lock) if lock.addr == addr)) {
+            *slot = None;
+        }
+    }
+
+    /// 获取任务当前持有的被追踪锁的数量
+    /// 
+    /// 句柄已失效时返回 0。
+    pub fn held_lock_count(&self) -> usize {
+        self.try_held_lock_count().unwrap_or(0)
+    }
+
+    /// 获取任务当前持有的被追踪锁的数量，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_held_lock_count(&self) -> Result<usize> {
+        Ok(self.tcb()?.held_locks.lock().iter().filter(|slot| slot.is_some()).count())
+    }
+
+    /// 记录任务正在等待的锁（内部使用）
+    /// 
+    /// 在任务阻塞在锁上之后调用，被唤醒后用 `None` 清除。
+    pub(crate) fn set_blocked_on(&self, lock: Option<HeldLock>) {
+        *get_task_list()[self.id].blocked_on.lock() = lock;
+    }
+
+    /// 结束对锁的等待，重新计算锁持有者的优先级
+    fn leave_blocked_on(&self) {
+        let lock = get_task_list()[self.id].blocked_on.lock().take();
+        // SAFETY: 阻塞中的任务持有锁对象的引用，锁对象在任务被唤醒前保持有效
+        if let Some(owner) = lock.and_then(|lock| unsafe { (lock.owner)(lock.addr) }) {
+            Task::propagate_priority(owner);
+        }
+    }
+
+    /// 根据基础优先级和持有的锁重新计算有效优先级（内部使用）
+    /// 
+    /// 有效优先级改变时同时调整任务在就绪队列中的位置。
+    /// 
+    /// # 返回值
+    /// 有效优先级是否改变
+    pub(crate) fn update_priority(&self) -> bool {
+        let tcb = &get_task_list()[self.id];
+        // 复制一份再计算，计算时会访问锁对象的等待队列
+        let held = *tcb.held_locks.lock();
+        let base = Priority::from_u8(tcb.base_priority.load(Ordering::Acquire)).unwrap_or(Priority::Normal);
+        let effective = held
+            .iter()
+            .flatten()
+            // SAFETY: 持有者持有锁对象的引用（或 Arc），锁对象在释放前保持有效
+            .filter_map(|lock| unsafe { (lock.boost)(lock.addr) })
+            .fold(base, Priority::max);
+
+        if effective == tcb.get_priority() {
+            return false;
+        }
+        Scheduler::requeue_task(self, || tcb.set_priority(effective));
+        true
+    }
+
+    /// 沿等待链传递优先级（内部使用）
+    /// 
+    /// 重新计算 `owner` 的有效优先级；如果发生变化且 `owner` 自己也在等待另一把锁，
+    /// 继续重新计算那把锁的持有者，直到优先级不再变化。
+    /// 最多走 `MAX_TASKS` 步，死锁形成的环不会导致死循环。
+    pub(crate) fn propagate_priority(owner: Task) {
+        let mut task = owner;
+        for _ in 0..MAX_TASKS {
+            if !task.update_priority() {
+                return;
+            }
+            let lock = *get_task_list()[task.id].blocked_on.lock();
+            // SAFETY: 阻塞中的任务持有锁对象的引用，锁对象在任务被唤醒前保持有效
+            match lock.and_then(|lock| unsafe { (lock.owner)(lock.addr) }) {
+                Some(next) if next != task => task = next,
+                _ => return,
+            }
+        }
+    }
+
+    /// 处理任务仍持有的所有锁
+    fn release_held_locks(&self) {
+        let held = core::mem::replace(&mut *get_task_list()[self.id].held_locks.lock(), [None; MAX_HELD_LOCKS]);
+        for lock in held.iter().flatten() {
+            // SAFETY: 锁对象的引用（或 Arc）仍保存在持有者的栈上，对象保持有效
+            unsafe { (lock.release)(lock.addr) };
+        }
+    }
+
+    /// 等待任务结束
+    /// 
+    /// 阻塞当前任务，直到目标任务结束，返回其退出码。
+    /// 如果目标任务已经结束，立即返回。
+    /// 
+    /// # 返回值
+    /// - `Ok(code)`: 目标任务的退出码
+    /// - `Err(RtosError::TaskNotFound)`: 目标任务不存在
+    /// - `Err(RtosError::InvalidArgument)`: 任务试图 join 自身
+    /// 
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    /// 
+    /// let worker = Task::new("worker", |_| {
+    ///     // 执行一次性工作...
+    /// }).unwrap();
+    /// 
+    /// let code = worker.join().unwrap();
+    /// assert_eq!(code, 0);
+    /// ```
+    pub fn join(&self) -> Result<i32> {
+        self.join_inner(None)
+    }
+
+    /// 带超时的等待任务结束
+    /// 
+    /// # 参数
+    /// - `timeout_ms`: 超时时间（毫秒）
+    /// 
+    /// # 返回值
+    /// - `Ok(code)`: 目标任务的退出码
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::TaskNotFound)`: 目标任务不存在
+    /// - `Err(RtosError::InvalidArgument)`: 任务试图 join 自身
+    /// - `Err(RtosError::TimerSlotsFull)`: 没有可用的定时器
+    pub fn join_timeout(&self, timeout_ms: usize) -> Result<i32> {
+        self.join_inner(Some(timeout_ms))
+    }
+
+    /// 尝试获取任务的退出码（非阻塞）
+    /// 
+    /// # 返回值
+    /// - `Ok(Some(code))`: 任务已结束
+    /// - `Ok(None)`: 任务尚未结束
+    /// - `Err(RtosError)`: 任务不存在或试图 join 自身
+    pub fn try_join(&self) -> Result<Option<i32>> {
+        match self.get_state() {
+            TaskState::Uninit => Err(RtosError::TaskNotFound),
+            TaskState::Terminated => Ok(self.exit_code()),
+            _ if Scheduler::is_running() && Scheduler::get_current_task() == *self => {
+                Err(RtosError::InvalidArgument)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 阻塞在目标任务的 join 等待队列上，直到目标任务结束或超时
+    ///
+    /// 在队列锁内检查目标任务的状态，`terminate` 先记录结束状态再唤醒队列，不会丢失唤醒。
+    fn join_inner(&self, timeout_ms: Option<usize>) -> Result<i32> {
+        let tcb = self.tcb()?;
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+        loop {
+            let remaining = deadline.map(|deadline| deadline.saturating_sub(Systick::get_current_time()));
+            let mut code = None;
+            tcb.joiners.wait_if(remaining, || {
+                code = self.try_join()?;
+                Ok(code.is_none())
+            })?;
+            if let Some(code) = code {
+                return Ok(code);
+            }
+        }
+    }
+
+    /// 获取任务退出码 - O(1)，原子操作
+    /// 
+    /// # 返回值
+    /// - `Some(code)`: 任务已结束
+    /// - `None`: 任务尚未结束
+    pub fn exit_code(&self) -> Option<i32> {
+        let tcb = &get_task_list()[self.id];
+        if self.is_valid() && tcb.state_atomic.load(Ordering::Acquire) == STATE_TERMINATED {
+            Some(tcb.exit_code.load(Ordering::Acquire))
+        } else {
+            None
+        }
     }
 
     /// 获取任务状态 - O(1)，原子操作（Blocked 状态需要锁）
+    /// 
+    /// 句柄已失效（槽位被新任务重用）时返回 `Uninit`。
     pub fn get_state(&self) -> TaskState {
-        get_task_list()[self.0].get_state()
+        if !self.is_valid() {
+            return TaskState::Uninit;
+        }
+        get_task_list()[self.id].get_state()
     }
 
     /// 获取任务名称 - O(1)，无锁（不变字段）
+    /// 
+    /// 句柄已失效时返回未初始化槽位的名称 `"noinit"`。
     pub fn get_name(&self) -> &'static str {
-        get_task_list()[self.0].name
+        self.tcb().map_or("noinit", |tcb| tcb.name)
     }
 
     /// 获取任务 ID - O(1)，无锁（不变字段）
     /// 
-    /// 注意：实际上 task ID 就是 self.0，这个方法保持向后兼容
+    /// 任务 ID 就是槽位下标，槽位被回收后会分配给新任务；
+    /// 需要区分新旧任务时比较 `Task` 句柄本身（包含槽位的代数）。
     #[inline]
     pub fn get_taskid(&self) -> usize {
-        self.0
+        self.id
     }
 
     /// 获取栈顶指针 - O(1)，原子操作
+    /// 
+    /// 句柄已失效时返回 0。
     pub fn get_stack_top(&self) -> usize {
-        get_task_list()[self.0].get_stack_top()
+        self.tcb().map_or(0, |tcb| tcb.get_stack_top())
     }
 
     /// 设置栈顶指针 - O(1)，原子操作
+    /// 
+    /// 句柄已失效时不做任何操作。
     pub fn set_stack_top(&mut self, stack_top: usize) {
-        get_task_list()[self.0].set_stack_top(stack_top);
+        if let Ok(tcb) = self.tcb() {
+            tcb.set_stack_top(stack_top);
+        }
+    }
+
+    /// 设置任务的时间片 - O(1)，原子操作
+    /// 
+    /// 启用优先级调度时，任务连续运行 `ticks` 个节拍后让给同优先级的其他就绪任务。
+    /// 
+    /// # 参数
+    /// - `ticks`: 时间片长度（节拍），0 表示使用 `Scheduler::set_time_slice` 设置的全局时间片
+    /// 
+    /// 句柄已失效时不做任何操作，需要知道是否设置成功时使用 `try_set_time_slice`。
+    pub fn set_time_slice(&mut self, ticks: usize) {
+        let _ = self.try_set_time_slice(ticks);
+    }
+
+    /// 设置任务的时间片
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 设置成功
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    pub fn try_set_time_slice(&mut self, ticks: usize) -> Result<()> {
+        self.tcb()?.time_slice.store(ticks, Ordering::Release);
+        Ok(())
+    }
+
+    /// 获取任务自己的时间片（节拍），0 表示使用全局时间片 - O(1)，原子操作
+    /// 
+    /// 句柄已失效时返回 0。
+    pub fn get_time_slice(&self) -> usize {
+        self.try_get_time_slice().unwrap_or(0)
+    }
+
+    /// 获取任务自己的时间片，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_time_slice(&self) -> Result<usize> {
+        Ok(self.tcb()?.time_slice.load(Ordering::Acquire))
+    }
+
+    /// 设置任务的绝对截止时间
+    /// 
+    /// 设置截止时间后任务成为 EDF 任务，在 `scheduler::EDF_BAND` 优先级按截止时间调度
+    /// （基础优先级高于频带的任务保持自身优先级）；设置为 `None` 后回到自己的固定优先级。
+    /// 就绪任务会被移到对应的就绪队列，新的截止时间是否导致抢占由下一次调度决定
+    /// （如 `Scheduler::preempt_check`）。EDF 仅在启用优先级调度时生效，
+    /// 默认的 `RoundRobinPolicy` 下截止时间会被记录但不影响调度。
+    /// 
+    /// # 参数
+    /// - `deadline`: 绝对截止时间（SysTick 节拍），`None` 表示清除
+    /// 
+    /// 句柄已失效时不做任何操作，需要知道是否设置成功时使用 `try_set_deadline`。
+    pub fn set_deadline(&mut self, deadline: Option<usize>) {
+        let _ = self.try_set_deadline(deadline);
+    }
+
+    /// 设置任务的绝对截止时间
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 设置成功
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    pub fn try_set_deadline(&mut self, deadline: Option<usize>) -> Result<()> {
+        let tcb = self.tcb()?;
+        Scheduler::requeue_task(self, || {
+            tcb.deadline.store(deadline.unwrap_or(NO_DEADLINE), Ordering::Release);
+        });
+        Ok(())
+    }
+
+    /// 获取任务的绝对截止时间（SysTick 节拍）- O(1)，原子操作
+    /// 
+    /// 不是 EDF 任务或句柄已失效时返回 `None`。
+    pub fn get_deadline(&self) -> Option<usize> {
+        self.try_get_deadline().ok().flatten()
+    }
+
+    /// 获取任务的绝对截止时间
+    /// 
+    /// # 返回值
+    /// - `Ok(Some(deadline))`: EDF 任务的截止时间（SysTick 节拍）
+    /// - `Ok(None)`: 不是 EDF 任务
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    pub fn try_get_deadline(&self) -> Result<Option<usize>> {
+        match self.tcb()?.deadline.load(Ordering::Acquire) {
+            NO_DEADLINE => Ok(None),
+            deadline => Ok(Some(deadline)),
+        }
+    }
+
+    /// 获取任务累计运行时间 - O(1)，原子操作
+    /// 
+    /// 单位为运行时间时钟的计数单位，默认为 SysTick 节拍，
+    /// 见 `Scheduler::set_runtime_clock`。句柄已失效时返回 0。
+    pub fn get_run_time(&self) -> usize {
+        self.try_get_run_time().unwrap_or(0)
+    }
+
+    /// 获取任务累计运行时间，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_run_time(&self) -> Result<usize> {
+        Ok(self.tcb()?.run_time.load(Ordering::Acquire))
+    }
+
+    /// 获取任务被切换进来运行的次数 - O(1)，原子操作
+    /// 
+    /// 句柄已失效时返回 0。
+    pub fn get_switch_count(&self) -> usize {
+        self.try_get_switch_count().unwrap_or(0)
+    }
+
+    /// 获取任务被切换进来运行的次数，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_switch_count(&self) -> Result<usize> {
+        Ok(self.tcb()?.switch_count.load(Ordering::Acquire))
+    }
+
+    /// 累加任务运行时间（调度器内部使用）
+    pub(crate) fn add_run_time(&self, elapsed: usize) {
+        get_task_list()[self.id].run_time.fetch_add(elapsed, Ordering::AcqRel);
+    }
+
+    /// 记录一次切入（调度器内部使用）
+    pub(crate) fn record_switch_in(&self) {
+        get_task_list()[self.id].switch_count.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// 获取任务栈大小（字节）- O(1)，原子操作
+    /// 
+    /// 句柄已失效时返回 0。
+    pub fn get_stack_size(&self) -> usize {
+        self.try_get_stack_size().unwrap_or(0)
+    }
+
+    /// 获取任务栈大小（字节），句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_stack_size(&self) -> Result<usize> {
+        Ok(self.tcb()?.stack_size.load(Ordering::Acquire))
+    }
+
+    /// 获取任务栈底地址（最低地址）- O(1)，原子操作
+    /// 
+    /// 句柄已失效时返回 0。
+    pub fn get_stack_base(&self) -> usize {
+        self.try_get_stack_base().unwrap_or(0)
+    }
+
+    /// 获取任务栈底地址（最低地址），句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_get_stack_base(&self) -> Result<usize> {
+        Ok(self.tcb()?.stack_base.load(Ordering::Acquire))
+    }
+
+    /// 获取栈高水位线 - O(n)，n 为栈大小
+    /// 
+    /// 返回任务运行以来栈中从未被使用过的字节数（不含栈底保护区），
+    /// 值越小说明栈越接近溢出。句柄已失效时返回 0。
+    /// 
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::kernel::task::Task;
+    /// 
+    /// let task = Task::new("worker", |_| {}).unwrap();
+    /// let free = task.stack_high_water_mark();
+    /// ```
+    pub fn stack_high_water_mark(&self) -> usize {
+        self.try_stack_high_water_mark().unwrap_or(0)
+    }
+
+    /// 获取栈高水位线，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_stack_high_water_mark(&self) -> Result<usize> {
+        let tcb = self.tcb()?;
+        let base = tcb.stack_base.load(Ordering::Acquire);
+        let size = tcb.stack_size.load(Ordering::Acquire);
+        if base == 0 || size <= stack::GUARD_SIZE {
+            return Ok(0);
+        }
+        // SAFETY: 栈区域在任务创建时由 prepare_guard 初始化
+        Ok(unsafe { stack::untouched_bytes(base, size) })
+    }
+
+    /// 检查任务栈是否溢出 - O(1)
+    /// 
+    /// 栈底金丝雀被破坏，或保存的栈顶指针越过保护区时返回 `true`；句柄已失效时返回 `false`。
+    pub fn is_stack_overflowed(&self) -> bool {
+        let Ok(tcb) = self.tcb() else {
+            return false;
+        };
+        let base = tcb.stack_base.load(Ordering::Acquire);
+        if base == 0 {
+            return false;
+        }
+        let stack_top = tcb.get_stack_top();
+        // SAFETY: 栈区域在任务创建时由 prepare_guard 初始化
+        stack_top < base + stack::GUARD_SIZE || unsafe { !stack::canary_intact(base) }
+    }
+
+    /// 检查任务栈并在溢出时调用溢出钩子
+    /// 
+    /// 由调度器在切出任务时调用。钩子返回后重新写入金丝雀，
+    /// 保证同一次溢出只报告一次。
+    pub(crate) fn check_stack(&self) {
+        if !self.is_stack_overflowed() {
+            return;
+        }
+        stack::report_overflow(*self);
+        let base = get_task_list()[self.id].stack_base.load(Ordering::Acquire);
+        // SAFETY: base 是该任务栈底地址，保护区属于任务栈
+        unsafe { core::ptr::write_volatile(base as *mut u64, stack::STACK_CANARY) };
     }
 
     /// 获取任务优先级 - O(1)，原子操作
     ///
     /// # 返回值
-    /// 任务的当前优先级
+    /// 任务的有效优先级（包括优先级继承和天花板提升）；句柄已失效时返回 `Priority::Normal`
     pub fn get_priority(&self) -> Priority {
-        get_task_list()[self.0].get_priority()
+        self.tcb().map_or(Priority::Normal, |tcb| tcb.get_priority())
+    }
+
+    /// 获取任务的基础优先级 - O(1)，原子操作
+    ///
+    /// # 返回值
+    /// 通过 `set_priority` 设置的优先级，不包括优先级继承和天花板提升；
+    /// 句柄已失效时返回 `Priority::Normal`
+    pub fn get_base_priority(&self) -> Priority {
+        self.try_get_base_priority().unwrap_or(Priority::Normal)
+    }
+
+    /// 获取任务的基础优先级
+    ///
+    /// # 返回值
+    /// - `Ok(priority)`: 通过 `set_priority` 设置的优先级，不包括优先级继承和天花板提升
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    pub fn try_get_base_priority(&self) -> Result<Priority> {
+        let prio = self.tcb()?.base_priority.load(Ordering::Acquire);
+        Ok(Priority::from_u8(prio).unwrap_or(Priority::Normal))
     }
 
-    /// 设置任务优先级 - O(1)，原子操作
+    /// 设置任务的基础优先级
+    ///
+    /// 任务持有优先级继承或天花板互斥锁时，有效优先级不会低于锁要求的优先级。
+    /// 有效优先级改变时任务在就绪队列中的位置随之调整，
+    /// 等待链上的锁持有者也会重新计算继承的优先级。
     ///
     /// # 参数
     /// - `priority`: 新的优先级
+    /// 
+    /// 句柄已失效时不做任何操作，需要知道是否设置成功时使用 `try_set_priority`。
     pub fn set_priority(&mut self, priority: Priority) {
-        get_task_list()[self.0].set_priority(priority);
+        let _ = self.try_set_priority(priority);
+    }
+
+    /// 设置任务的基础优先级
+    /// 
+    /// # 返回值
+    /// - `Ok(())`: 设置成功
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    pub fn try_set_priority(&mut self, priority: Priority) -> Result<()> {
+        let tcb = self.tcb()?;
+        tcb.base_priority.store(priority.as_u8(), Ordering::Release);
+        if !self.update_priority() {
+            return Ok(());
+        }
+        let lock = *tcb.blocked_on.lock();
+        // SAFETY: 阻塞中的任务持有锁对象的引用，锁对象在任务被唤醒前保持有效
+        if let Some(owner) = lock.and_then(|lock| unsafe { (lock.owner)(lock.addr) }) {
+            Task::propagate_priority(owner);
+        }
+        Ok(())
     }
     
     /// 批量获取任务信息 - 减少多次访问的开销
@@ -414,11 +1439,14 @@ impl Task {
     /// 当需要同时获取多个字段时，使用此方法可以减少原子操作次数
     /// 
     /// # 返回值
-    /// 返回 (state, priority, stack_top) 元组
+    /// 返回 (state, priority, stack_top) 元组；句柄已失效时返回
+    /// `(TaskState::Uninit, Priority::Normal, 0)`，与未初始化的槽位一致
     #[inline]
     pub fn get_info(&self) -> (TaskState, Priority, usize) {
-        let tcb = &get_task_list()[self.0];
-        (tcb.get_state(), tcb.get_priority(), tcb.get_stack_top())
+        match self.tcb() {
+            Ok(tcb) => (tcb.get_state(), tcb.get_priority(), tcb.get_stack_top()),
+            Err(_) => (TaskState::Uninit, Priority::Normal, 0),
+        }
     }
 
     /// 初始化任务系统
@@ -426,17 +1454,17 @@ impl Task {
         let task_list = get_task_list();
         let _alloc_guard = get_alloc_lock().lock();
         
-        for i in 0..MAX_TASKS {
-            task_list[i].reset();
+        for tcb in task_list.iter() {
+            tcb.reset();
         }
+        local::reset_keys();
         
         // TASK_STACKS 是静态数组，需要 unsafe 访问
         // 这是必要的 unsafe，因为我们需要重置静态可变数组
         unsafe {
-            for i in 0..MAX_TASKS {
-                TASK_STACKS[i] = Stack {
-                    data: [0; STACK_SIZE],
-                };
+            let stacks = &raw mut TASK_STACKS;
+            for stack in (*stacks).iter_mut() {
+                stack.data = [0; STACK_SIZE];
             }
         }
     }
@@ -453,7 +1481,7 @@ impl Task {
         let task_list = get_task_list();
         for i in 0..MAX_TASKS {
             if task_list[i].is_initialized() {
-                f(&mut Task(i), i);
+                f(&mut Task::from_id(i), i);
             }
         }
     }
@@ -470,12 +1498,12 @@ impl Task {
         let task_list = get_task_list();
         for i in start..MAX_TASKS {
             if task_list[i].is_initialized() {
-                f(&mut Task(i), i);
+                f(&mut Task::from_id(i), i);
             }
         }
         for i in 0..start {
             if task_list[i].is_initialized() {
-                f(&mut Task(i), i);
+                f(&mut Task::from_id(i), i);
             }
         }
     }
@@ -564,7 +1592,7 @@ impl Task {
     /// # 返回值
     ///
     /// - `Ok(TypedTaskAny)`: 成功转换，包含对应状态的 `TypedTask`
-    /// - `Err(RtosError::InvalidTaskState)`: 任务处于未初始化状态
+    /// - `Err(RtosError::InvalidTaskState)`: 任务处于未初始化或已结束状态
     ///
     /// # 示例
     ///
@@ -588,16 +1616,19 @@ impl Task {
     ///     TypedTaskAny::Created(created_task) => {
     ///         println!("Task is created");
     ///     }
+    ///     TypedTaskAny::Suspended(suspended_task) => {
+    ///         println!("Task is suspended");
+    ///     }
     /// }
     /// # Ok(())
     /// # }
     /// ```
     pub fn into_typed(self) -> Result<state::TypedTaskAny> {
-        use state::{TypedTask, TypedTaskAny, Created, Ready, Running, Blocked};
+        use state::{TypedTask, TypedTaskAny};
         use core::marker::PhantomData;
 
         match self.get_state() {
-            TaskState::Uninit => Err(RtosError::InvalidTaskState),
+            TaskState::Uninit | TaskState::Terminated => Err(RtosError::InvalidTaskState),
             TaskState::Ready => Ok(TypedTaskAny::Ready(TypedTask {
                 inner: self,
                 blocked_event: None,
@@ -613,6 +1644,11 @@ impl Task {
                 blocked_event: Some(event),
                 _state: PhantomData,
             })),
+            TaskState::Suspended => Ok(TypedTaskAny::Suspended(TypedTask {
+                inner: self,
+                blocked_event: None,
+                _state: PhantomData,
+            })),
         }
     }
 }
@@ -646,7 +1682,7 @@ impl Iterator for TaskIter {
             self.current += 1;
             // 使用原子操作检查状态，无需锁
             if task_list[idx].is_initialized() {
-                return Some(Task(idx));
+                return Some(Task::from_id(idx));
             }
         }
         None
@@ -666,6 +1702,12 @@ pub struct TaskSnapshot {
     pub priority: Priority,
     /// 任务名称
     pub name: &'static str,
+    /// 任务栈大小（字节）
+    pub stack_size: usize,
+    /// 累计运行时间（运行时间时钟计数）
+    pub run_time: usize,
+    /// 被切换进来运行的次数
+    pub switch_count: usize,
 }
 
 /// 任务快照迭代器
@@ -720,6 +1762,9 @@ impl TaskSnapshotIter {
                     state: task_list[i].get_state(),
                     priority: task_list[i].get_priority(),
                     name: task_list[i].name,
+                    stack_size: task_list[i].stack_size.load(Ordering::Acquire),
+                    run_time: task_list[i].run_time.load(Ordering::Acquire),
+                    switch_count: task_list[i].switch_count.load(Ordering::Acquire),
                 });
                 count += 1;
             }
@@ -1027,6 +2072,7 @@ mod tests {
         assert_eq!(snapshots[0].name, "task1");
         assert_eq!(snapshots[1].name, "task2");
         assert_eq!(snapshots[2].name, "task3");
+        assert!(snapshots.iter().all(|s| s.stack_size == STACK_SIZE));
     }
 
     #[test]
@@ -1110,6 +2156,391 @@ mod tests {
         assert!(matches!(blocked_snapshots[0].state, TaskState::Blocked(_)));
     }
 
+    // ========================================================================
+    // 任务结束与 join 测试
+    // ========================================================================
+
+    #[test]
+    #[serial]
+    fn test_task_terminate_and_join() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        assert_eq!(worker.try_join(), Ok(None));
+        assert_eq!(worker.exit_code(), None);
+
+        worker.terminate(7);
+        assert_eq!(worker.get_state(), TaskState::Terminated);
+        assert_eq!(worker.exit_code(), Some(7));
+        assert_eq!(worker.join(), Ok(7));
+        assert_eq!(worker.join_timeout(10), Ok(7));
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_join_errors() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 不能 join 自身
+        assert_eq!(main.join(), Err(RtosError::InvalidArgument));
+        // 不存在的任务
+        assert_eq!(Task::from_id(5).join(), Err(RtosError::TaskNotFound));
+        // 超时时间为 0 且任务未结束
+        assert_eq!(worker.join_timeout(0), Err(RtosError::Timeout));
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_terminate_wakes_joiner() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut joiner = Task::new("joiner", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        let joiners = &get_task_list()[worker.get_taskid()].joiners;
+        assert!(joiners.enqueue(joiner));
+        joiner.block(Event::WaitQueue(joiners.id()));
+        worker.terminate(0);
+        assert_eq!(joiner.get_state(), TaskState::Ready);
+        assert!(joiners.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_join_timeout() {
+        use crate::hal::test::set_schedule_hook;
+        use crate::kernel::time::timer::Timer;
+
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+        let joiners = &get_task_list()[worker.get_taskid()].joiners;
+
+        // 阻塞期间 SysTick 到期，定时器唤醒 join 的任务并返回超时
+        set_schedule_hook(Some(Box::new(|| {
+            Systick::add_current_time(10);
+            Timer::timer_check_and_send_event();
+            assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Ready);
+        })));
+        assert_eq!(worker.join_timeout(10), Err(RtosError::Timeout));
+        assert!(joiners.is_empty());
+        assert_eq!(main.get_state(), TaskState::Running);
+
+        // 超时之前目标任务结束，返回退出码
+        set_schedule_hook(Some(Box::new(move || worker.terminate(7))));
+        assert_eq!(worker.join_timeout(10), Ok(7));
+        set_schedule_hook(None);
+        assert!(joiners.is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_slot_reuse_after_terminate() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        for _ in 1..MAX_TASKS {
+            Task::new("worker", |_| {}).unwrap();
+        }
+        Scheduler::start();
+        assert_eq!(Task::new("extra", |_| {}).err(), Some(RtosError::TaskSlotsFull));
+
+        let mut worker = Task::from_id(3);
+        worker.set_priority(Priority::High);
+        worker.terminate(1);
+
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), 3);
+        assert_eq!(reused.get_name(), "reused");
+        assert_eq!(reused.get_state(), TaskState::Ready);
+        assert_eq!(reused.get_priority(), Priority::Normal);
+        assert_eq!(reused.exit_code(), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_stale_task_handle() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut stale = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        stale.terminate(1);
+        assert_eq!(stale.join(), Ok(1));
+
+        // 槽位被新任务重用后，旧句柄不再指向任何任务
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), stale.get_taskid());
+        assert_ne!(reused, stale);
+        assert!(!stale.is_valid());
+        assert_eq!(stale.get_state(), TaskState::Uninit);
+        assert_eq!(stale.join(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.delete(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.suspend(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_set_priority(Priority::High), Err(RtosError::TaskNotFound));
+        assert_eq!(reused.get_priority(), Priority::Normal);
+        assert_eq!(reused.get_state(), TaskState::Ready);
+
+        // 读取和修改槽位数据的接口同样拒绝旧句柄
+        assert_eq!(stale.get_info(), (TaskState::Uninit, Priority::Normal, 0));
+        assert_eq!(reused.get_info().0, TaskState::Ready);
+        assert_eq!(stale.get_name(), "noinit");
+        assert_eq!(stale.get_stack_top(), 0);
+        assert_eq!(stale.try_get_stack_size(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_stack_high_water_mark(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_get_run_time(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_held_lock_count(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_get_base_priority(), Err(RtosError::TaskNotFound));
+        let key = local::TaskLocalKey::new().unwrap();
+        reused.set_local(key, 3);
+        assert_eq!(stale.try_get_local(key), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_set_local(key, 9), Err(RtosError::TaskNotFound));
+        assert_eq!(reused.get_local(key), 3);
+        key.delete();
+        assert_eq!(stale.notify(1, NotifyAction::SetBits), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_notify_value(), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_is_notify_pending(), Err(RtosError::TaskNotFound));
+        assert!(!reused.is_notify_pending());
+    }
+
+    #[test]
+    #[serial]
+    fn test_stale_handle_set_priority() {
+        kernel_init();
+        let mut stale = Task::new("worker", |_| {}).unwrap();
+        stale.terminate(0);
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), stale.get_taskid());
+
+        assert_eq!(stale.try_set_priority(Priority::Critical), Err(RtosError::TaskNotFound));
+        // 不返回结果的接口对旧句柄不做任何操作
+        stale.set_priority(Priority::Critical);
+        assert_eq!(reused.get_priority(), Priority::Normal);
+        assert_eq!(reused.get_base_priority(), Priority::Normal);
+    }
+
+    #[test]
+    #[serial]
+    fn test_stale_handle_set_deadline() {
+        kernel_init();
+        let mut stale = Task::new("worker", |_| {}).unwrap();
+        stale.terminate(0);
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), stale.get_taskid());
+
+        assert_eq!(stale.try_set_deadline(Some(10)), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_get_deadline(), Err(RtosError::TaskNotFound));
+        stale.set_deadline(Some(10));
+        assert_eq!(stale.get_deadline(), None);
+        assert_eq!(reused.get_deadline(), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_stale_handle_set_time_slice() {
+        kernel_init();
+        let mut stale = Task::new("worker", |_| {}).unwrap();
+        stale.terminate(0);
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), stale.get_taskid());
+
+        assert_eq!(stale.try_set_time_slice(5), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.try_get_time_slice(), Err(RtosError::TaskNotFound));
+        stale.set_time_slice(5);
+        assert_eq!(stale.get_time_slice(), 0);
+        assert_eq!(reused.get_time_slice(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_current_task_slot_not_reused() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 当前任务结束后仍在使用自己的栈，直到被切换走
+        main.terminate(0);
+        let next = Task::new("next", |_| {}).unwrap();
+        assert_ne!(next.get_taskid(), main.get_taskid());
+    }
+
+    // ========================================================================
+    // 挂起与恢复测试
+    // ========================================================================
+
+    #[test]
+    #[serial]
+    fn test_task_suspend_resume() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+        let queued = Scheduler::ready_task_stats().total;
+
+        worker.suspend().unwrap();
+        assert_eq!(worker.get_state(), TaskState::Suspended);
+        assert!(worker.is_suspended());
+        assert_eq!(Scheduler::ready_task_stats().total, queued - 1);
+        assert_eq!(worker.suspend(), Err(RtosError::InvalidTaskState));
+
+        worker.resume().unwrap();
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert_eq!(Scheduler::ready_task_stats().total, queued);
+        assert_eq!(worker.resume(), Err(RtosError::InvalidTaskState));
+    }
+
+    #[test]
+    #[serial]
+    fn test_resume_pends_preemption() {
+        use crate::hal::test::set_schedule_hook;
+
+        kernel_init();
+        let main = Task::builder("main").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut urgent = Task::builder("urgent").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        urgent.suspend().unwrap();
+        Scheduler::start();
+
+        // 恢复更高优先级的任务只触发调度，当前任务在 task_switch 保存上下文之前保持不变
+        let pended = std::rc::Rc::new(core::cell::Cell::new(false));
+        let flag = pended.clone();
+        set_schedule_hook(Some(Box::new(move || {
+            assert_eq!(Scheduler::get_current_task(), main);
+            flag.set(true);
+        })));
+        urgent.resume().unwrap();
+        set_schedule_hook(None);
+        assert!(pended.get());
+        assert_eq!(Scheduler::get_current_task(), main);
+        assert_eq!(main.get_state(), TaskState::Running);
+
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), urgent);
+    }
+
+    #[test]
+    #[serial]
+    fn test_suspended_task_ignores_events() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        worker.block(Event::Signal(3));
+        worker.suspend().unwrap();
+        Event::wake_task(Event::Signal(3));
+        assert_eq!(worker.get_state(), TaskState::Suspended);
+
+        // 挂起的任务不会被调度
+        Scheduler::task_switch();
+        assert_ne!(Scheduler::get_current_task(), worker);
+
+        worker.resume().unwrap();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), worker);
+    }
+
+    #[test]
+    #[serial]
+    fn test_suspend_aborts_semaphore_wait() {
+        use crate::hal::test::set_schedule_hook;
+        use crate::sync::StaticSemaphore;
+
+        static SEM: StaticSemaphore = StaticSemaphore::new(0);
+
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        // main 阻塞在信号量上时被挂起：离开等待队列并取消超时定时器
+        set_schedule_hook(Some(Box::new(move || {
+            let mut task = main;
+            assert!(matches!(task.get_state(), TaskState::Blocked(_)));
+            task.suspend().unwrap();
+            assert_eq!(SEM.handle().waiter_count(), 0);
+            let mut running = 0;
+            Timer::for_each_used(|timer, _| {
+                running += timer.is_running() as usize;
+                false
+            });
+            assert_eq!(running, 0);
+            task.resume().unwrap();
+        })));
+        // 恢复后等待返回错误，而不是当作获得了许可
+        assert_eq!(SEM.acquire_timeout(100), Err(RtosError::WaitAborted));
+        set_schedule_hook(None);
+
+        // 之后的等待不受影响
+        SEM.release().unwrap();
+        assert_eq!(SEM.acquire_timeout(100), Ok(()));
+    }
+
+    // ========================================================================
+    // 任务删除测试
+    // ========================================================================
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_task_delete_releases_resources() {
+        use crate::sync::{Mutex, Signal};
+
+        kernel_init();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        let supervisor = Task::new("supervisor", |_| {}).unwrap();
+        let mut joiner = Task::new("joiner", |_| {}).unwrap();
+        Scheduler::start();
+
+        // worker 持有互斥锁后阻塞在信号上
+        let mutex = Mutex::new(0);
+        core::mem::forget(mutex.lock().unwrap());
+        assert_eq!(worker.held_lock_count(), 1);
+        let signal = Signal::new();
+        signal.wait().unwrap();
+        assert_eq!(signal.waiter_count(), 1);
+        let joiners = &get_task_list()[worker.get_taskid()].joiners;
+        assert!(joiners.enqueue(joiner));
+        joiner.block(Event::WaitQueue(joiners.id()));
+
+        // 切换到 supervisor 后删除 worker
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), supervisor);
+        Task::kill(worker).unwrap();
+
+        assert_eq!(worker.get_state(), TaskState::Terminated);
+        assert_eq!(worker.exit_code(), Some(TASK_KILLED_EXIT_CODE));
+        assert_eq!(signal.waiter_count(), 0);
+        assert_eq!(worker.held_lock_count(), 0);
+        assert!(mutex.is_poisoned());
+        assert!(!mutex.is_locked());
+        assert_eq!(joiner.get_state(), TaskState::Ready);
+
+        // 槽位可被重新使用
+        let respawned = Task::new("worker", |_| {}).unwrap();
+        assert_eq!(respawned.get_taskid(), worker.get_taskid());
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_delete_errors() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        assert_eq!(Task::from_id(MAX_TASKS - 1).delete(), Err(RtosError::TaskNotFound));
+        worker.delete().unwrap();
+        assert_eq!(worker.delete(), Err(RtosError::InvalidTaskState));
+        assert_eq!(Scheduler::ready_task_stats().total, 1);
+    }
+
     #[test]
     #[serial]
     fn test_snapshot_iter_exact_size() {
@@ -0,0 +1,478 @@
+//! 任务通知
+//!
+//! 每个任务在 TCB 中拥有一个 32 位通知值和一个"待处理"标志，
+//! 适用于一个生产者（任务或中断）向一个已知的消费者任务传递事件或数值，
+//! 无需为此分配 `Signal` / `Semaphore`。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::task::{NotifyAction, Task};
+//!
+//! const RX_DONE: u32 = 1 << 0;
+//! const TX_DONE: u32 = 1 << 1;
+//!
+//! let consumer = Task::new("uart", |_| loop {
+//!     // 进入时不清除，退出时清除所有位
+//!     let bits = Task::notify_wait(0, u32::MAX, None).unwrap();
+//!     if bits & RX_DONE != 0 { /* 处理接收 */ }
+//!     if bits & TX_DONE != 0 { /* 处理发送 */ }
+//! }).unwrap();
+//!
+//! // 在中断处理函数中：只登记通知，由下一次 SysTick 或任务切换更新通知值并唤醒接收者
+//! consumer.notify_from_isr(RX_DONE, NotifyAction::SetBits).unwrap();
+//! ```
+
+use super::{get_task_list, Task, TaskState, STATE_TERMINATED, STATE_UNINIT};
+use crate::error::{Result, RtosError};
+use crate::kernel::deferred::{self, Deferred};
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::time::systick::Systick;
+use crate::sync::event::Event;
+use core::sync::atomic::Ordering;
+
+/// 延迟通知的动作编码：只唤醒等待者，通知值已经更新
+const WAKE_ONLY: usize = usize::MAX;
+
+/// 通知对接收任务通知值的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyAction {
+    /// 不修改通知值，仅标记为待处理
+    NoAction,
+    /// 将 `value` 按位或到通知值
+    SetBits,
+    /// 通知值加一（忽略 `value`），可作为轻量计数信号量
+    Increment,
+    /// 用 `value` 覆盖通知值
+    Overwrite,
+    /// 仅当没有待处理的通知时写入 `value`
+    NoOverwrite,
+}
+
+impl NotifyAction {
+    fn to_raw(self) -> usize {
+        match self {
+            NotifyAction::NoAction => 0,
+            NotifyAction::SetBits => 1,
+            NotifyAction::Increment => 2,
+            NotifyAction::Overwrite => 3,
+            NotifyAction::NoOverwrite => 4,
+        }
+    }
+
+    fn from_raw(raw: usize) -> Option<Self> {
+        match raw {
+            0 => Some(NotifyAction::NoAction),
+            1 => Some(NotifyAction::SetBits),
+            2 => Some(NotifyAction::Increment),
+            3 => Some(NotifyAction::Overwrite),
+            4 => Some(NotifyAction::NoOverwrite),
+            _ => None,
+        }
+    }
+}
+
+/// 任务通知状态（保存在 TCB 中）
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NotifyState {
+    pub(crate) value: u32,
+    pub(crate) pending: bool,
+}
+
+impl NotifyState {
+    /// 按 `action` 更新通知值并标记为待处理
+    fn apply(&mut self, value: u32, action: NotifyAction) -> Result<()> {
+        match action {
+            NotifyAction::NoAction => {}
+            NotifyAction::SetBits => self.value |= value,
+            NotifyAction::Increment => self.value = self.value.wrapping_add(1),
+            NotifyAction::Overwrite => self.value = value,
+            NotifyAction::NoOverwrite => {
+                if self.pending {
+                    return Err(RtosError::NotificationPending);
+                }
+                self.value = value;
+            }
+        }
+        self.pending = true;
+        Ok(())
+    }
+}
+
+impl Task {
+    /// 向任务发送通知
+    ///
+    /// 按 `action` 更新任务的通知值并标记为待处理，
+    /// 如果任务正阻塞在 `notify_wait` 上则将其唤醒。
+    /// 会等待任务持有的锁，不能在中断中调用，中断中使用 `notify_from_isr`。
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 通知成功
+    /// - `Err(RtosError::NotificationPending)`: `NoOverwrite` 时已有待处理的通知
+    /// - `Err(RtosError::TaskNotFound)`: 任务不存在
+    /// - `Err(RtosError::InvalidTaskState)`: 任务已经结束
+    pub fn notify(&self, value: u32, action: NotifyAction) -> Result<()> {
+        let tcb = self.tcb()?;
+        match tcb.get_state() {
+            TaskState::Uninit => return Err(RtosError::TaskNotFound),
+            TaskState::Terminated => return Err(RtosError::InvalidTaskState),
+            _ => {}
+        }
+
+        tcb.notify.lock().apply(value, action)?;
+
+        // 被唤醒的接收者放回就绪队列，优先级更高时请求抢占
+        if tcb.notify_waiters.wake_all() > 0 {
+            Scheduler::request_preempt();
+        }
+        Ok(())
+    }
+
+    /// 在中断中向任务发送通知
+    ///
+    /// 只检查句柄并登记通知，不等待任何锁；通知值的更新和接收者的唤醒
+    /// 在下一次 SysTick 或任务切换时完成。`NoOverwrite` 的冲突此时才能发现，
+    /// 冲突的通知被丢弃。
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 已登记通知
+    /// - `Err(RtosError::TaskNotFound)`: 句柄已失效
+    /// - `Err(RtosError::QueueFull)`: 中断延迟处理队列已满
+    pub fn notify_from_isr(&self, value: u32, action: NotifyAction) -> Result<()> {
+        if !self.is_valid() {
+            return Err(RtosError::TaskNotFound);
+        }
+        deferred::defer(Deferred {
+            func: Self::notify_deferred,
+            args: [self.id, self.generation as usize, value as usize, action.to_raw()],
+        })
+    }
+
+    /// 执行 `notify_from_isr` 登记的通知
+    ///
+    /// 在节拍或任务切换的中断中执行，被打断的任务可能持有通知状态、等待队列、
+    /// 任务状态或调度器的锁，因此只尝试加锁，锁被占用时重新登记，留到下一次处理。
+    fn notify_deferred(args: [usize; 4]) {
+        let task = Task { id: args[0], generation: args[1] as u32 };
+        let Ok(tcb) = task.tcb() else {
+            return;
+        };
+        // 不读取阻塞事件：那需要等待任务状态的锁
+        if matches!(tcb.state_atomic.load(Ordering::Acquire), STATE_UNINIT | STATE_TERMINATED) {
+            return;
+        }
+
+        if let Some(action) = NotifyAction::from_raw(args[3]) {
+            let Some(mut state) = tcb.notify.try_lock() else {
+                let _ = deferred::defer(Deferred { func: Self::notify_deferred, args });
+                return;
+            };
+            if state.apply(args[2] as u32, action).is_err() {
+                return;
+            }
+        }
+
+        if tcb.notify_waiters.try_wake_all().is_none() {
+            // 通知值已经更新，下一次只需要唤醒等待者
+            let args = [args[0], args[1], 0, WAKE_ONLY];
+            let _ = deferred::defer(Deferred { func: Self::notify_deferred, args });
+        }
+    }
+
+    /// 等待当前任务的通知
+    ///
+    /// # 参数
+    /// - `clear_on_entry`: 没有待处理的通知时，进入等待前从通知值中清除的位
+    /// - `clear_on_exit`: 收到通知后，返回前从通知值中清除的位
+    /// - `timeout_ms`: 超时时间（毫秒），`None` 表示一直等待
+    ///
+    /// # 返回值
+    /// - `Ok(value)`: 收到通知，返回清除 `clear_on_exit` 之前的通知值
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::TimerSlotsFull)`: 带超时等待时没有可用的定时器
+    pub fn notify_wait(clear_on_entry: u32, clear_on_exit: u32, timeout_ms: Option<usize>) -> Result<u32> {
+        let current = Scheduler::get_current_task();
+        let tcb = &get_task_list()[current.id];
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+
+        {
+            let mut state = tcb.notify.lock();
+            if !state.pending {
+                state.value &= !clear_on_entry;
+            }
+        }
+
+        loop {
+            // 在等待队列锁内检查待处理标志，notify 先标记再唤醒，不会丢失唤醒
+            let remaining = deadline.map(|deadline| deadline.saturating_sub(Systick::get_current_time()));
+            let mut received = None;
+            tcb.notify_waiters.wait_if_as(Event::Notify(current.id), remaining, || {
+                let mut state = tcb.notify.lock();
+                if state.pending {
+                    received = Some(state.value);
+                    state.value &= !clear_on_exit;
+                    state.pending = false;
+                }
+                Ok(received.is_none())
+            })?;
+            if let Some(value) = received {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// 获取任务当前的通知值
+    ///
+    /// 句柄已失效时返回 0。
+    pub fn notify_value(&self) -> u32 {
+        self.try_notify_value().unwrap_or(0)
+    }
+
+    /// 获取任务当前的通知值，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_notify_value(&self) -> Result<u32> {
+        Ok(self.tcb()?.notify.lock().value)
+    }
+
+    /// 检查任务是否有待处理的通知
+    ///
+    /// 句柄已失效时返回 `false`。
+    pub fn is_notify_pending(&self) -> bool {
+        self.try_is_notify_pending().unwrap_or(false)
+    }
+
+    /// 检查任务是否有待处理的通知，句柄已失效时返回 `Err(RtosError::TaskNotFound)`
+    pub fn try_is_notify_pending(&self) -> Result<bool> {
+        Ok(self.tcb()?.notify.lock().pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::Priority;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    /// 模拟任务阻塞在 `notify_wait` 上，返回阻塞事件
+    fn block_on_notify(mut task: Task) -> Event {
+        let waiters = &get_task_list()[task.get_taskid()].notify_waiters;
+        assert!(waiters.enqueue(task));
+        let event = Event::Notify(task.get_taskid());
+        task.block(event);
+        event
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_actions() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        worker.notify(0b01, NotifyAction::SetBits).unwrap();
+        worker.notify(0b10, NotifyAction::SetBits).unwrap();
+        assert_eq!(worker.notify_value(), 0b11);
+        assert!(worker.is_notify_pending());
+
+        worker.notify(0, NotifyAction::Increment).unwrap();
+        assert_eq!(worker.notify_value(), 0b100);
+
+        worker.notify(42, NotifyAction::Overwrite).unwrap();
+        assert_eq!(worker.notify_value(), 42);
+
+        assert_eq!(worker.notify(7, NotifyAction::NoOverwrite), Err(RtosError::NotificationPending));
+        assert_eq!(worker.notify_value(), 42);
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_wait_pending() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        main.notify(0b1010, NotifyAction::SetBits).unwrap();
+        // 已有待处理通知时不执行进入清除
+        assert_eq!(Task::notify_wait(u32::MAX, 0b0010, None), Ok(0b1010));
+        assert_eq!(main.notify_value(), 0b1000);
+        assert!(!main.is_notify_pending());
+
+        // 没有通知时先执行进入清除，超时返回
+        assert_eq!(Task::notify_wait(0b1000, 0, Some(0)), Err(RtosError::Timeout));
+        assert_eq!(main.notify_value(), 0);
+        assert_eq!(main.get_state(), TaskState::Running);
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_wait_timeout() {
+        use crate::hal::test::set_schedule_hook;
+        use crate::kernel::time::timer::Timer;
+
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 阻塞期间 SysTick 到期，定时器唤醒等待通知的任务并返回超时
+        set_schedule_hook(Some(Box::new(|| {
+            Systick::add_current_time(10);
+            Timer::timer_check_and_send_event();
+            assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Ready);
+        })));
+        assert_eq!(Task::notify_wait(0, 0, Some(10)), Err(RtosError::Timeout));
+        assert_eq!(main.get_state(), TaskState::Running);
+
+        // 超时之前收到通知
+        set_schedule_hook(Some(Box::new(move || main.notify(3, NotifyAction::SetBits).unwrap())));
+        assert_eq!(Task::notify_wait(0, u32::MAX, Some(10)), Ok(3));
+        set_schedule_hook(None);
+        assert_eq!(main.notify_value(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_wakes_blocked_task() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        let other = Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        block_on_notify(worker);
+        let other_event = block_on_notify(other);
+        worker.notify(1, NotifyAction::Overwrite).unwrap();
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert_eq!(other.get_state(), TaskState::Blocked(other_event));
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_wakes_into_ready_queue() {
+        kernel_init();
+        let main = Task::builder("main").priority(Priority::Normal).spawn(|_| {}).unwrap();
+        let low = Task::builder("low").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let high = Task::builder("high").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        Scheduler::start();
+
+        // 两个接收者阻塞等待通知并离开就绪队列，由调度器切换到 main
+        block_on_notify(low);
+        block_on_notify(high);
+        Scheduler::dequeue_task(&low);
+        Scheduler::dequeue_task(&high);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), main);
+        let low_queued = || Scheduler::ready_task_stats().by_priority[Priority::Low.as_u8() as usize];
+        assert_eq!(low_queued(), 0);
+
+        // 低优先级的接收者进入就绪队列，不抢占
+        low.notify(1, NotifyAction::SetBits).unwrap();
+        assert_eq!(low.get_state(), TaskState::Ready);
+        assert_eq!(low_queued(), 1);
+        assert_eq!(Scheduler::get_current_task(), main);
+
+        // 高优先级的接收者请求抢占，在任务切换时运行
+        high.notify(1, NotifyAction::SetBits).unwrap();
+        assert_eq!(Scheduler::get_current_task(), main);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_invalid_target() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        assert_eq!(Task::from_id(5).notify(1, NotifyAction::SetBits), Err(RtosError::TaskNotFound));
+        worker.delete().unwrap();
+        assert_eq!(worker.notify(1, NotifyAction::SetBits), Err(RtosError::InvalidTaskState));
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_stale_handle() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let stale = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        stale.terminate(0);
+        stale.join().unwrap();
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_taskid(), stale.get_taskid());
+
+        // 旧句柄不会通知重用槽位的新任务
+        assert_eq!(stale.notify(1, NotifyAction::SetBits), Err(RtosError::TaskNotFound));
+        assert_eq!(stale.notify_from_isr(1, NotifyAction::SetBits), Err(RtosError::TaskNotFound));
+        assert!(!reused.is_notify_pending());
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_from_isr_defers_wakeup() {
+        use crate::kernel::time::timer::Timer;
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        let event = block_on_notify(worker);
+        assert_eq!(event, Event::Notify(worker.get_taskid()));
+
+        // 中断中只登记通知，不修改通知值也不唤醒
+        worker.notify_from_isr(0b01, NotifyAction::SetBits).unwrap();
+        worker.notify_from_isr(0b10, NotifyAction::SetBits).unwrap();
+        assert_eq!(worker.notify_value(), 0);
+        assert_eq!(worker.get_state(), TaskState::Blocked(event));
+
+        // 下一次 SysTick 执行登记的通知
+        Timer::timer_check_and_send_event();
+        assert_eq!(worker.notify_value(), 0b11);
+        assert!(worker.is_notify_pending());
+        assert_eq!(worker.get_state(), TaskState::Ready);
+    }
+
+    #[test]
+    #[serial]
+    fn test_notify_from_isr_retries_when_locked() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        let event = block_on_notify(worker);
+        worker.notify_from_isr(5, NotifyAction::Overwrite).unwrap();
+
+        // 被打断的任务持有通知状态的锁时，留到下一次处理
+        {
+            let _state = get_task_list()[worker.get_taskid()].notify.lock();
+            deferred::run_deferred();
+        }
+        assert_eq!(worker.get_state(), TaskState::Blocked(event));
+
+        // 被打断的任务持有等待队列的锁时，通知值已更新，唤醒留到下一次处理
+        {
+            let _waiters = get_task_list()[worker.get_taskid()].notify_waiters.waiters.lock();
+            deferred::run_deferred();
+        }
+        assert_eq!(worker.notify_value(), 5);
+        assert_eq!(worker.get_state(), TaskState::Blocked(event));
+
+        // 被打断的任务持有接收者状态的锁时，接收者留在等待队列中
+        {
+            let _event = get_task_list()[worker.get_taskid()].blocked_event.lock();
+            deferred::run_deferred();
+        }
+        assert_eq!(worker.get_state(), TaskState::Blocked(event));
+        assert_eq!(get_task_list()[worker.get_taskid()].notify_waiters.len(), 1);
+
+        deferred::run_deferred();
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert_eq!(worker.notify_value(), 5);
+    }
+}
@@ -0,0 +1,293 @@
+//! 周期任务
+//!
+//! 周期任务由内核按固定周期释放：任务体每个周期被调用一次，返回后任务阻塞到下一个释放时刻。
+//! 释放时刻按 `上一次释放时刻 + 周期` 推进，不会像 `loop { work(); Delay::delay(n) }`
+//! 那样随任务体的执行时间漂移。
+//!
+//! ```text
+//!  release        deadline            release + period
+//!     │  任务体运行  │                        │
+//!     ├────────────┼────────────────────────┤
+//!     │◄─ 相对截止时间 ─►│                        │
+//!     │◄──────────────── 周期 ──────────────►│
+//! ```
+//!
+//! - 每次释放时任务的绝对截止时间设为 `释放时刻 + 相对截止时间`，周期任务按 EDF 调度
+//! - 任务体在截止时间之后才返回记为一次截止时间错过，并调用错过钩子
+//! - 任务体在下一个释放时刻之后才返回记为一次超限，错过的释放被跳过，
+//!   下一次释放对齐到之后的第一个周期边界
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::task::{Task, set_deadline_miss_hook};
+//!
+//! fn on_miss(task: Task, deadline: usize) {
+//!     panic!("task {} missed its deadline at tick {}", task.get_name(), deadline);
+//! }
+//!
+//! set_deadline_miss_hook(on_miss);
+//!
+//! // 每 10 个节拍释放一次，必须在释放后 5 个节拍内完成
+//! let control = Task::builder("control")
+//!     .periodic(10, 5)
+//!     .spawn(|_| {
+//!         // 一次控制周期的工作，返回后等待下一次释放
+//!     })
+//!     .unwrap();
+//!
+//! let _ = (control.deadline_miss_count(), control.overrun_count());
+//! ```
+
+use super::{get_task_list, Task, TaskBuilder};
+use crate::error::{Result, RtosError};
+use crate::hal::trigger_schedule;
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::time::systick::Systick;
+use crate::kernel::time::timer::Delay;
+use spin::Mutex;
+
+/// 截止时间错过钩子类型
+///
+/// 参数为错过截止时间的任务句柄和被错过的绝对截止时间（SysTick 节拍）
+pub type DeadlineMissHook = fn(Task, usize);
+
+/// 截止时间错过钩子
+static DEADLINE_MISS_HOOK: Mutex<Option<DeadlineMissHook>> = Mutex::new(None);
+
+/// 周期任务状态（保存在 TCB 中）
+///
+/// `period` 为 0 表示不是周期任务。
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PeriodicState {
+    /// 周期（节拍）
+    pub(crate) period: usize,
+    /// 相对截止时间（节拍）
+    pub(crate) deadline: usize,
+    /// 本次释放时刻
+    pub(crate) release: usize,
+    /// 错过截止时间的次数
+    pub(crate) missed: usize,
+    /// 超限的次数
+    pub(crate) overruns: usize,
+}
+
+/// 周期任务构建器
+///
+/// 由 `TaskBuilder::periodic` 创建。
+pub struct PeriodicTaskBuilder {
+    builder: TaskBuilder,
+    period: usize,
+    deadline: usize,
+}
+
+impl PeriodicTaskBuilder {
+    pub(crate) fn new(builder: TaskBuilder, period: usize, deadline: usize) -> Self {
+        Self { builder, period, deadline }
+    }
+
+    /// 创建并启动周期任务
+    ///
+    /// 第一次释放发生在创建时。任务体每次释放被调用一次，参数为任务 ID。
+    ///
+    /// # 返回值
+    /// - `Ok(Task)`: 成功创建的任务句柄
+    /// - `Err(RtosError::InvalidArgument)`: 周期为 0，或相对截止时间为 0 或大于周期
+    /// - 其他错误同 `TaskBuilder::spawn`
+    pub fn spawn<F>(self, mut body: F) -> Result<Task>
+    where
+        F: FnMut(usize) + Send + Sync + 'static,
+    {
+        let PeriodicTaskBuilder { builder, period, deadline } = self;
+        if period == 0 || deadline == 0 || deadline > period {
+            return Err(RtosError::InvalidArgument);
+        }
+
+        let mut task = builder.spawn(move |task_id| loop {
+            body(task_id);
+            Task::complete_job();
+        })?;
+        task.start_periodic(period, deadline)?;
+        Ok(task)
+    }
+}
+
+impl Task {
+    /// 开始周期释放（以当前时间作为第一次释放时刻）
+    fn start_periodic(&mut self, period: usize, deadline: usize) -> Result<()> {
+        let release = Systick::get_current_time();
+        *self.tcb()?.periodic.lock() = PeriodicState {
+            period,
+            deadline,
+            release,
+            missed: 0,
+            overruns: 0,
+        };
+        self.try_set_deadline(Some(release + deadline))
+    }
+
+    /// 结束当前周期任务本次释放的工作，阻塞到下一个释放时刻
+    ///
+    /// 由周期任务的包装循环在任务体返回后调用。
+    pub(crate) fn complete_job() {
+        let mut current = Scheduler::get_current_task();
+        let now = Systick::get_current_time();
+
+        let (missed_deadline, next_release, next_deadline) = {
+            let mut state = get_task_list()[current.id].periodic.lock();
+            let deadline = state.release + state.deadline;
+            let missed = now > deadline;
+            if missed {
+                state.missed += 1;
+            }
+
+            let mut next = state.release + state.period;
+            if now > next {
+                // 跳过已经错过的释放
+                state.overruns += 1;
+                next += (now - next).div_ceil(state.period) * state.period;
+            }
+            state.release = next;
+            (missed.then_some(deadline), next, next + state.deadline)
+        };
+
+        if let Some(deadline) = missed_deadline {
+            report_miss(current, deadline);
+        }
+        // 当前任务的句柄总是有效的
+        current.set_deadline(Some(next_deadline));
+
+        if next_release > now && Delay::delay(next_release - now).is_err() {
+            // 没有可用的定时器时退化为让出 CPU 直到释放时刻
+            while Systick::get_current_time() < next_release {
+                trigger_schedule();
+            }
+        }
+    }
+
+    /// 检查任务是否为周期任务 - O(1)
+    pub fn is_periodic(&self) -> bool {
+        get_task_list()[self.id].periodic.lock().period != 0
+    }
+
+    /// 获取周期任务的周期（节拍），非周期任务返回 `None`
+    pub fn get_period(&self) -> Option<usize> {
+        match get_task_list()[self.id].periodic.lock().period {
+            0 => None,
+            period => Some(period),
+        }
+    }
+
+    /// 获取周期任务错过截止时间的次数
+    pub fn deadline_miss_count(&self) -> usize {
+        get_task_list()[self.id].periodic.lock().missed
+    }
+
+    /// 获取周期任务超限（任务体在下一个释放时刻之后才返回）的次数
+    pub fn overrun_count(&self) -> usize {
+        get_task_list()[self.id].periodic.lock().overruns
+    }
+}
+
+/// 设置截止时间错过钩子
+///
+/// 周期任务的任务体在截止时间之后返回时调用。未设置钩子时只进行计数。
+pub fn set_deadline_miss_hook(hook: DeadlineMissHook) {
+    *DEADLINE_MISS_HOOK.lock() = Some(hook);
+}
+
+/// 清除截止时间错过钩子
+pub fn clear_deadline_miss_hook() {
+    *DEADLINE_MISS_HOOK.lock() = None;
+}
+
+/// 报告截止时间错过
+fn report_miss(task: Task, deadline: usize) {
+    let hook = *DEADLINE_MISS_HOOK.lock();
+    if let Some(hook) = hook {
+        hook(task, deadline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::TaskState;
+    use crate::utils::kernel_init;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use serial_test::serial;
+
+    static MISSED_DEADLINE: AtomicUsize = AtomicUsize::new(0);
+
+    fn record_miss(_task: Task, deadline: usize) {
+        MISSED_DEADLINE.store(deadline, Ordering::SeqCst);
+    }
+
+    #[test]
+    #[serial]
+    fn test_periodic_release_without_drift() {
+        kernel_init();
+        let mut task = Task::builder("control").periodic(10, 5).spawn(|_| {}).unwrap();
+        Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        assert!(task.is_periodic());
+        assert_eq!(task.get_period(), Some(10));
+        assert_eq!(task.get_deadline(), Some(5));
+
+        // 任务体运行 3 个节拍后返回，下一次释放仍在第 10 个节拍
+        Systick::add_current_time(3);
+        Task::complete_job();
+        assert!(matches!(task.get_state(), TaskState::Blocked(_)));
+        assert_eq!(task.get_deadline(), Some(15));
+
+        task.run();
+        Systick::add_current_time(10);
+        Task::complete_job();
+        assert_eq!(task.get_deadline(), Some(25));
+        assert_eq!(task.deadline_miss_count(), 0);
+        assert_eq!(task.overrun_count(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_periodic_miss_and_overrun() {
+        kernel_init();
+        let mut task = Task::builder("control").periodic(10, 5).spawn(|_| {}).unwrap();
+        Scheduler::start();
+        set_deadline_miss_hook(record_miss);
+
+        // 第 7 个节拍返回：错过截止时间 5，但没有超限
+        Systick::add_current_time(7);
+        Task::complete_job();
+        assert_eq!(MISSED_DEADLINE.load(Ordering::SeqCst), 5);
+        assert_eq!(task.deadline_miss_count(), 1);
+        assert_eq!(task.overrun_count(), 0);
+        assert_eq!(task.get_deadline(), Some(15));
+
+        // 第 33 个节拍返回：错过截止时间 15 并超限，跳过第 20、30 个节拍的释放
+        task.run();
+        Systick::add_current_time(26);
+        Task::complete_job();
+        assert_eq!(MISSED_DEADLINE.load(Ordering::SeqCst), 15);
+        assert_eq!(task.deadline_miss_count(), 2);
+        assert_eq!(task.overrun_count(), 1);
+        assert_eq!(task.get_deadline(), Some(45));
+
+        clear_deadline_miss_hook();
+    }
+
+    #[test]
+    #[serial]
+    fn test_periodic_invalid_arguments() {
+        kernel_init();
+        let zero_period = Task::builder("a").periodic(0, 0).spawn(|_| {});
+        assert_eq!(zero_period.err(), Some(RtosError::InvalidArgument));
+        let late_deadline = Task::builder("b").periodic(10, 11).spawn(|_| {});
+        assert_eq!(late_deadline.err(), Some(RtosError::InvalidArgument));
+
+        let plain = Task::new("plain", |_| {}).unwrap();
+        assert!(!plain.is_periodic());
+        assert_eq!(plain.get_period(), None);
+    }
+}
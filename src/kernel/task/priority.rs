@@ -1,6 +1,26 @@
 //! 任务优先级定义
 //!
-//! 提供任务优先级枚举，用于优先级调度。
+//! 优先级是 `0..config::PRIORITY_LEVELS` 范围内的数值，数值越大优先级越高。
+//! 级数可以在配置文件中调整（最多 256 级），以容纳更多的速率组。
+//!
+//! ## 命名优先级
+//!
+//! 常用的命名优先级是数值优先级的别名，按级数均匀分布：
+//!
+//! | 名称 | 数值 | 5 级 | 8 级 | 256 级 |
+//! |------|------|------|------|--------|
+//! | `Idle` | 0 | 0 | 0 | 0 |
+//! | `Low` | L / 4 | 1 | 2 | 64 |
+//! | `Normal` | L / 2 | 2 | 4 | 128 |
+//! | `High` | 3L / 4 | 3 | 6 | 192 |
+//! | `Critical` | L - 1 | 4 | 7 | 255 |
+
+use crate::config::PRIORITY_LEVELS;
+
+const _: () = assert!(
+    PRIORITY_LEVELS >= 5 && PRIORITY_LEVELS <= 256,
+    "PRIORITY_LEVELS must be in 5..=256"
+);
 
 /// 任务优先级
 ///
@@ -12,67 +32,82 @@
 ///
 /// let priority = Priority::High;
 /// assert!(priority > Priority::Normal);
+///
+/// // 数值优先级
+/// let rate_group = Priority::from_u8(3).unwrap();
+/// assert!(rate_group > Priority::Idle);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[repr(u8)]
-pub enum Priority {
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority(u8);
+
+#[allow(non_upper_case_globals)]
+impl Priority {
     /// 空闲优先级（最低）
-    /// 
+    ///
     /// 仅用于系统空闲任务
-    Idle = 0,
-    
+    pub const Idle: Priority = Priority(0);
+
     /// 低优先级
-    /// 
+    ///
     /// 用于后台任务、日志记录等
-    Low = 1,
-    
+    pub const Low: Priority = Priority((PRIORITY_LEVELS / 4) as u8);
+
     /// 普通优先级（默认）
-    /// 
+    ///
     /// 大多数任务使用此优先级
-    Normal = 2,
-    
+    pub const Normal: Priority = Priority((PRIORITY_LEVELS / 2) as u8);
+
     /// 高优先级
-    /// 
+    ///
     /// 用于需要快速响应的任务
-    High = 3,
-    
+    pub const High: Priority = Priority((PRIORITY_LEVELS * 3 / 4) as u8);
+
     /// 关键优先级（最高）
-    /// 
+    ///
     /// 用于关键系统任务，如看门狗喂狗
-    Critical = 4,
-}
-
-impl Default for Priority {
-    fn default() -> Self {
-        Priority::Normal
-    }
+    pub const Critical: Priority = Priority((PRIORITY_LEVELS - 1) as u8);
 }
 
 impl Priority {
+    /// 最低优先级
+    pub const MIN: Priority = Priority::Idle;
+
+    /// 最高优先级
+    pub const MAX: Priority = Priority::Critical;
+
+    /// 优先级级数
+    pub const LEVELS: usize = PRIORITY_LEVELS;
+
     /// 获取优先级数值
     ///
     /// # 返回值
     /// 优先级对应的 u8 数值
-    pub fn as_u8(self) -> u8 {
-        self as u8
+    pub const fn as_u8(self) -> u8 {
+        self.0
     }
 
     /// 从数值创建优先级
     ///
     /// # 参数
-    /// - `value`: 优先级数值 (0-4)
+    /// - `value`: 优先级数值 (0 到 `PRIORITY_LEVELS - 1`)
     ///
     /// # 返回值
     /// - `Some(Priority)`: 有效的优先级
     /// - `None`: 无效的数值
-    pub fn from_u8(value: u8) -> Option<Self> {
-        match value {
-            0 => Some(Priority::Idle),
-            1 => Some(Priority::Low),
-            2 => Some(Priority::Normal),
-            3 => Some(Priority::High),
-            4 => Some(Priority::Critical),
-            _ => None,
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        if (value as usize) < PRIORITY_LEVELS {
+            Some(Priority(value))
+        } else {
+            None
+        }
+    }
+
+    /// 从数值创建优先级，超出范围时取最高优先级
+    pub const fn saturating_from_u8(value: u8) -> Self {
+        if (value as usize) < PRIORITY_LEVELS {
+            Priority(value)
+        } else {
+            Priority::MAX
         }
     }
 
@@ -85,6 +120,33 @@ impl Priority {
     pub fn is_critical(self) -> bool {
         self == Priority::Critical
     }
+
+    /// 获取命名优先级的名称（数值优先级返回 `None`）
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Priority::Idle => Some("Idle"),
+            Priority::Low => Some("Low"),
+            Priority::Normal => Some("Normal"),
+            Priority::High => Some("High"),
+            Priority::Critical => Some("Critical"),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Priority::Normal
+    }
+}
+
+impl core::fmt::Debug for Priority {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "Priority({})", self.0),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -130,5 +192,14 @@ mod tests {
         assert!(Priority::Critical.is_critical());
         assert!(!Priority::High.is_critical());
     }
-}
 
+    #[test]
+    fn test_priority_numeric_levels() {
+        assert_eq!(Priority::LEVELS, PRIORITY_LEVELS);
+        assert_eq!(Priority::MAX.as_u8() as usize, PRIORITY_LEVELS - 1);
+        assert_eq!(Priority::saturating_from_u8(u8::MAX), Priority::MAX);
+        assert_eq!(Priority::from_u8(1).unwrap().name(), Some("Low"));
+        extern crate std;
+        assert_eq!(std::format!("{:?}", Priority::High), "High");
+    }
+}
@@ -0,0 +1,375 @@
+//! 任务栈管理
+//!
+//! 负责任务栈的分配与溢出检测。
+//!
+//! ## 栈布局
+//!
+//! ```text
+//!  stack_base                                              stack_base + stack_size
+//!  ┌──────────┬──────────────────────────────────┬───────────────────────────┐
+//!  │  canary  │ 0xA5 0xA5 0xA5 ... (未使用区域)  │ 已使用区域（向低地址增长）│
+//!  └──────────┴──────────────────────────────────┴───────────────────────────┘
+//!   GUARD_SIZE      ▲ 高水位线：从未被写过的字节数
+//! ```
+//!
+//! - 创建任务时整个栈被填充为 `STACK_FILL_PATTERN`，栈底写入金丝雀值
+//! - 每次 `Scheduler::task_switch` 检查被切出任务的金丝雀，被破坏即调用溢出钩子
+//! - `Task::stack_high_water_mark()` 统计从未被写过的字节数
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::kernel::task::{Task, set_stack_overflow_hook};
+//!
+//! fn on_overflow(task: Task, name: &'static str) {
+//!     panic!("task {} ({}) overflowed its stack", name, task.get_taskid());
+//! }
+//!
+//! set_stack_overflow_hook(on_overflow);
+//! ```
+
+use super::Task;
+#[cfg(feature = "alloc")]
+use crate::compat::{Box, Vec};
+use crate::config::{MIN_STACK_SIZE, STACK_POOL_SIZE};
+use crate::error::{Result, RtosError};
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+
+/// 栈填充字节，用于统计高水位线
+pub const STACK_FILL_PATTERN: u8 = 0xA5;
+
+/// 栈底金丝雀值
+pub const STACK_CANARY: u64 = 0xDEAD_BEEF_CAFE_F00D;
+
+/// 栈底保护区大小（字节）
+pub const GUARD_SIZE: usize = 8;
+
+/// 栈溢出钩子类型
+///
+/// 参数为溢出的任务句柄和任务名称
+pub type StackOverflowHook = fn(Task, &'static str);
+
+/// 栈溢出钩子
+static STACK_OVERFLOW_HOOK: Mutex<Option<StackOverflowHook>> = Mutex::new(None);
+
+/// 任务栈来源
+/// 
+/// - `Pool`: 使用静态栈池 `TASK_STACKS` 中空闲的栈，大小为 `config::STACK_SIZE`，
+///   栈池的容量为 `config::STACK_POOL_SIZE`，与任务槽位数量无关
+/// - `Heap`: 从堆上分配指定大小的栈，任务槽位被回收时释放（需要 `alloc`）
+/// - `Static`: 调用者提供的静态缓冲区，由调用者负责其生命周期
+pub(crate) enum StackSpec {
+    Pool,
+    Heap(usize),
+    Static(&'static mut [u8]),
+}
+
+/// 堆上分配的栈内存（使用 `u64` 保证 8 字节对齐）
+#[cfg(feature = "alloc")]
+pub(crate) type OwnedStack = Box<[u64]>;
+
+/// 关闭 `alloc` 时没有堆上的栈
+#[cfg(not(feature = "alloc"))]
+pub(crate) type OwnedStack = core::convert::Infallible;
+
+/// 栈池中每个栈是否被任务占用
+static POOL_IN_USE: [AtomicBool; STACK_POOL_SIZE] = [const { AtomicBool::new(false) }; STACK_POOL_SIZE];
+
+/// 任务占用的栈池中的栈，析构时归还栈池
+pub(crate) struct PoolStack(usize);
+
+impl PoolStack {
+    /// 占用一个空闲的栈，栈池已满时返回 `None`
+    pub(crate) fn acquire() -> Option<Self> {
+        POOL_IN_USE
+            .iter()
+            .position(|used| used.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_ok())
+            .map(PoolStack)
+    }
+
+    /// 栈在 `TASK_STACKS` 中的下标
+    pub(crate) fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Drop for PoolStack {
+    fn drop(&mut self) {
+        POOL_IN_USE[self.0].store(false, Ordering::Release);
+    }
+}
+
+/// 已分配的任务栈
+/// 
+/// `owned` 保存堆上分配的栈内存，`pool` 保存占用的池化栈，
+/// 随 TCB 一起保存，直到槽位被回收。
+pub(crate) struct StackAlloc {
+    pub(crate) base: usize,
+    pub(crate) size: usize,
+    pub(crate) owned: Option<OwnedStack>,
+    pub(crate) pool: Option<PoolStack>,
+}
+
+impl StackSpec {
+    /// 根据栈来源准备栈内存（不需要持有分配锁）
+    /// 
+    /// 池化栈在分配槽位时占用（回收的槽位会先归还它的池化栈），这里返回 `None`。
+    pub(crate) fn prepare(self) -> Result<Option<StackAlloc>> {
+        match self {
+            StackSpec::Pool => Ok(None),
+            StackSpec::Heap(size) => {
+                let size = (size + 7) & !7;
+                if size < MIN_STACK_SIZE {
+                    return Err(RtosError::InvalidArgument);
+                }
+                alloc_stack(size).map(Some)
+            }
+            StackSpec::Static(buffer) => {
+                let start = buffer.as_mut_ptr() as usize;
+                let base = (start + 7) & !7;
+                let top = (start + buffer.len()) & !7;
+                if top <= base || top - base < MIN_STACK_SIZE {
+                    return Err(RtosError::InvalidArgument);
+                }
+                Ok(Some(StackAlloc {
+                    base,
+                    size: top - base,
+                    owned: None,
+                    pool: None,
+                }))
+            }
+        }
+    }
+}
+
+/// 从堆上分配 `size` 字节（8 的倍数）的栈
+#[cfg(feature = "alloc")]
+fn alloc_stack(size: usize) -> Result<StackAlloc> {
+    let words = size / 8;
+    let mut memory: Vec<u64> = Vec::new();
+    memory
+        .try_reserve_exact(words)
+        .map_err(|_| RtosError::OutOfMemory)?;
+    memory.resize(words, 0);
+    let memory = memory.into_boxed_slice();
+    Ok(StackAlloc {
+        base: memory.as_ptr() as usize,
+        size,
+        owned: Some(memory),
+        pool: None,
+    })
+}
+
+/// 关闭 `alloc` 时没有堆，只能使用栈池或调用者提供的静态缓冲区
+#[cfg(not(feature = "alloc"))]
+fn alloc_stack(_size: usize) -> Result<StackAlloc> {
+    Err(RtosError::OutOfMemory)
+}
+
+/// 填充栈区域并写入金丝雀值
+///
+/// # Safety
+/// `base` 必须 8 字节对齐，`[base, base + size)` 必须是调用者独占的可写内存。
+pub(crate) unsafe fn prepare_guard(base: usize, size: usize) {
+    unsafe {
+        core::ptr::write_bytes(base as *mut u8, STACK_FILL_PATTERN, size);
+        core::ptr::write_volatile(base as *mut u64, STACK_CANARY);
+    }
+}
+
+/// 检查栈底金丝雀是否完好
+///
+/// # Safety
+/// `base` 必须是通过 `prepare_guard` 初始化过的栈底地址。
+#[inline]
+pub(crate) unsafe fn canary_intact(base: usize) -> bool {
+    unsafe { core::ptr::read_volatile(base as *const u64) == STACK_CANARY }
+}
+
+/// 统计栈中从未被写过的字节数
+///
+/// # Safety
+/// `[base, base + size)` 必须是通过 `prepare_guard` 初始化过的栈区域。
+pub(crate) unsafe fn untouched_bytes(base: usize, size: usize) -> usize {
+    let mut count = 0;
+    for offset in GUARD_SIZE..size {
+        // SAFETY: 调用者保证地址在栈区域内
+        if unsafe { core::ptr::read_volatile((base + offset) as *const u8) } != STACK_FILL_PATTERN {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+/// 设置栈溢出钩子
+///
+/// 当 `Scheduler::task_switch` 检测到任务的栈底金丝雀被破坏时调用。
+/// 未设置钩子时默认 panic。
+///
+/// 钩子返回后金丝雀会被重新写入，同一次溢出只报告一次。
+pub fn set_stack_overflow_hook(hook: StackOverflowHook) {
+    *STACK_OVERFLOW_HOOK.lock() = Some(hook);
+}
+
+/// 清除栈溢出钩子，恢复默认行为（panic）
+pub fn clear_stack_overflow_hook() {
+    *STACK_OVERFLOW_HOOK.lock() = None;
+}
+
+/// 报告栈溢出
+pub(crate) fn report_overflow(task: Task) {
+    let hook = *STACK_OVERFLOW_HOOK.lock();
+    match hook {
+        Some(hook) => hook(task, task.get_name()),
+        None => panic!("stack overflow in task '{}' (id {})", task.get_name(), task.get_taskid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::STACK_SIZE;
+    use crate::kernel::scheduler::Scheduler;
+    use crate::utils::kernel_init;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use serial_test::serial;
+
+    static OVERFLOWED_TASK: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+    fn record_overflow(task: Task, name: &'static str) {
+        assert_eq!(name, task.get_name());
+        OVERFLOWED_TASK.store(task.get_taskid(), Ordering::SeqCst);
+    }
+
+    #[test]
+    #[serial]
+    fn test_stack_high_water_mark_fresh_task() {
+        kernel_init();
+        let task = Task::new("fresh", |_| {}).unwrap();
+        assert_eq!(task.stack_high_water_mark(), STACK_SIZE - GUARD_SIZE);
+        assert!(!task.is_stack_overflowed());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_stack_high_water_mark_after_use() {
+        kernel_init();
+        let task = Task::builder("used").stack_size(1024).spawn(|_| {}).unwrap();
+        let top = task.get_stack_base() + task.get_stack_size();
+
+        // 模拟任务使用了栈顶的 100 字节
+        unsafe { core::ptr::write_bytes((top - 100) as *mut u8, 0, 100) };
+        assert_eq!(task.stack_high_water_mark(), 1024 - GUARD_SIZE - 100);
+    }
+
+    #[test]
+    #[serial]
+    fn test_stack_overflow_hook_called() {
+        kernel_init();
+        let task = Task::new("victim", |_| {}).unwrap();
+        Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        OVERFLOWED_TASK.store(usize::MAX, Ordering::SeqCst);
+        set_stack_overflow_hook(record_overflow);
+
+        // 破坏栈底金丝雀
+        unsafe { core::ptr::write_volatile(task.get_stack_base() as *mut u64, 0) };
+        assert!(task.is_stack_overflowed());
+
+        Scheduler::task_switch();
+        assert_eq!(OVERFLOWED_TASK.load(Ordering::SeqCst), task.get_taskid());
+        // 钩子返回后金丝雀被重新写入
+        assert!(!task.is_stack_overflowed());
+
+        clear_stack_overflow_hook();
+    }
+
+    #[test]
+    #[serial]
+    #[should_panic(expected = "stack overflow in task 'victim'")]
+    fn test_stack_overflow_default_panics() {
+        kernel_init();
+        clear_stack_overflow_hook();
+        let task = Task::new("victim", |_| {}).unwrap();
+        Scheduler::start();
+
+        unsafe { core::ptr::write_volatile(task.get_stack_base() as *mut u64, 0) };
+        Scheduler::task_switch();
+    }
+
+    #[test]
+    #[serial]
+    fn test_stack_spec_rejects_small_buffer() {
+        let buffer: &'static mut [u8] = Box::leak(crate::compat::vec![0u8; 64].into_boxed_slice());
+        assert_eq!(StackSpec::Static(buffer).prepare().err(), Some(RtosError::InvalidArgument));
+    }
+
+    #[test]
+    #[serial]
+    fn test_stack_pool_only_backs_default_stacks() {
+        use crate::config::STACK_POOL_SIZE;
+        use crate::compat::Vec;
+
+        static mut DSP_STACK: [u8; 1024] = [0; 1024];
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 栈池用完之后，使用默认栈的任务创建失败，使用其他栈来源的任务不受影响
+        let worker = Task::new("worker", |_| {}).unwrap();
+        let held: Vec<PoolStack> = core::iter::from_fn(PoolStack::acquire).collect();
+        assert_eq!(held.len(), STACK_POOL_SIZE - 2);
+        assert_eq!(Task::new("pooled", |_| {}).err(), Some(RtosError::OutOfMemory));
+        let dsp = Task::builder("dsp")
+            .stack(unsafe { &mut *core::ptr::addr_of_mut!(DSP_STACK) })
+            .spawn(|_| {})
+            .unwrap();
+        assert_eq!(dsp.get_stack_size(), 1024);
+
+        // 回收槽位时归还池化栈，槽位和池化栈不必一一对应
+        let base = worker.get_stack_base();
+        worker.terminate(0);
+        worker.join().unwrap();
+        let reused = Task::new("reused", |_| {}).unwrap();
+        assert_eq!(reused.get_stack_base(), base);
+        assert_eq!(reused.get_stack_size(), STACK_SIZE);
+
+        drop(held);
+        assert!(Task::new("pooled", |_| {}).is_ok());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_stack_pool_reuses_terminated_stack_before_reset() {
+        use crate::compat::Vec;
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 使用堆栈的任务先结束：它的槽位排在前面，但没有池化栈
+        let heap = Task::builder("heap").stack_size(1024).spawn(|_| {}).unwrap();
+        let pooled = Task::new("pooled", |_| {}).unwrap();
+        heap.terminate(3);
+        let held: Vec<PoolStack> = core::iter::from_fn(PoolStack::acquire).collect();
+
+        // 没有可用的池化栈：创建失败，已结束任务的退出码仍然可以取回
+        assert_eq!(Task::new("next", |_| {}).err(), Some(RtosError::OutOfMemory));
+        assert_eq!(heap.try_join(), Ok(Some(3)));
+
+        // 使用已结束任务留下的池化栈，没有池化栈的槽位保持不变
+        let base = pooled.get_stack_base();
+        pooled.terminate(0);
+        let next = Task::new("next", |_| {}).unwrap();
+        assert_eq!(next.get_taskid(), pooled.get_taskid());
+        assert_eq!(next.get_stack_base(), base);
+        assert_eq!(heap.try_join(), Ok(Some(3)));
+        drop(held);
+    }
+}
@@ -63,6 +63,8 @@
 //!                         │ wake()
 //!                         ▼
 //!                      (Ready)
+//!
+//!   Ready / Running / Blocked ── suspend() ──► Suspended ── resume() ──► Ready
 //! ```
 
 use core::marker::PhantomData;
@@ -95,11 +97,16 @@ pub struct Running;
 #[derive(Debug, Clone, Copy)]
 pub struct Blocked;
 
+/// 挂起状态 - 任务不参与调度，也不响应事件，直到被恢复
+#[derive(Debug, Clone, Copy)]
+pub struct Suspended;
+
 // 实现状态标记 trait
 impl TaskStateMarker for Created {}
 impl TaskStateMarker for Ready {}
 impl TaskStateMarker for Running {}
 impl TaskStateMarker for Blocked {}
+impl TaskStateMarker for Suspended {}
 
 // 私有模块用于封闭 trait
 mod private {
@@ -108,6 +115,7 @@ mod private {
     impl Sealed for super::Ready {}
     impl Sealed for super::Running {}
     impl Sealed for super::Blocked {}
+    impl Sealed for super::Suspended {}
 }
 
 // ============================================================================
@@ -236,6 +244,7 @@ impl TypedTask<Created> {
     /// # }
     /// ```
     pub fn set_priority(&mut self, priority: Priority) {
+        // 句柄已失效时 `Task::set_priority` 不会作用到其他任务，这里忽略错误
         self.inner.set_priority(priority);
     }
 
@@ -336,6 +345,23 @@ impl TypedTask<Ready> {
         }
     }
 
+    /// 挂起任务（Ready -> Suspended）
+    ///
+    /// 任务被移出就绪队列，不再被调度或被事件唤醒，直到调用 `resume()`。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `TypedTask<Suspended>`
+    pub fn suspend(mut self) -> TypedTask<Suspended> {
+        // 类型状态保证任务处于可挂起的状态
+        let _ = self.inner.suspend();
+        TypedTask {
+            inner: self.inner,
+            blocked_event: None,
+            _state: PhantomData,
+        }
+    }
+
     /// 获取任务 ID
     pub fn id(&self) -> usize {
         self.inner.get_taskid()
@@ -353,6 +379,7 @@ impl TypedTask<Ready> {
 
     /// 设置任务优先级
     pub fn set_priority(&mut self, priority: Priority) {
+        // 句柄已失效时 `Task::set_priority` 不会作用到其他任务，这里忽略错误
         self.inner.set_priority(priority);
     }
 }
@@ -399,6 +426,23 @@ impl TypedTask<Running> {
         }
     }
 
+    /// 挂起任务（Running -> Suspended）
+    ///
+    /// 任务被移出就绪队列，不再被调度或被事件唤醒，直到调用 `resume()`。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `TypedTask<Suspended>`
+    pub fn suspend(mut self) -> TypedTask<Suspended> {
+        // 类型状态保证任务处于可挂起的状态
+        let _ = self.inner.suspend();
+        TypedTask {
+            inner: self.inner,
+            blocked_event: None,
+            _state: PhantomData,
+        }
+    }
+
     /// 获取任务 ID
     pub fn id(&self) -> usize {
         self.inner.get_taskid()
@@ -437,6 +481,23 @@ impl TypedTask<Blocked> {
         }
     }
 
+    /// 挂起任务（Blocked -> Suspended）
+    ///
+    /// 任务被移出就绪队列，放弃当前等待，不再被调度或被事件唤醒，直到调用 `resume()`。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `TypedTask<Suspended>`
+    pub fn suspend(mut self) -> TypedTask<Suspended> {
+        // 类型状态保证任务处于可挂起的状态
+        let _ = self.inner.suspend();
+        TypedTask {
+            inner: self.inner,
+            blocked_event: None,
+            _state: PhantomData,
+        }
+    }
+
     /// 获取阻塞事件
     pub fn blocked_event(&self) -> Option<Event> {
         self.blocked_event
@@ -453,6 +514,44 @@ impl TypedTask<Blocked> {
     }
 }
 
+// ============================================================================
+// Suspended 状态实现
+// ============================================================================
+
+impl TypedTask<Suspended> {
+    /// 恢复任务（Suspended -> Ready）
+    ///
+    /// 任务重新加入就绪队列，等待调度。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `TypedTask<Ready>`
+    pub fn resume(mut self) -> TypedTask<Ready> {
+        // 类型状态保证任务处于挂起状态
+        let _ = self.inner.resume();
+        TypedTask {
+            inner: self.inner,
+            blocked_event: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// 获取任务 ID
+    pub fn id(&self) -> usize {
+        self.inner.get_taskid()
+    }
+
+    /// 获取任务名称
+    pub fn name(&self) -> &'static str {
+        self.inner.get_name()
+    }
+
+    /// 获取任务优先级
+    pub fn priority(&self) -> Priority {
+        self.inner.get_priority()
+    }
+}
+
 // ============================================================================
 // 通用实现（所有状态共享）
 // ============================================================================
@@ -601,6 +700,9 @@ impl TypedTaskBuilder {
 ///     TypedTaskAny::Created(_) => {
 ///         // 处理创建状态的任务
 ///     }
+///     TypedTaskAny::Suspended(_) => {
+///         // 处理挂起状态的任务
+///     }
 /// }
 /// # Ok(())
 /// # }
@@ -615,6 +717,8 @@ pub enum TypedTaskAny {
     Running(TypedTask<Running>),
     /// 阻塞状态
     Blocked(TypedTask<Blocked>),
+    /// 挂起状态
+    Suspended(TypedTask<Suspended>),
 }
 
 impl TypedTaskAny {
@@ -646,6 +750,14 @@ impl TypedTaskAny {
         }
     }
 
+    /// 尝试转换为 Suspended 状态
+    pub fn into_suspended(self) -> Option<TypedTask<Suspended>> {
+        match self {
+            TypedTaskAny::Suspended(task) => Some(task),
+            _ => None,
+        }
+    }
+
     /// 尝试转换为 Created 状态
     pub fn into_created(self) -> Option<TypedTask<Created>> {
         match self {
@@ -661,6 +773,7 @@ impl TypedTaskAny {
             TypedTaskAny::Ready(task) => task.id(),
             TypedTaskAny::Running(task) => task.id(),
             TypedTaskAny::Blocked(task) => task.id(),
+            TypedTaskAny::Suspended(task) => task.id(),
         }
     }
 
@@ -671,6 +784,7 @@ impl TypedTaskAny {
             TypedTaskAny::Ready(task) => task.name(),
             TypedTaskAny::Running(task) => task.name(),
             TypedTaskAny::Blocked(task) => task.name(),
+            TypedTaskAny::Suspended(task) => task.name(),
         }
     }
 }
@@ -713,6 +827,7 @@ mod tests {
     use super::*;
     use crate::utils::kernel_init;
     use serial_test::serial;
+    use crate::kernel::task::TaskState;
 
     #[test]
     #[serial]
@@ -923,5 +1038,37 @@ mod tests {
         let ready_task = task.start();
         assert_eq!(ready_task.priority(), Priority::Critical);
     }
-}
 
+    #[test]
+    #[serial]
+    fn test_typed_task_suspend_resume() {
+        kernel_init();
+
+        let ready = TypedTask::<Created>::new("typed_suspend", |_| {}).unwrap().start();
+        let suspended = ready.suspend();
+        assert_eq!(suspended.inner().get_state(), TaskState::Suspended);
+
+        let ready = suspended.resume();
+        assert_eq!(ready.inner().get_state(), TaskState::Ready);
+
+        let blocked = ready.block(Event::Signal(7));
+        let suspended = blocked.suspend();
+        assert_eq!(suspended.inner().get_state(), TaskState::Suspended);
+        assert_eq!(suspended.name(), "typed_suspend");
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_into_typed_suspended() {
+        kernel_init();
+
+        let mut task = Task::new("typed_suspended", |_| {}).unwrap();
+        task.suspend().unwrap();
+
+        let typed = task.into_typed().unwrap();
+        assert_eq!(typed.id(), task.get_taskid());
+        assert_eq!(typed.name(), "typed_suspended");
+        let suspended = typed.into_suspended().expect("Expected Suspended state");
+        assert_eq!(suspended.resume().inner().get_state(), TaskState::Ready);
+    }
+}
@@ -1,20 +1,37 @@
 use crate::hal::trigger_schedule;
 use crate::config::MAX_TIMERS;
 use crate::sync::event::Event;
+use crate::kernel::deferred;
 use crate::kernel::scheduler::Scheduler;
+use crate::kernel::task::Task;
 use crate::kernel::time::systick::Systick;
+use crate::sync::wait_queue::WaitQueue;
 use crate::error::{Result, RtosError};
+use core::mem::ManuallyDrop;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 static mut TIMER_LIST: [Option<TimerInner>; MAX_TIMERS] = [None; MAX_TIMERS];
 
+/// 每个定时器槽位的代数，槽位被释放时加一，使旧的 `Timer` 句柄失效
+static TIMER_GENERATIONS: [AtomicU32; MAX_TIMERS] = [const { AtomicU32::new(0) }; MAX_TIMERS];
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct TimerInner {
     running: bool,
     id: usize,
     timeout: usize,
+    /// 阻塞等待该定时器到期的任务
+    waiter: Option<Task>,
 }
 
-pub struct Timer(usize);
+/// 定时器句柄
+///
+/// 由槽位下标和槽位的代数组成。定时器被删除后句柄失效，
+/// 即使槽位被新的定时器重用，旧句柄的操作也返回 `RtosError::InvalidHandle`。
+pub struct Timer {
+    id: usize,
+    generation: u32,
+}
 
 impl Timer {
     /// 新建一个定时器的句柄
@@ -33,6 +50,7 @@ impl Timer {
                     running: false,
                     id: id,
                     timeout: timeout + Systick::get_current_time(),
+                    waiter: None,
                 });
                 found = true;
                 return true;
@@ -44,32 +62,50 @@ impl Timer {
         if !found {
             return Err(RtosError::TimerSlotsFull);
         }
-        Ok(Timer(id))
+        Ok(Timer::from_id(id))
+    }
+
+    /// 获取槽位 `id` 中当前定时器的句柄
+    fn from_id(id: usize) -> Timer {
+        Timer {
+            id,
+            generation: TIMER_GENERATIONS[id].load(Ordering::Acquire),
+        }
     }
 
     pub fn init() {
         unsafe {
             for i in 0..MAX_TIMERS {
                 TIMER_LIST[i] = None;
+                TIMER_GENERATIONS[i].fetch_add(1, Ordering::AcqRel);
             }
         }
     }
 
     pub fn get_id(&self) -> usize {
-        self.0
+        self.id
+    }
+
+    /// 句柄是否仍然指向创建时的定时器
+    pub fn is_valid(&self) -> bool {
+        TIMER_GENERATIONS[self.id].load(Ordering::Acquire) == self.generation
     }
     
-    /// 通过 id 来获取定时器结构体并执行操作
+    /// 获取句柄对应的定时器结构体并执行操作
     /// 
     /// # 返回值
     /// - `Ok(())` - 成功执行操作
+    /// - `Err(RtosError::InvalidHandle)` - 定时器已被删除，句柄失效
     /// - `Err(RtosError::TimerNotFound)` - 定时器不存在
-    fn get_timer_by_id<F>(id: usize, mut f: F) -> Result<()>
+    fn get_timer<F>(&self, mut f: F) -> Result<()>
     where
         F: FnMut(&mut TimerInner),
     {
+        if !self.is_valid() {
+            return Err(RtosError::InvalidHandle);
+        }
         unsafe {
-            if let Some(ref mut timer) = TIMER_LIST[id] {
+            if let Some(ref mut timer) = TIMER_LIST[self.id] {
                 f(timer);
                 Ok(())
             } else {
@@ -77,23 +113,58 @@ impl Timer {
             }
         }
     }
+
     /// 删除定时器
+    ///
+    /// 释放槽位并使句柄失效，句柄已经失效时不做任何操作。
     pub fn delete(&mut self) {
+        if !self.is_valid() {
+            return;
+        }
         unsafe {
-            TIMER_LIST[self.0] = None;
+            TIMER_LIST[self.id] = None;
         }
+        TIMER_GENERATIONS[self.id].fetch_add(1, Ordering::AcqRel);
     }
 
     /// 启动定时器
+    ///
+    /// 到期时唤醒所有阻塞在 `Event::Timer(id)` 上的任务。
     pub fn start(&mut self) -> Result<()> {
-        Self::get_timer_by_id(self.0, |timer| {
+        self.get_timer(|timer| {
+            timer.running = true;
+        })
+    }
+
+    /// 启动定时器，到期时唤醒阻塞的 `task`（内部使用）
+    ///
+    /// 到期时直接唤醒登记的任务，不需要遍历所有任务控制块。
+    /// 任务的阻塞原因可以是它所等待的对象，超时由等待方在被唤醒后根据截止时间判断。
+    pub(crate) fn start_for(&mut self, task: Task) -> Result<()> {
+        self.get_timer(|timer| {
+            timer.waiter = Some(task);
             timer.running = true;
         })
     }
 
+    /// 停止通过 `start_for` 为 `task` 启动的定时器（内部使用）
+    ///
+    /// 任务放弃等待时调用，定时器到期时不再唤醒它；槽位仍由定时器的持有者删除。
+    pub(crate) fn cancel_for(task: Task) {
+        Timer::for_each_used(|timer, _| {
+            let _ = timer.get_timer(|timer| {
+                if timer.waiter == Some(task) {
+                    timer.waiter = None;
+                    timer.running = false;
+                }
+            });
+            false
+        });
+    }
+
     /// 停止定时器
     pub fn stop(&mut self) -> Result<()> {
-        Self::get_timer_by_id(self.0, |timer| {
+        self.get_timer(|timer| {
             timer.running = false;
         })
     }
@@ -101,7 +172,7 @@ impl Timer {
     /// 检查定时器是否正在运行
     pub fn is_running(&self) -> bool {
         let mut running = false;
-        let _ = Self::get_timer_by_id(self.0, |timer| {
+        let _ = self.get_timer(|timer| {
             running = timer.running;
         });
         running
@@ -110,20 +181,43 @@ impl Timer {
     /// 检查定时器是否超时
     pub fn is_timeout(&self) -> bool {
         let mut timeout = 0;
-        let _ = Self::get_timer_by_id(self.0, |timer| {
+        let _ = self.get_timer(|timer| {
             timeout = timer.timeout;
         });
         Systick::get_current_time() >= timeout
     }
 
-    /// 遍历检查是否有满足条件的定时器，如果有的话就发送信号，并删除定时器
+    /// 获取定时器的超时时刻（SysTick 节拍），定时器不存在时返回 `None`
+    pub(crate) fn expires_at(&self) -> Option<usize> {
+        let mut expires_at = None;
+        let _ = self.get_timer(|timer| {
+            expires_at = Some(timer.timeout);
+        });
+        expires_at
+    }
+
+    /// 检查所有正在运行的定时器，唤醒等待已超时定时器的任务
+    ///
+    /// 超时的定时器被停止，不会重复触发；槽位由定时器的持有者删除。
+    /// 通过 `start_for` 启动的定时器只唤醒登记的任务，不遍历任务控制块；
+    /// 通过 `start` 启动的定时器唤醒所有阻塞在 `Event::Timer` 上的任务。
+    /// 检查之前先执行中断推迟的调用（见 `kernel::deferred`），这些调用不会等待任务持有的锁。
     pub fn timer_check_and_send_event() {
-        Timer::for_each_used(|timer, id| {
-            if timer.is_timeout() {
-                Event::wake_task(Event::Timer(id));
-                return true;
+        // 先处理中断推迟到节拍上下文的唤醒
+        deferred::run_deferred();
+        Timer::for_each_used(|timer, _| {
+            if timer.is_running() && timer.is_timeout() {
+                let _ = timer.stop();
+                let mut waiter = None;
+                let _ = timer.get_timer(|timer| waiter = timer.waiter.take());
+                match waiter {
+                    Some(task) => {
+                        WaitQueue::wake_task(task);
+                    }
+                    None => Event::wake_task(Event::Timer(timer.get_id())),
+                }
             }
-            return false;
+            false
         });
     }
 
@@ -141,6 +235,9 @@ impl Timer {
         }
     }
 
+    /// 遍历所有已分配的定时器
+    ///
+    /// 传给 `f` 的句柄不拥有定时器，遍历结束后不会删除定时器。
     pub fn for_each_used<F>(mut f: F)
     where
         F: FnMut(&mut Timer, usize) -> bool,
@@ -148,7 +245,8 @@ impl Timer {
         for i in 0..MAX_TIMERS {
             unsafe {
                 if TIMER_LIST[i].is_some() {
-                    let ret: bool = f(&mut Timer(i), i);
+                    let mut timer = ManuallyDrop::new(Timer::from_id(i));
+                    let ret: bool = f(&mut timer, i);
                     if ret {
                         break;
                     }
@@ -163,9 +261,7 @@ impl Drop for Timer {
     ///
     /// 这允许槽位被后续的 Timer::new() 重用
     fn drop(&mut self) {
-        unsafe {
-            TIMER_LIST[self.0] = None;
-        }
+        self.delete();
     }
 }
 
@@ -179,8 +275,9 @@ impl Delay {
     /// - `Err(RtosError::TimerSlotsFull)` - 没有可用的定时器槽位
     pub fn delay(timeout: usize) -> Result<()> {
         let mut timer = Timer::new(timeout)?;
-        timer.start()?;
-        Scheduler::get_current_task().block(Event::Timer(timer.0));
+        let mut current = Scheduler::get_current_task();
+        timer.start_for(current)?;
+        current.block(Event::Timer(timer.id));
         trigger_schedule();
         timer.delete();
         Ok(())
@@ -211,12 +308,12 @@ mod tests {
         Timer::for_each_used(|timer, id| {
             unsafe {
                 assert_eq!(id, 0);
-                assert_eq!(timer.0, 0);
-                assert_eq!(TIMER_LIST[timer.0].unwrap().running, true);
+                assert_eq!(timer.id, 0);
+                assert_eq!(TIMER_LIST[timer.id].unwrap().running, true);
             }
             if timer.is_timeout() {
                 unsafe {
-                    assert_eq!(TIMER_LIST[timer.0].unwrap().running, true);
+                    assert_eq!(TIMER_LIST[timer.id].unwrap().running, true);
                 }
                 assert_eq!(timer.get_id(), 0);
                 timer.delete();
@@ -228,11 +325,11 @@ mod tests {
         Systick::add_current_time(1000);
         Timer::for_each_used(|timer, _| {
             unsafe {
-                assert_eq!(TIMER_LIST[timer.0].unwrap().running, true);
+                assert_eq!(TIMER_LIST[timer.id].unwrap().running, true);
             }
             if timer.is_timeout() {
                 unsafe {
-                    assert_eq!(TIMER_LIST[timer.0].unwrap().running, true);
+                    assert_eq!(TIMER_LIST[timer.id].unwrap().running, true);
                 }
                 assert_eq!(timer.get_id(), 1);
                 timer.delete();
@@ -290,4 +387,102 @@ mod tests {
         Systick::add_current_time(500);
         assert_eq!(timer.is_timeout(), true);
     }
+
+    #[test]
+    #[serial]
+    fn test_stale_timer_handle() {
+        kernel_init();
+        let mut old = Timer::new(1000).unwrap();
+        old.delete();
+        let mut timer = Timer::new(2000).unwrap();
+        assert_eq!(timer.get_id(), old.get_id());
+
+        // 旧句柄不会操作重用槽位的新定时器，drop 时也不会释放它
+        assert!(!old.is_valid());
+        assert_eq!(old.start(), Err(RtosError::InvalidHandle));
+        drop(old);
+        assert!(timer.start().is_ok());
+        assert!(timer.is_running());
+    }
+
+    #[test]
+    #[serial]
+    fn test_timer_timeout_wakes_blocked_task() {
+        use crate::hal::test::set_schedule_hook;
+        use crate::kernel::scheduler::Scheduler;
+        use crate::sync::wait_queue::WaitQueue;
+
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 未到期的定时器在检查之后仍然有效，到期时唤醒阻塞的任务
+        let mut timer = Timer::new(10).unwrap();
+        timer.start_for(worker).unwrap();
+        worker.block(Event::Timer(timer.get_id()));
+        Systick::add_current_time(5);
+        Timer::timer_check_and_send_event();
+        assert!(timer.is_valid());
+        assert!(timer.is_running());
+        assert_eq!(worker.get_state(), TaskState::Blocked(Event::Timer(timer.get_id())));
+        Systick::add_current_time(5);
+        Timer::timer_check_and_send_event();
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert!(!timer.is_running());
+
+        // 当前任务的超时等待：阻塞期间 SysTick 到期，任务被唤醒并得到超时结果
+        set_schedule_hook(Some(Box::new(|| {
+            Systick::add_current_time(10);
+            Timer::timer_check_and_send_event();
+            assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Ready);
+        })));
+        let queue = WaitQueue::new();
+        assert_eq!(queue.wait(Some(10)), Err(RtosError::Timeout));
+        set_schedule_hook(None);
+        assert!(queue.is_empty());
+        assert_eq!(main.get_state(), TaskState::Running);
+    }
+
+    #[test]
+    #[serial]
+    fn test_timer_wakes_only_registered_waiter() {
+        use crate::kernel::scheduler::Scheduler;
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        let mut other = Task::new("other", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 等待者的阻塞原因是它等待的对象，定时器到期时仍然直接唤醒它
+        let mut timer = Timer::new(10).unwrap();
+        timer.start_for(waiter).unwrap();
+        waiter.block(Event::WaitQueue(1));
+        other.block(Event::Timer(timer.get_id()));
+        Systick::add_current_time(10);
+        Timer::timer_check_and_send_event();
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(other.get_state(), TaskState::Blocked(Event::Timer(timer.get_id())));
+    }
+
+    #[test]
+    #[serial]
+    fn test_timer_start_wakes_event_waiters() {
+        use crate::kernel::scheduler::Scheduler;
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 没有登记等待者的定时器到期时唤醒阻塞在 Event::Timer 上的任务
+        let mut timer = Timer::new(10).unwrap();
+        timer.start().unwrap();
+        worker.block(Event::Timer(timer.get_id()));
+        Systick::add_current_time(10);
+        Timer::timer_check_and_send_event();
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert!(!timer.is_running());
+    }
 }
@@ -104,6 +104,7 @@
 
 #[cfg(test)]
 extern crate std;
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 // ============================================================================
@@ -1,5 +1,8 @@
 // ============================================================================
 // 嵌入式环境使用 embedded-alloc
+// 
+// 嵌入式目标 feature 和 `heap` 引入 embedded-alloc，
+// 本模块只在启用 `alloc` 时编译，no-alloc 构建不注册全局分配器。
 // ============================================================================
 
 #[cfg(feature = "embedded-alloc")]
@@ -0,0 +1,122 @@
+//! # InlineBox - 固定大小的内联存储
+//!
+//! 关闭 `alloc` feature 时代替 `Box<dyn ...>` 保存类型擦除的值（任务闭包、Ipc 消息）。
+//! 值直接存放在 `WORDS` 个字的缓冲区中，大小或对齐超出缓冲区时 `new` 把值原样返回。
+//!
+//! 取出值时调用者需要知道原来的类型，通常与值一起保存一个单态化的函数指针或 `TypeId`。
+
+use core::mem::{align_of, size_of, MaybeUninit};
+
+/// 保存一个任意类型值的内联缓冲区
+pub struct InlineBox<const WORDS: usize> {
+    data: [MaybeUninit<usize>; WORDS],
+    /// 析构函数，值被取出后为 `None`
+    drop: Option<unsafe fn(*mut u8)>,
+}
+
+// 只能通过 `take` 按值取出，`new` 要求值是 Send。
+// 不实现 Sync：放入的值不一定是 Sync，需要共享时由外层的锁保证互斥。
+unsafe impl<const WORDS: usize> Send for InlineBox<WORDS> {}
+
+impl<const WORDS: usize> InlineBox<WORDS> {
+    /// 类型 `T` 的值能否放入缓冲区
+    pub const fn fits<T>() -> bool {
+        size_of::<T>() <= WORDS * size_of::<usize>() && align_of::<T>() <= align_of::<usize>()
+    }
+
+    /// 把值放入缓冲区
+    ///
+    /// # 返回值
+    /// - `Ok(InlineBox)`: 放入成功
+    /// - `Err(value)`: 值的大小或对齐超出缓冲区，返回传入的值
+    pub fn new<T: Send + 'static>(value: T) -> Result<Self, T> {
+        if !Self::fits::<T>() {
+            return Err(value);
+        }
+        let mut data = [MaybeUninit::uninit(); WORDS];
+        // SAFETY: 上面检查过大小和对齐
+        unsafe { data.as_mut_ptr().cast::<T>().write(value) };
+        Ok(Self {
+            data,
+            drop: Some(drop_value::<T>),
+        })
+    }
+
+    /// 取出值
+    ///
+    /// # Safety
+    /// `T` 必须是调用 `new` 时放入的类型。
+    pub unsafe fn take<T>(mut self) -> T {
+        self.drop = None;
+        unsafe { self.data.as_ptr().cast::<T>().read() }
+    }
+}
+
+impl<const WORDS: usize> Drop for InlineBox<WORDS> {
+    fn drop(&mut self) {
+        if let Some(drop) = self.drop {
+            // SAFETY: 值尚未被取出，drop 与放入的类型对应
+            unsafe { drop(self.data.as_mut_ptr().cast()) };
+        }
+    }
+}
+
+/// 析构缓冲区中类型为 `T` 的值
+unsafe fn drop_value<T>(ptr: *mut u8) {
+    unsafe { ptr.cast::<T>().drop_in_place() };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 析构时计数的值
+    struct Counted(&'static AtomicUsize);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_inline_box_take() {
+        let boxed = InlineBox::<2>::new((7u32, 9usize)).ok().unwrap();
+        assert_eq!(unsafe { boxed.take::<(u32, usize)>() }, (7, 9));
+
+        // 捕获数据的闭包放入后可以取出调用
+        fn call<F: Fn(usize) -> usize + Send + 'static>(func: F, arg: usize) -> usize {
+            let boxed = InlineBox::<1>::new(func).ok().unwrap();
+            let func = unsafe { boxed.take::<F>() };
+            func(arg)
+        }
+        let offset = 5usize;
+        assert_eq!(call(move |x| x + offset, 1), 6);
+    }
+
+    #[test]
+    fn test_inline_box_rejects_oversized() {
+        assert!(InlineBox::<2>::fits::<[usize; 2]>());
+        assert!(!InlineBox::<2>::fits::<[usize; 3]>());
+        assert!(InlineBox::<0>::fits::<()>());
+
+        // 放不下时原样返回
+        assert_eq!(InlineBox::<1>::new([1usize, 2]).err(), Some([1, 2]));
+    }
+
+    #[test]
+    fn test_inline_box_drop() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        // 没有取出的值随缓冲区析构
+        drop(InlineBox::<1>::new(Counted(&DROPS)).ok().unwrap());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        // 取出的值由调用者负责，缓冲区不会再析构一次
+        let value = unsafe { InlineBox::<1>::new(Counted(&DROPS)).ok().unwrap().take::<Counted>() };
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        drop(value);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1 +1,4 @@
+#[cfg(feature = "alloc")]
 pub mod allocator;
+pub mod inline;
+pub mod ring;
@@ -0,0 +1,205 @@
+//! # RingBuffer - 固定容量的环形队列
+//!
+//! 关闭 `alloc` feature 时代替 `VecDeque` 使用，容量在编译期确定，不需要分配内存。
+//! 队列满时 `push_back` 把元素原样返回，由调用者决定丢弃、重试还是报错。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use neon_rtos2::mem::ring::RingBuffer;
+//!
+//! let mut ring: RingBuffer<u32, 2> = RingBuffer::new();
+//! assert!(ring.push_back(1).is_ok());
+//! assert!(ring.push_back(2).is_ok());
+//! assert_eq!(ring.push_back(3), Err(3));
+//! assert_eq!(ring.pop_front(), Some(1));
+//! ```
+
+use core::mem::MaybeUninit;
+
+/// 固定容量的 FIFO 环形队列
+pub struct RingBuffer<T, const N: usize> {
+    buffer: [MaybeUninit<T>; N],
+    /// 队首下标
+    head: usize,
+    /// 元素数量
+    len: usize,
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    /// 创建空队列
+    pub const fn new() -> Self {
+        Self {
+            buffer: [const { MaybeUninit::uninit() }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// 队列容量
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 队列是否已满
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// 在队尾加入元素
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 加入成功
+    /// - `Err(value)`: 队列已满，返回传入的元素
+    pub fn push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.buffer[(self.head + self.len) % N].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// 取出队首元素
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: [head, head + len) 范围内的元素都已经初始化
+        let value = unsafe { self.buffer[self.head].assume_init_read() };
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// 查看队首元素
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: 同 pop_front
+        Some(unsafe { self.buffer[self.head].assume_init_ref() })
+    }
+
+    /// 按 FIFO 顺序遍历元素
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        // SAFETY: 同 pop_front
+        (0..self.len).map(move |i| unsafe { self.buffer[(self.head + i) % N].assume_init_ref() })
+    }
+
+    /// 清空队列
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for RingBuffer<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for RingBuffer<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// 析构时计数的元素
+    struct Counted(&'static AtomicUsize);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_fifo_and_full() {
+        let mut ring: RingBuffer<u32, 3> = RingBuffer::new();
+        assert!(ring.is_empty());
+        assert_eq!(ring.capacity(), 3);
+        assert_eq!(ring.front(), None);
+        assert_eq!(ring.pop_front(), None);
+
+        for i in 0..3 {
+            assert_eq!(ring.push_back(i), Ok(()));
+        }
+        assert!(ring.is_full());
+        assert_eq!(ring.push_back(3), Err(3));
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.front(), Some(&0));
+        assert_eq!(ring.pop_front(), Some(0));
+        assert_eq!(ring.pop_front(), Some(1));
+        assert_eq!(ring.len(), 1);
+    }
+
+    #[test]
+    fn test_ring_buffer_wraps_around() {
+        let mut ring: RingBuffer<u32, 3> = RingBuffer::new();
+        for round in 0..5 {
+            ring.push_back(round * 2).unwrap();
+            ring.push_back(round * 2 + 1).unwrap();
+            assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [round * 2, round * 2 + 1]);
+            assert_eq!(ring.pop_front(), Some(round * 2));
+            assert_eq!(ring.pop_front(), Some(round * 2 + 1));
+        }
+        assert!(ring.is_empty());
+        assert_eq!(format!("{:?}", ring), "[]");
+
+        ring.push_back(1).unwrap();
+        ring.push_back(2).unwrap();
+        assert_eq!(format!("{:?}", ring), "[1, 2]");
+    }
+
+    #[test]
+    fn test_ring_buffer_zero_capacity() {
+        let mut ring: RingBuffer<u32, 0> = RingBuffer::default();
+        assert!(ring.is_empty() && ring.is_full());
+        assert_eq!(ring.push_back(1), Err(1));
+        assert_eq!(ring.pop_front(), None);
+    }
+
+    #[test]
+    fn test_ring_buffer_drops_remaining() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        let mut ring: RingBuffer<Counted, 4> = RingBuffer::new();
+        for _ in 0..4 {
+            assert!(ring.push_back(Counted(&DROPS)).is_ok());
+        }
+        // 队列已满时返回的元素由调用者析构
+        drop(ring.push_back(Counted(&DROPS)));
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        drop(ring.pop_front());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+
+        ring.clear();
+        assert_eq!(DROPS.load(Ordering::SeqCst), 5);
+        ring.push_back(Counted(&DROPS)).ok().unwrap();
+        drop(ring);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 6);
+    }
+}
@@ -7,34 +7,75 @@
 //! - **简单**: 单线程执行，无需复杂的同步
 //! - **轻量**: 最小化内存占用
 //! - **可预测**: 确定性的执行顺序
+//!
+//! 关闭 `alloc` feature 时就绪队列的容量为 `config::MAX_ASYNC_TASKS`，
+//! 只能通过 `spawn_static` 添加由调用者提供存储的 Future。
 
 use core::future::Future;
 use core::pin::Pin;
 use core::task::{Context, Poll};
+#[cfg(feature = "alloc")]
 use crate::compat::{Box, VecDeque};
+#[cfg(not(feature = "alloc"))]
+use crate::config::MAX_ASYNC_TASKS;
+use crate::error::{Result, RtosError};
+#[cfg(not(feature = "alloc"))]
+use crate::mem::ring::RingBuffer;
 use super::waker::TaskWaker;
 
+/// 被包装的 Future
+enum TaskFuture {
+    /// 堆上的 Future
+    #[cfg(feature = "alloc")]
+    Boxed(Pin<Box<dyn Future<Output = ()> + Send>>),
+    /// 调用者提供存储的 Future
+    Static(Pin<&'static mut (dyn Future<Output = ()> + Send)>),
+}
+
 /// 异步任务包装器
 ///
 /// 将 Future 包装为可执行的任务
 pub struct AsyncTask {
     /// 被包装的 Future
-    future: Pin<Box<dyn Future<Output = ()> + Send>>,
+    future: TaskFuture,
     /// 任务 ID，用于创建 Waker
     task_id: usize,
 }
 
 impl AsyncTask {
     /// 创建新的异步任务
+    #[cfg(feature = "alloc")]
     pub fn new<F>(future: F, task_id: usize) -> Self
     where
         F: Future<Output = ()> + Send + 'static,
     {
         Self {
-            future: Box::pin(future),
+            future: TaskFuture::Boxed(Box::pin(future)),
             task_id,
         }
     }
+
+    /// 使用调用者提供的存储创建异步任务，不需要堆分配
+    pub fn from_static<F>(future: &'static mut F, task_id: usize) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            future: TaskFuture::Static(Pin::static_mut(future)),
+            task_id,
+        }
+    }
+
+    /// poll 一次被包装的 Future
+    fn poll(&mut self) -> Poll<()> {
+        let waker = TaskWaker::new(self.task_id);
+        let mut cx = Context::from_waker(&waker);
+        match &mut self.future {
+            #[cfg(feature = "alloc")]
+            TaskFuture::Boxed(future) => future.as_mut().poll(&mut cx),
+            TaskFuture::Static(future) => future.as_mut().poll(&mut cx),
+        }
+    }
 }
 
 /// 异步执行器
@@ -57,7 +98,10 @@ impl AsyncTask {
 /// ```
 pub struct Executor {
     /// 就绪队列
+    #[cfg(feature = "alloc")]
     ready_queue: VecDeque<AsyncTask>,
+    #[cfg(not(feature = "alloc"))]
+    ready_queue: RingBuffer<AsyncTask, MAX_ASYNC_TASKS>,
     /// 下一个任务 ID
     next_task_id: usize,
 }
@@ -66,7 +110,7 @@ impl Executor {
     /// 创建新的执行器
     pub fn new() -> Self {
         Self {
-            ready_queue: VecDeque::new(),
+            ready_queue: Default::default(),
             next_task_id: 0,
         }
     }
@@ -91,6 +135,7 @@ impl Executor {
     ///     });
     /// }
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn spawn<F>(&mut self, future: F) -> usize
     where
         F: Future<Output = ()> + Send + 'static,
@@ -99,11 +144,65 @@ impl Executor {
         self.next_task_id += 1;
         
         let task = AsyncTask::new(future, task_id);
-        self.ready_queue.push_back(task);
+        self.push(task);
         
         task_id
     }
 
+    /// 添加由调用者提供存储的异步任务
+    ///
+    /// Future 保存在调用者的 `&'static mut` 存储中，执行器只保存引用，不需要堆分配。
+    ///
+    /// # 返回值
+    /// - `Ok(task_id)`: 任务 ID
+    /// - `Err(RtosError::QueueFull)`: 就绪队列已满（关闭 `alloc` 时）
+    ///
+    /// # 示例
+    ///
+    /// ```rust,no_run
+    /// use core::future::Future;
+    /// use core::pin::Pin;
+    /// use core::task::{Context, Poll};
+    /// use neon_rtos2::runtime::Executor;
+    ///
+    /// struct Blink;
+    ///
+    /// impl Future for Blink {
+    ///     type Output = ();
+    ///     fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+    ///         Poll::Ready(())
+    ///     }
+    /// }
+    ///
+    /// static mut BLINK: Blink = Blink;
+    ///
+    /// let mut executor = Executor::new();
+    /// executor.spawn_static(unsafe { &mut *core::ptr::addr_of_mut!(BLINK) }).unwrap();
+    /// executor.run();
+    /// ```
+    pub fn spawn_static<F>(&mut self, future: &'static mut F) -> Result<usize>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let task_id = self.next_task_id;
+        if !self.push(AsyncTask::from_static(future, task_id)) {
+            return Err(RtosError::QueueFull);
+        }
+        self.next_task_id += 1;
+        Ok(task_id)
+    }
+
+    /// 加入就绪队列，队列已满时返回 `false`
+    fn push(&mut self, task: AsyncTask) -> bool {
+        #[cfg(feature = "alloc")]
+        self.ready_queue.push_back(task);
+        #[cfg(not(feature = "alloc"))]
+        if self.ready_queue.push_back(task).is_err() {
+            return false;
+        }
+        true
+    }
+
     /// 运行执行器
     ///
     /// 持续执行就绪队列中的任务，直到所有任务完成。
@@ -113,16 +212,13 @@ impl Executor {
     /// 在嵌入式环境中，通常不会返回，而是在空闲时进入低功耗模式。
     pub fn run(&mut self) {
         while let Some(mut task) = self.ready_queue.pop_front() {
-            let waker = TaskWaker::new(task.task_id);
-            let mut cx = Context::from_waker(&waker);
-            
-            match task.future.as_mut().poll(&mut cx) {
+            match task.poll() {
                 Poll::Ready(()) => {
                     // 任务完成，不再重新入队
                 }
                 Poll::Pending => {
-                    // 任务未完成，重新入队等待下次调度
-                    self.ready_queue.push_back(task);
+                    // 任务未完成，重新入队等待下次调度（刚取出一个任务，不会满）
+                    self.push(task);
                 }
             }
         }
@@ -138,16 +234,13 @@ impl Executor {
     /// - `false`: 所有任务已完成
     pub fn poll_once(&mut self) -> bool {
         if let Some(mut task) = self.ready_queue.pop_front() {
-            let waker = TaskWaker::new(task.task_id);
-            let mut cx = Context::from_waker(&waker);
-            
-            match task.future.as_mut().poll(&mut cx) {
+            match task.poll() {
                 Poll::Ready(()) => {
                     // 任务完成
                 }
                 Poll::Pending => {
                     // 任务未完成，重新入队
-                    self.ready_queue.push_back(task);
+                    self.push(task);
                 }
             }
         }
@@ -175,6 +268,7 @@ impl Default for Executor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "alloc")]
     use core::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
@@ -184,6 +278,7 @@ mod tests {
         assert_eq!(executor.pending_count(), 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn test_executor_spawn() {
         let mut executor = Executor::new();
@@ -197,6 +292,7 @@ mod tests {
         assert_eq!(executor.pending_count(), 2);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn test_executor_run_simple() {
         static COUNTER: AtomicUsize = AtomicUsize::new(0);
@@ -217,6 +313,7 @@ mod tests {
         assert!(executor.is_empty());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn test_executor_poll_once() {
         static COUNTER: AtomicUsize = AtomicUsize::new(0);
@@ -241,5 +338,22 @@ mod tests {
         
         assert_eq!(COUNTER.load(Ordering::SeqCst), 2);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_executor_spawn_static() {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let mut executor = Executor::new();
+        let future = crate::compat::Box::leak(crate::compat::Box::new(async {
+            COUNTER.fetch_add(1, Ordering::SeqCst);
+        }));
+        assert_eq!(executor.spawn_static(future), Ok(0));
+        assert_eq!(executor.spawn(async {}), 1);
+
+        executor.run();
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+        assert!(executor.is_empty());
+    }
 }
 
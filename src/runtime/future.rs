@@ -4,10 +4,10 @@
 
 use core::future::Future;
 use core::pin::Pin;
-use core::task::{Context, Poll, Waker};
-use alloc::collections::VecDeque;
+use core::task::{Context, Poll};
 use spin::Mutex;
 use crate::kernel::time::systick::Systick;
+use crate::sync::wait_queue::WakerQueue;
 
 // ============================================================================
 // 异步信号量
@@ -43,7 +43,7 @@ use crate::kernel::time::systick::Systick;
 /// ```
 pub struct AsyncSignal {
     /// 等待队列
-    waiters: Mutex<VecDeque<Waker>>,
+    waiters: Mutex<WakerQueue>,
     /// 信号计数
     count: Mutex<usize>,
 }
@@ -52,7 +52,7 @@ impl AsyncSignal {
     /// 创建新的异步信号量
     pub const fn new() -> Self {
         Self {
-            waiters: Mutex::new(VecDeque::new()),
+            waiters: Mutex::new(WakerQueue::new()),
             count: Mutex::new(0),
         }
     }
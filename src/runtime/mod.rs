@@ -6,7 +6,7 @@
 //!
 //! - 🚀 **轻量级执行器**: 适合嵌入式环境的简单执行器
 //! - ⚡ **零成本 Waker**: 基于任务 ID 的唤醒机制
-//! - 🔄 **异步原语**: 异步信号量、定时器、通道
+//! - 🔄 **异步原语**: 异步信号量、定时器、通道（通道需要 `alloc`）
 //! - 🎯 **Select 宏**: 同时等待多个异步操作
 //!
 //! ## 使用示例
@@ -40,30 +40,32 @@
 //!
 //! ### 使用 Select
 //!
-/// ```rust,no_run
-/// # use neon_rtos2::select;
-/// # use neon_rtos2::kernel::time::timer::Timer;
-/// # struct Rx;
-/// # impl Rx { async fn recv(&self) -> i32 { 0 } }
-/// # let rx = Rx;
-/// # let timer = Timer;
-/// async fn handle_events() {
-///     select! {
-///         msg = rx.recv() => println!("Received: {:?}", msg),
-///         _ = Timer::sleep(1000) => println!("Timeout!"),
-///     }
-/// }
-/// ```
+//! ```rust,no_run
+//! # use neon_rtos2::select;
+//! # use neon_rtos2::kernel::time::timer::Timer;
+//! # struct Rx;
+//! # impl Rx { async fn recv(&self) -> i32 { 0 } }
+//! # let rx = Rx;
+//! # let timer = Timer;
+//! async fn handle_events() {
+//!     select! {
+//!         msg = rx.recv() => println!("Received: {:?}", msg),
+//!         _ = Timer::sleep(1000) => println!("Timeout!"),
+//!     }
+//! }
+//! ```
 
 mod waker;
 mod executor;
 mod future;
+#[cfg(feature = "alloc")]
 mod channel;
 pub mod select;
 
 pub use waker::TaskWaker;
 pub use executor::Executor;
 pub use future::*;
+#[cfg(feature = "alloc")]
 pub use channel::{channel, unbounded, Sender, Receiver, SendError, RecvError};
 
 // 重新导出 select 模块的类型
@@ -36,7 +36,7 @@ impl TaskWaker {
     ///
     /// 将任务状态设置为就绪
     fn wake(task_id: usize) {
-        let mut task = Task(task_id);
+        let mut task = Task::from_id(task_id);
         task.ready();
     }
 }
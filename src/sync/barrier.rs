@@ -0,0 +1,383 @@
+//! # Barrier - 可重用的屏障
+//!
+//! 基于 Arc 的屏障实现，可以通过闭包传递。
+//! `n` 个任务都调用 `wait()` 之后所有任务一起继续执行，其中最后到达的任务被选为领导者。
+//! 一轮结束后屏障自动复位，可以用于下一轮同步。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::sync::Barrier;
+//! use neon_rtos2::kernel::task::Task;
+//!
+//! let barrier = Barrier::new(3);
+//!
+//! for name in ["worker0", "worker1", "worker2"] {
+//!     let barrier = barrier.clone();
+//!     Task::builder(name)
+//!         .spawn(move |_| loop {
+//!             // 计算本轮的数据...
+//!             if barrier.wait().unwrap().is_leader() {
+//!                 // 只有一个任务负责汇总结果
+//!             }
+//!         })
+//!         .unwrap();
+//! }
+//! ```
+
+use crate::compat::Shared;
+use crate::error::Result;
+use crate::sync::wait_queue::{WaitQueue, WakerQueue};
+use spin::Mutex;
+
+/// 屏障的计数状态
+struct BarrierState {
+    /// 本轮已经到达的任务数
+    count: usize,
+    /// 轮次，每轮结束时加一
+    generation: usize,
+}
+
+struct BarrierInner {
+    /// 每轮需要到达的任务数
+    parties: usize,
+    state: Mutex<BarrierState>,
+    /// 同步等待队列
+    waiters: WaitQueue,
+    /// 异步等待者
+    async_waiters: Mutex<WakerQueue>,
+}
+
+impl BarrierInner {
+    const fn new(n: usize) -> Self {
+        Self {
+            parties: if n == 0 { 1 } else { n },
+            state: Mutex::new(BarrierState { count: 0, generation: 0 }),
+            waiters: WaitQueue::new(),
+            async_waiters: Mutex::new(WakerQueue::new()),
+        }
+    }
+
+    /// 到达屏障
+    ///
+    /// 返回本轮的轮次和是否为最后到达的任务。最后到达时结束本轮并唤醒所有等待者。
+    fn arrive(&self) -> (usize, bool) {
+        let (generation, leader) = {
+            let mut state = self.state.lock();
+            let generation = state.generation;
+            state.count += 1;
+            let leader = state.count == self.parties;
+            if leader {
+                state.count = 0;
+                state.generation = generation.wrapping_add(1);
+            }
+            (generation, leader)
+        };
+
+        if leader {
+            self.waiters.wake_all();
+            let async_wakers = core::mem::take(&mut *self.async_waiters.lock());
+            for waker in async_wakers {
+                waker.wake();
+            }
+        }
+        (generation, leader)
+    }
+
+    /// 检查 `generation` 这一轮是否已经结束
+    fn passed(&self, generation: usize) -> bool {
+        self.state.lock().generation != generation
+    }
+
+    /// 放弃本轮（本轮还没有结束时撤销到达计数）
+    fn withdraw(&self, generation: usize) -> bool {
+        let mut state = self.state.lock();
+        if state.generation != generation {
+            return false;
+        }
+        state.count -= 1;
+        true
+    }
+}
+
+/// `Barrier::wait` 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// 是否为本轮的领导者（每轮恰好一个任务返回 `true`）
+    pub fn is_leader(&self) -> bool {
+        self.leader
+    }
+}
+
+/// 可克隆、可重用的屏障
+#[derive(Clone)]
+pub struct Barrier {
+    inner: Shared<BarrierInner>,
+}
+
+impl Barrier {
+    /// 创建需要 `n` 个任务到达的屏障
+    ///
+    /// `n` 为 0 时与 1 相同，`wait()` 总是立即返回。
+    #[cfg(feature = "alloc")]
+    pub fn new(n: usize) -> Self {
+        Self {
+            inner: Shared::new(BarrierInner::new(n)),
+        }
+    }
+
+    /// 阻塞等待，直到本轮所有任务都到达
+    ///
+    /// # 返回值
+    /// - `Ok(result)`: 本轮结束，最后到达的任务 `result.is_leader()` 为 `true`
+    /// - `Err(RtosError::WaiterQueueFull)`: 等待的任务太多，本次到达被撤销
+    pub fn wait(&self) -> Result<BarrierWaitResult> {
+        let (generation, leader) = self.inner.arrive();
+        if leader {
+            return Ok(BarrierWaitResult { leader: true });
+        }
+
+        loop {
+            if let Err(e) = self.inner.waiters.wait_if(None, || Ok(!self.inner.passed(generation))) {
+                // 撤销失败说明本轮刚好结束
+                if self.inner.withdraw(generation) {
+                    return Err(e);
+                }
+            }
+            if self.inner.passed(generation) {
+                return Ok(BarrierWaitResult { leader: false });
+            }
+        }
+    }
+
+    /// 异步等待本轮所有任务到达
+    ///
+    /// Future 第一次被 poll 时到达屏障；到达后被丢弃不会撤销到达计数。
+    pub fn wait_async(&self) -> BarrierFuture<'_> {
+        BarrierFuture::new(self)
+    }
+
+    /// 每轮需要到达的任务数
+    pub fn parties(&self) -> usize {
+        self.inner.parties
+    }
+
+    /// 本轮已经到达、正在等待的任务数
+    pub fn arrived(&self) -> usize {
+        self.inner.state.lock().count
+    }
+
+    /// 获取屏障的唯一标识
+    pub fn id(&self) -> usize {
+        Shared::as_ptr(&self.inner) as usize
+    }
+}
+
+impl core::fmt::Debug for Barrier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Barrier")
+            .field("id", &self.id())
+            .field("parties", &self.parties())
+            .field("arrived", &self.arrived())
+            .finish()
+    }
+}
+
+/// 不需要堆分配、可以放在 `static` 中的屏障
+///
+/// 通过 [`handle`](Self::handle) 获取 [`Barrier`] 句柄后使用。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticBarrier;
+///
+/// static STAGE: StaticBarrier = StaticBarrier::new(2);
+///
+/// let barrier = STAGE.handle();
+/// barrier.wait().unwrap();
+/// ```
+pub struct StaticBarrier {
+    inner: BarrierInner,
+}
+
+impl StaticBarrier {
+    /// 创建需要 `n` 个任务到达的静态屏障，见 [`Barrier::new`]
+    pub const fn new(n: usize) -> Self {
+        Self { inner: BarrierInner::new(n) }
+    }
+
+    /// 获取指向这个静态屏障的 [`Barrier`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> Barrier {
+        Barrier { inner: Shared::from_static(&self.inner) }
+    }
+}
+
+impl core::fmt::Debug for StaticBarrier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticBarrier")
+            .field("parties", &self.inner.parties)
+            .field("arrived", &self.inner.state.lock().count)
+            .finish()
+    }
+}
+
+// ============================================================================
+// 异步支持
+// ============================================================================
+
+/// 异步等待屏障的 Future
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::Barrier;
+///
+/// async fn stage(barrier: Barrier) {
+///     if barrier.wait_async().await.is_leader() {
+///         // 汇总本轮结果
+///     }
+/// }
+/// ```
+pub struct BarrierFuture<'a> {
+    barrier: &'a Barrier,
+    /// 到达时的轮次
+    generation: Option<usize>,
+    registered: bool,
+}
+
+impl<'a> BarrierFuture<'a> {
+    fn new(barrier: &'a Barrier) -> Self {
+        Self {
+            barrier,
+            generation: None,
+            registered: false,
+        }
+    }
+}
+
+impl<'a> core::future::Future for BarrierFuture<'a> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        let inner = &self.barrier.inner;
+
+        // 第一次 poll 时到达屏障
+        let generation = match self.generation {
+            Some(generation) => generation,
+            None => {
+                let (generation, leader) = inner.arrive();
+                if leader {
+                    return core::task::Poll::Ready(BarrierWaitResult { leader: true });
+                }
+                self.generation = Some(generation);
+                generation
+            }
+        };
+
+        // 持有异步等待者锁检查，领导者结束本轮后才会取走等待者，不会丢失唤醒
+        let mut async_waiters = inner.async_waiters.lock();
+        if inner.passed(generation) {
+            return core::task::Poll::Ready(BarrierWaitResult { leader: false });
+        }
+        if !self.registered {
+            self.registered = async_waiters.push_back(cx.waker().clone());
+            drop(async_waiters);
+        }
+
+        core::task::Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::scheduler::Scheduler;
+    use crate::kernel::task::{Task, TaskState};
+    use crate::sync::event::Event;
+    use crate::utils::kernel_init;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use serial_test::serial;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_barrier_leader_wakes_waiters() {
+        kernel_init();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+
+        let barrier = Barrier::new(2);
+
+        // worker 已经到达并在等待
+        let (generation, leader) = barrier.inner.arrive();
+        assert!(!leader);
+        worker.block(Event::WaitQueue(barrier.inner.waiters.id()));
+        assert!(barrier.inner.waiters.enqueue(worker));
+        assert_eq!(barrier.arrived(), 1);
+
+        // 最后到达的任务是领导者，唤醒 worker 并复位屏障
+        assert!(barrier.wait().unwrap().is_leader());
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert!(barrier.inner.passed(generation));
+        assert_eq!(barrier.arrived(), 0);
+        assert!(barrier.inner.waiters.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_barrier_single_party_and_async() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let barrier = Barrier::new(0);
+        assert_eq!(barrier.parties(), 1);
+        assert!(barrier.wait().unwrap().is_leader());
+
+        let barrier = Barrier::new(2);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut first = pin!(barrier.wait_async());
+        assert_eq!(first.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(barrier.arrived(), 1);
+
+        let mut second = pin!(barrier.wait_async());
+        assert_eq!(second.as_mut().poll(&mut cx), Poll::Ready(BarrierWaitResult { leader: true }));
+        assert_eq!(first.as_mut().poll(&mut cx), Poll::Ready(BarrierWaitResult { leader: false }));
+    }
+
+    #[test]
+    #[serial]
+    fn test_static_barrier() {
+        static STAGE: StaticBarrier = StaticBarrier::new(2);
+
+        kernel_init();
+        let mut worker = Task::new("worker", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 同步等待：最后到达的任务是领导者，唤醒已到达的 worker
+        let barrier = STAGE.handle();
+        let (generation, _) = barrier.inner.arrive();
+        worker.block(Event::WaitQueue(barrier.inner.waiters.id()));
+        assert!(barrier.inner.waiters.enqueue(worker));
+        assert!(barrier.wait().unwrap().is_leader());
+        assert_eq!(worker.get_state(), TaskState::Ready);
+        assert!(barrier.inner.passed(generation));
+
+        // 异步等待：句柄共享同一个静态屏障
+        let other = STAGE.handle();
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut first = pin!(barrier.wait_async());
+        assert_eq!(first.as_mut().poll(&mut cx), Poll::Pending);
+        let mut second = pin!(other.wait_async());
+        assert_eq!(second.as_mut().poll(&mut cx), Poll::Ready(BarrierWaitResult { leader: true }));
+        assert_eq!(first.as_mut().poll(&mut cx), Poll::Ready(BarrierWaitResult { leader: false }));
+        assert_eq!(STAGE.handle().arrived(), 0);
+    }
+}
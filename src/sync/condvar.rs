@@ -45,36 +45,128 @@
 //! }
 //! ```
 
-use crate::compat::{Arc, VecDeque};
-use crate::kernel::scheduler::Scheduler;
-use crate::kernel::task::{Task, TaskState};
+use crate::compat::Shared;
 use crate::kernel::time::systick::Systick;
-use crate::hal::trigger_schedule;
 use crate::error::{Result, RtosError};
-use crate::sync::signal::WaiterList;
-use crate::sync::mutex::{Mutex, MutexGuard};
+use crate::sync::mutex::{Mutex, MutexGuard, StaticMutexGuard};
+use crate::sync::wait_queue::{WaitQueue, WakerQueue};
+use core::marker::PhantomData;
 use core::sync::atomic::{AtomicBool, Ordering};
-use core::task::Waker;
 use spin::Mutex as SpinMutex;
 
 /// 条件变量内部状态
 struct CondVarInner {
     /// 同步���待者列表
-    waiters: SpinMutex<WaiterList>,
+    waiters: WaitQueue,
     /// 异步等待者列表
-    async_waiters: SpinMutex<VecDeque<Waker>>,
+    async_waiters: SpinMutex<WakerQueue>,
     /// 是否已关闭
     closed: AtomicBool,
 }
 
 impl CondVarInner {
-    fn new() -> Self {
+    const fn new() -> Self {
         Self {
-            waiters: SpinMutex::new(WaiterList::new()),
-            async_waiters: SpinMutex::new(VecDeque::new()),
+            waiters: WaitQueue::new(),
+            async_waiters: SpinMutex::new(WakerQueue::new()),
             closed: AtomicBool::new(false),
         }
     }
+
+    /// 等待条件变量
+    ///
+    /// `unlock` 在等待队列锁内释放互斥锁，`notify_one` / `notify_all` 不会在
+    /// "释放互斥锁 - 加入等待队列"之间插入而丢失通知。被唤醒后调用 `relock` 重新获取互斥锁。
+    /// 返回重新获取的守卫和是否超时。
+    fn wait<G>(
+        &self,
+        timeout_ms: Option<usize>,
+        unlock: impl FnOnce(),
+        relock: impl FnOnce() -> Result<G>,
+    ) -> Result<(G, bool)> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(RtosError::CondVarClosed);
+        }
+
+        // 加入等待队列的同时释放互斥锁
+        let timed_out = match self.waiters.wait_if(timeout_ms, move || {
+            unlock();
+            Ok(true)
+        }) {
+            Ok(_) => false,
+            Err(RtosError::Timeout) => true,
+            Err(e) => return Err(e),
+        };
+
+        // 检查是否因为关闭而唤醒
+        if self.closed.load(Ordering::Acquire) {
+            // 尝试重新获取锁后返回错误
+            let _ = relock();
+            return Err(RtosError::CondVarClosed);
+        }
+
+        // 重新获取互斥锁
+        let new_guard = relock()?;
+        Ok((new_guard, timed_out))
+    }
+
+    /// 唤醒一个等待者（先同步等待者，后异步等待者）
+    fn notify_one(&self) {
+        if self.closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        // 首先尝试唤醒同步等待者
+        if self.waiters.wake_one().is_some() {
+            return;
+        }
+
+        // 然后尝试唤醒异步等待者
+        let waker = {
+            let mut async_waiters = self.async_waiters.lock();
+            async_waiters.pop_front()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// 唤醒所有等待者
+    fn notify_all(&self) {
+        if self.closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        // 唤醒所有同步等待者
+        self.waiters.wake_all();
+
+        // 唤醒所有异步等待者
+        let async_wakers: WakerQueue;
+        {
+            let mut async_waiters = self.async_waiters.lock();
+            async_wakers = core::mem::take(&mut *async_waiters);
+        }
+
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 关闭并唤醒所有等待者
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.waiters.wake_all();
+        let async_wakers = core::mem::take(&mut *self.async_waiters.lock());
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 同步和异步等待者的总数
+    fn waiter_count(&self) -> usize {
+        self.waiters.len() + self.async_waiters.lock().len()
+    }
 }
 
 /// 可克隆、可传递的条件变量
@@ -97,7 +189,7 @@ impl CondVarInner {
 /// ```
 #[derive(Clone)]
 pub struct CondVar {
-    inner: Arc<CondVarInner>,
+    inner: Shared<CondVarInner>,
 }
 
 impl CondVar {
@@ -109,9 +201,10 @@ impl CondVar {
     ///
     /// let condvar = CondVar::new();
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(CondVarInner::new()),
+            inner: Shared::new(CondVarInner::new()),
         }
     }
 
@@ -140,41 +233,7 @@ impl CondVar {
     /// }
     /// ```
     pub fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> Result<MutexGuard<'a, T>> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::CondVarClosed);
-        }
-
-        // 获取互斥锁的引用（在释放 guard 之前）
-        let mutex = guard.mutex();
-        
-        // 获取当前任务 ID
-        let task_id = Scheduler::get_current_task().get_taskid();
-
-        // 将当前任务加入等待队列
-        {
-            let mut waiters = self.inner.waiters.lock();
-            if !waiters.push(task_id) {
-                return Err(RtosError::WaiterQueueFull);
-            }
-        }
-
-        // 释放互斥锁（通过 unlock 方法，避免 drop 后无法访问 mutex）
-        let mutex = MutexGuard::unlock(guard);
-
-        // 阻塞当前任务
-        let condvar_id = Arc::as_ptr(&self.inner) as usize;
-        Scheduler::get_current_task().block(crate::sync::event::Event::CondVar(condvar_id));
-        trigger_schedule();
-
-        // 被唤醒后检查是否因为关闭而唤醒
-        if self.inner.closed.load(Ordering::Acquire) {
-            // 尝试重新获取锁后返回错误
-            let _ = mutex.lock();
-            return Err(RtosError::CondVarClosed);
-        }
-
-        // 重新获取互斥锁
-        mutex.lock()
+        self.wait_inner(guard, None).map(|(guard, _)| guard)
     }
 
     /// 带超时的等待条件变量
@@ -207,47 +266,20 @@ impl CondVar {
         guard: MutexGuard<'a, T>,
         timeout_ms: usize,
     ) -> Result<(MutexGuard<'a, T>, bool)> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::CondVarClosed);
-        }
-
-        let deadline = Systick::get_current_time() + timeout_ms;
-        let task_id = Scheduler::get_current_task().get_taskid();
-
-        // 将当前任务加入等待队列
-        {
-            let mut waiters = self.inner.waiters.lock();
-            if !waiters.push(task_id) {
-                return Err(RtosError::WaiterQueueFull);
-            }
-        }
-
-        // 释放互斥锁
-        let mutex = MutexGuard::unlock(guard);
-
-        // 阻塞当前任务
-        let condvar_id = Arc::as_ptr(&self.inner) as usize;
-        Scheduler::get_current_task().block(crate::sync::event::Event::CondVar(condvar_id));
-        trigger_schedule();
-
-        // 检查是否超时
-        let timed_out = Systick::get_current_time() >= deadline;
-
-        // 如果超时，从等待队列中移除
-        if timed_out {
-            let mut waiters = self.inner.waiters.lock();
-            waiters.remove(task_id);
-        }
-
-        // 检查是否因为关闭而唤醒
-        if self.inner.closed.load(Ordering::Acquire) {
-            let _ = mutex.lock();
-            return Err(RtosError::CondVarClosed);
-        }
+        self.wait_inner(guard, Some(timeout_ms))
+    }
 
-        // 重新获取互斥锁
-        let new_guard = mutex.lock()?;
-        Ok((new_guard, timed_out))
+    /// 等待条件变量的公共实现
+    fn wait_inner<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout_ms: Option<usize>,
+    ) -> Result<(MutexGuard<'a, T>, bool)> {
+        // 获取互斥锁的引用（在释放 guard 之前）
+        let mutex = guard.mutex();
+        self.inner.wait(timeout_ms, move || {
+            MutexGuard::unlock(guard);
+        }, || mutex.lock())
     }
 
     /// 带条件的等待
@@ -338,36 +370,7 @@ impl CondVar {
     /// condvar.notify_one();
     /// ```
     pub fn notify_one(&self) {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return;
-        }
-
-        // 首先尝试唤醒同步等待者
-        let task_id = {
-            let mut waiters = self.inner.waiters.lock();
-            waiters.pop_front()
-        };
-
-        if let Some(task_id) = task_id {
-            Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-            return;
-        }
-
-        // 然后尝试唤醒异步等待者
-        let waker = {
-            let mut async_waiters = self.inner.async_waiters.lock();
-            async_waiters.pop_front()
-        };
-
-        if let Some(waker) = waker {
-            waker.wake();
-        }
+        self.inner.notify_one();
     }
 
     /// 唤醒所有等待的任务
@@ -380,45 +383,14 @@ impl CondVar {
     /// condvar.notify_all();
     /// ```
     pub fn notify_all(&self) {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return;
-        }
-
-        // 唤醒所有同步等待者
-        let task_ids: [Option<usize>; 16];
-        {
-            let mut waiters = self.inner.waiters.lock();
-            task_ids = waiters.drain();
-        }
-
-        for task_id in task_ids.iter().filter_map(|&id| id) {
-            Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-        }
-
-        // 唤醒所有异步等待者
-        let async_wakers: VecDeque<Waker>;
-        {
-            let mut async_waiters = self.inner.async_waiters.lock();
-            async_wakers = core::mem::take(&mut *async_waiters);
-        }
-
-        for waker in async_wakers {
-            waker.wake();
-        }
+        self.inner.notify_all();
     }
 
     /// 关闭条件变量
     ///
     /// 关闭后，所有等待的任务会被唤醒并收到错误。
     pub fn close(&self) {
-        self.inner.closed.store(true, Ordering::Release);
-        self.notify_all();
+        self.inner.close();
     }
 
     /// 检查是否已关闭
@@ -428,15 +400,16 @@ impl CondVar {
 
     /// 获取等待者数量
     pub fn waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len() + self.inner.async_waiters.lock().len()
+        self.inner.waiter_count()
     }
 
     /// 获取唯一标识（用于调试）
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for CondVar {
     fn default() -> Self {
         Self::new()
@@ -453,23 +426,153 @@ impl core::fmt::Debug for CondVar {
     }
 }
 
+// ============================================================================
+// StaticCondVar - 静态条件变量
+// ============================================================================
+
+/// 不需要堆分配、可以放在 `static` 中的条件变量
+///
+/// 与 [`CondVar`] 使用相同的等待逻辑，配合 [`StaticMutex`](crate::sync::StaticMutex) 使用。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::{StaticCondVar, StaticMutex};
+///
+/// static READY: StaticMutex<bool> = StaticMutex::new(false);
+/// static READY_CHANGED: StaticCondVar = StaticCondVar::new();
+///
+/// fn wait_ready() {
+///     let mut guard = READY.lock().unwrap();
+///     while !*guard {
+///         guard = READY_CHANGED.wait(guard).unwrap();
+///     }
+/// }
+/// ```
+pub struct StaticCondVar {
+    inner: CondVarInner,
+}
+
+impl StaticCondVar {
+    /// 创建新的静态条件变量
+    pub const fn new() -> Self {
+        Self { inner: CondVarInner::new() }
+    }
+
+    /// 获取指向这个静态条件变量的 [`CondVar`] 句柄，不需要 `alloc`
+    ///
+    /// 句柄与 [`Mutex`] 配合使用，可以是 [`StaticMutex::handle`] 返回的句柄。
+    pub fn handle(&'static self) -> CondVar {
+        CondVar { inner: Shared::from_static(&self.inner) }
+    }
+
+    /// 等待条件变量，见 [`CondVar::wait`]
+    pub fn wait<'a, T>(&self, guard: StaticMutexGuard<'a, T>) -> Result<StaticMutexGuard<'a, T>> {
+        self.wait_inner(guard, None).map(|(guard, _)| guard)
+    }
+
+    /// 带超时的等待条件变量
+    ///
+    /// 返回重新获取的守卫和是否超时。
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: StaticMutexGuard<'a, T>,
+        timeout_ms: usize,
+    ) -> Result<(StaticMutexGuard<'a, T>, bool)> {
+        self.wait_inner(guard, Some(timeout_ms))
+    }
+
+    /// 等待直到条件不再满足
+    pub fn wait_while<'a, T, F>(
+        &self,
+        mut guard: StaticMutexGuard<'a, T>,
+        mut condition: F,
+    ) -> Result<StaticMutexGuard<'a, T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        while condition(&*guard) {
+            guard = self.wait(guard)?;
+        }
+        Ok(guard)
+    }
+
+    fn wait_inner<'a, T>(
+        &self,
+        guard: StaticMutexGuard<'a, T>,
+        timeout_ms: Option<usize>,
+    ) -> Result<(StaticMutexGuard<'a, T>, bool)> {
+        let mutex = guard.mutex();
+        self.inner.wait(timeout_ms, move || {
+            StaticMutexGuard::unlock(guard);
+        }, || mutex.lock())
+    }
+
+    /// 唤醒一个等待的任务
+    pub fn notify_one(&self) {
+        self.inner.notify_one();
+    }
+
+    /// 唤醒所有等待的任务
+    pub fn notify_all(&self) {
+        self.inner.notify_all();
+    }
+
+    /// 关闭条件变量，唤醒所有等待者
+    pub fn close(&self) {
+        self.inner.close();
+    }
+
+    /// 检查是否已关闭
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
+    /// 获取等待者数量
+    pub fn waiter_count(&self) -> usize {
+        self.inner.waiter_count()
+    }
+
+    /// 获取唯一标识（用于调试）
+    pub fn id(&self) -> usize {
+        &self.inner as *const CondVarInner as usize
+    }
+}
+
+impl Default for StaticCondVar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for StaticCondVar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticCondVar")
+            .field("id", &self.id())
+            .field("waiters", &self.waiter_count())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
 // ============================================================================
 // 异步支持
 // ============================================================================
 
 /// 条件变量的异步等待 Future
+///
+/// 不会释放或重新获取互斥锁，`T` 只用于标记等待所配合的互斥锁类型。
 pub struct CondVarFuture<'a, T> {
     condvar: &'a CondVar,
-    mutex: &'a Mutex<T>,
     registered: bool,
+    _marker: PhantomData<&'a Mutex<T>>,
 }
 
 impl<'a, T> CondVarFuture<'a, T> {
-    fn new(condvar: &'a CondVar, mutex: &'a Mutex<T>) -> Self {
+    fn new(condvar: &'a CondVar, _mutex: &'a Mutex<T>) -> Self {
         Self {
             condvar,
-            mutex,
             registered: false,
+            _marker: PhantomData,
         }
     }
 }
@@ -487,8 +590,7 @@ impl<'a, T> core::future::Future for CondVarFuture<'a, T> {
 
         if !self.registered {
             let mut async_waiters = self.condvar.inner.async_waiters.lock();
-            async_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_waiters.push_back(cx.waker().clone());
             return core::task::Poll::Pending;
         }
 
@@ -519,6 +621,7 @@ impl CondVar {
 ///
 /// let cv = condvar();
 /// ```
+#[cfg(feature = "alloc")]
 pub fn condvar() -> CondVar {
     CondVar::new()
 }
@@ -545,6 +648,7 @@ pub fn condvar() -> CondVar {
 ///     guard = condvar.wait(guard).unwrap();
 /// }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn mutex_condvar_pair<T>(data: T) -> (Mutex<T>, CondVar) {
     (Mutex::new(data), CondVar::new())
 }
@@ -555,6 +659,7 @@ mod tests {
     use crate::utils::kernel_init;
     use serial_test::serial;
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_v2_basic() {
@@ -568,6 +673,7 @@ mod tests {
         assert_eq!(condvar.waiter_count(), 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_v2_clone() {
@@ -581,6 +687,7 @@ mod tests {
         assert_eq!(condvar1.id(), condvar2.id());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_v2_notify_without_waiters() {
@@ -595,6 +702,7 @@ mod tests {
         condvar.notify_all();
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_v2_close() {
@@ -609,6 +717,7 @@ mod tests {
         assert!(condvar.is_closed());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_v2_debug() {
@@ -624,6 +733,7 @@ mod tests {
         assert!(debug_str.contains("closed"));
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_condvar_pair() {
@@ -643,6 +753,7 @@ mod tests {
         assert!(!condvar.is_closed());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_condvar_convenience_function() {
@@ -653,5 +764,36 @@ mod tests {
         let cv = condvar();
         assert!(!cv.is_closed());
     }
-}
 
+    #[test]
+    #[serial]
+    fn test_static_condvar() {
+        use crate::kernel::scheduler::Scheduler;
+        use crate::kernel::task::{Task, TaskState};
+        use crate::sync::event::Event;
+        use crate::sync::StaticMutex;
+
+        static READY: StaticMutex<bool> = StaticMutex::new(false);
+        static READY_CHANGED: StaticCondVar = StaticCondVar::new();
+
+        kernel_init();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        waiter.block(Event::WaitQueue(READY_CHANGED.inner.waiters.id()));
+        assert!(READY_CHANGED.inner.waiters.enqueue(waiter));
+        *READY.lock().unwrap() = true;
+        READY_CHANGED.notify_one();
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+
+        // 条件已经满足时不等待
+        let guard = READY_CHANGED.wait_while(READY.lock().unwrap(), |ready| !*ready).unwrap();
+        assert!(*guard);
+        drop(guard);
+
+        READY_CHANGED.close();
+        assert!(matches!(READY_CHANGED.wait(READY.lock().unwrap()), Err(RtosError::CondVarClosed)));
+        assert!(!READY.is_locked());
+    }
+}
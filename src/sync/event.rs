@@ -1,5 +1,6 @@
 use crate::kernel::task::Task;
 use crate::kernel::task::TaskState;
+use crate::sync::wait_queue::WaitQueue;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Event {
@@ -15,14 +16,25 @@ pub enum Event {
     CondVar(usize),
     Barrier(usize),
     Once(usize),
+    /// 等待任务通知（参数为接收通知的任务 ID）
+    Notify(usize),
+    /// 等待队列（参数为等待队列地址）
+    WaitQueue(usize),
+    /// 同时等待多个对象（参数为等待任务 ID）
+    WaitAny(usize),
+    /// 等待事件组（参数为事件组地址）
+    EventGroup(usize),
 }
 
 impl Event {
-    //根据事件唤醒被所有被这个事件阻塞的task
-    pub(crate) fn wake_task(event_type: Event) {
-        Task::for_each(|mut task, _| {
+    /// 唤醒所有被这个事件阻塞的任务 - O(MAX_TASKS)
+    ///
+    /// 用于通过 `Task::block` 手动阻塞的任务；内核对象和定时器通过各自的等待队列
+    /// 或登记的等待者唤醒，不使用这个遍历所有任务控制块的方法。
+    pub fn wake_task(event_type: Event) {
+        Task::for_each(|task, _| {
             if task.get_state() == TaskState::Blocked(event_type) {
-                task.ready();
+                WaitQueue::wake_task(*task);
             }
         });
     }
@@ -0,0 +1,682 @@
+//! # EventGroup - 事件组（位标志同步）
+//!
+//! 事件组保存一个 32 位的标志字，任务可以等待其中的某些位被设置：
+//!
+//! | 操作 | 说明 |
+//! |------|------|
+//! | `set_bits` | 设置标志位，唤醒条件满足的等待者 |
+//! | `clear_bits` | 清除标志位 |
+//! | `wait_bits` | 等待任意一位（`WaitAny`）或全部位（`WaitAll`）被设置 |
+//! | `sync` | 设置自己的位并等待所有参与者的位（会合点） |
+//! | `set_bits_from_isr` | 在中断中记录标志位，唤醒推迟到 SysTick 或任务切换时执行 |
+//!
+//! 设置标志位时在同一个临界区内检查所有等待者，等待者返回的是条件满足时的标志字，
+//! 不会被随后的 `clear_on_exit` 或 `clear_bits` 影响。
+//!
+//! `set_bits_from_isr` 只用原子操作记录标志位，不获取事件组或调度器的锁；
+//! 合并标志位和唤醒等待者通过 `kernel::deferred` 推迟执行。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::sync::{EventGroup, WaitMode};
+//! use neon_rtos2::kernel::task::Task;
+//!
+//! const RX_DONE: u32 = 1 << 0;
+//! const TX_DONE: u32 = 1 << 1;
+//!
+//! let events = EventGroup::new();
+//! let events_clone = events.clone();
+//!
+//! Task::builder("driver")
+//!     .spawn(move |_| {
+//!         events_clone.set_bits(RX_DONE | TX_DONE);
+//!     })
+//!     .unwrap();
+//!
+//! Task::builder("app")
+//!     .spawn(move |_| {
+//!         // 等待收发都完成，返回时清除这两位
+//!         let bits = events.wait_bits(RX_DONE | TX_DONE, WaitMode::WaitAll, true, Some(100)).unwrap();
+//!         assert_eq!(bits & (RX_DONE | TX_DONE), RX_DONE | TX_DONE);
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::compat::Shared;
+use crate::error::{Result, RtosError};
+use crate::hal::trigger_schedule;
+use crate::kernel::deferred::{self, Deferred};
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::task::{Task, TaskState};
+use crate::kernel::time::systick::Systick;
+use crate::kernel::time::timer::Timer;
+use crate::sync::event::Event;
+use crate::sync::wait_queue::WaitQueue;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use spin::Mutex as SpinMutex;
+
+/// 每个事件组最多同时等待的任务数
+const MAX_EVENT_GROUP_WAITERS: usize = 16;
+
+// ============================================================================
+// WaitMode
+// ============================================================================
+
+/// 等待条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitMode {
+    /// 掩码中的所有位都被设置
+    #[default]
+    WaitAll,
+    /// 掩码中的任意一位被设置
+    WaitAny,
+}
+
+impl WaitMode {
+    /// 检查标志字是否满足条件
+    fn satisfied(self, bits: u32, mask: u32) -> bool {
+        match self {
+            WaitMode::WaitAll => bits & mask == mask,
+            WaitMode::WaitAny => bits & mask != 0,
+        }
+    }
+}
+
+// ============================================================================
+// 内部状态
+// ============================================================================
+
+/// 一个等待请求
+#[derive(Debug, Clone, Copy)]
+struct BitsRequest {
+    /// 等待的任务 ID
+    task: usize,
+    /// 等待的位
+    mask: u32,
+    /// 等待条件
+    mode: WaitMode,
+    /// 条件满足时是否清除 `mask`
+    clear_on_exit: bool,
+    /// 条件满足时的标志字（由设置标志位的一方填写）
+    result: Option<u32>,
+}
+
+struct EventGroupState {
+    /// 标志字
+    bits: u32,
+    /// 等待请求
+    requests: [Option<BitsRequest>; MAX_EVENT_GROUP_WAITERS],
+}
+
+impl EventGroupState {
+    /// 设置标志位并检查所有等待请求
+    ///
+    /// 条件满足的请求记录当前标志字，所有请求检查完之后再统一清除 `clear_on_exit` 的位，
+    /// 因此同时满足的等待者看到的是同一个标志字。除 `skip` 外被满足的任务会被唤醒。
+    fn set(&mut self, bits: u32, skip: Option<usize>) {
+        self.set_with(bits, skip, |task| Some(WaitQueue::wake_task(task)));
+    }
+
+    /// 与 `set` 相同，但用 `wake` 唤醒任务
+    ///
+    /// `wake` 返回 `None`（锁被占用）时对应的请求保持未满足，留到下一次检查。
+    ///
+    /// # 返回值
+    /// 所有满足条件的请求都处理完时返回 `true`
+    fn set_with(&mut self, bits: u32, skip: Option<usize>, wake: impl Fn(Task) -> Option<bool>) -> bool {
+        self.bits |= bits;
+        let value = self.bits;
+        let mut clear = 0;
+        let mut complete = true;
+
+        for request in self.requests.iter_mut().flatten() {
+            if request.result.is_some() || !request.mode.satisfied(value, request.mask) {
+                continue;
+            }
+            if skip != Some(request.task) && wake(Task::from_id(request.task)).is_none() {
+                complete = false;
+                continue;
+            }
+            request.result = Some(value);
+            if request.clear_on_exit {
+                clear |= request.mask;
+            }
+        }
+
+        self.bits &= !clear;
+        complete
+    }
+
+    /// 查找任务的等待请求
+    fn find(&self, task: usize) -> Option<usize> {
+        self.requests
+            .iter()
+            .position(|request| request.is_some_and(|request| request.task == task))
+    }
+
+    /// 取出任务已经满足的等待结果
+    fn take_result(&mut self, task: usize) -> Option<u32> {
+        let slot = self.find(task)?;
+        let result = self.requests[slot]?.result?;
+        self.requests[slot] = None;
+        Some(result)
+    }
+
+    /// 移除任务的等待请求
+    fn remove(&mut self, task: usize) {
+        if let Some(slot) = self.find(task) {
+            self.requests[slot] = None;
+        }
+    }
+
+    /// 登记等待请求
+    ///
+    /// 没有空位时回收已经不在等待的任务（被删除或被其他方式唤醒）留下的请求。
+    fn register(&mut self, request: BitsRequest) -> Result<()> {
+        if self.find(request.task).is_some() {
+            return Ok(());
+        }
+        let slot = self
+            .requests
+            .iter()
+            .position(Option::is_none)
+            .or_else(|| {
+                self.requests.iter().position(|stale| {
+                    stale.is_some_and(|stale| {
+                        !matches!(Task::from_id(stale.task).get_state(), TaskState::Blocked(_))
+                    })
+                })
+            })
+            .ok_or(RtosError::WaiterQueueFull)?;
+        self.requests[slot] = Some(request);
+        Ok(())
+    }
+}
+
+struct EventGroupInner {
+    /// 标志字和等待请求
+    state: SpinMutex<EventGroupState>,
+    /// 中断中记录、尚未合并的标志位
+    pending: AtomicU32,
+    /// 是否已经登记了合并 `pending` 的延迟调用
+    queued: AtomicBool,
+}
+
+impl EventGroupInner {
+    const fn new(bits: u32) -> Self {
+        Self {
+            state: SpinMutex::new(EventGroupState {
+                bits,
+                requests: [None; MAX_EVENT_GROUP_WAITERS],
+            }),
+            pending: AtomicU32::new(0),
+            queued: AtomicBool::new(false),
+        }
+    }
+
+    /// 合并中断暂存的标志位
+    fn merge_pending(&self, state: &mut EventGroupState) {
+        let pending = self.pending.swap(0, Ordering::AcqRel);
+        if pending != 0 {
+            state.set(pending, None);
+        }
+    }
+
+    /// 在锁内执行操作
+    ///
+    /// 进入时合并暂存的标志位；释放锁之后如果中断又暂存了标志位，
+    /// 尝试再次加锁合并（锁被其他任务持有时由它负责合并）。
+    fn locked<R>(&self, f: impl FnOnce(&mut EventGroupState) -> R) -> R {
+        let result = {
+            let mut state = self.state.lock();
+            self.merge_pending(&mut state);
+            f(&mut state)
+        };
+        while self.pending.load(Ordering::Acquire) != 0 {
+            let Some(mut state) = self.state.try_lock() else {
+                break;
+            };
+            self.merge_pending(&mut state);
+        }
+        result
+    }
+
+    /// 登记合并 `pending` 的延迟调用，已经登记过时不重复登记
+    fn defer_merge(&self) -> Result<()> {
+        if self.queued.swap(true, Ordering::AcqRel) {
+            // 已登记的调用会合并这次的标志位
+            return Ok(());
+        }
+        let call = Deferred {
+            func: Self::merge_deferred,
+            args: [self as *const Self as usize, 0, 0, 0],
+        };
+        deferred::defer(call).inspect_err(|_| self.queued.store(false, Ordering::Release))
+    }
+
+    /// 合并中断记录的标志位（由 `kernel::deferred` 在节拍或任务切换上下文中调用）
+    ///
+    /// 可能运行在中断中，不等待任何锁：事件组的锁被其他任务持有时由持有者在释放锁时合并；
+    /// 调度器或等待者状态的锁被占用、有等待者没能唤醒时重新登记，留到下一次处理。
+    fn merge_deferred(args: [usize; 4]) {
+        // SAFETY: 登记时事件组仍然有效，事件组被释放前会先执行已登记的调用
+        let inner = unsafe { &*(args[0] as *const EventGroupInner) };
+        inner.queued.store(false, Ordering::Release);
+        let Some(mut state) = inner.state.try_lock() else {
+            return;
+        };
+        let pending = inner.pending.swap(0, Ordering::AcqRel);
+        if !state.set_with(pending, None, |task| Scheduler::try_wake_task(&task)) {
+            let _ = inner.defer_merge();
+        }
+    }
+}
+
+impl Drop for EventGroupInner {
+    fn drop(&mut self) {
+        // 延迟调用持有指向内部状态的指针，释放之前执行掉
+        if self.queued.load(Ordering::Acquire) {
+            deferred::run_deferred();
+        }
+    }
+}
+
+// ============================================================================
+// EventGroup
+// ============================================================================
+
+/// 事件组
+///
+/// 内部状态通过 `Shared` 共享（堆上分配或来自 `StaticEventGroup`），克隆后指向同一个事件组。
+#[derive(Clone)]
+pub struct EventGroup {
+    inner: Shared<EventGroupInner>,
+}
+
+impl EventGroup {
+    /// 创建所有位都为 0 的事件组
+    #[cfg(feature = "alloc")]
+    pub fn new() -> Self {
+        Self::with_bits(0)
+    }
+
+    /// 创建带初始标志字的事件组
+    #[cfg(feature = "alloc")]
+    pub fn with_bits(bits: u32) -> Self {
+        Self {
+            inner: Shared::new(EventGroupInner::new(bits)),
+        }
+    }
+
+    /// 获取当前标志字
+    pub fn get_bits(&self) -> u32 {
+        self.inner.locked(|state| state.bits)
+    }
+
+    /// 设置标志位
+    ///
+    /// 唤醒条件满足的等待者，返回设置（以及等待者的 `clear_on_exit` 清除）之后的标志字。
+    pub fn set_bits(&self, bits: u32) -> u32 {
+        self.inner.locked(|state| {
+            state.set(bits, None);
+            state.bits
+        })
+    }
+
+    /// 在中断中设置标志位
+    ///
+    /// 只用原子操作记录标志位，不获取事件组或调度器的锁，被打断的任务持有这些锁时也不会死锁。
+    /// 合并标志位和唤醒等待者推迟到下一次 SysTick 或任务切换时执行；
+    /// 在此之前任务对事件组的任何操作也会先合并这些标志位。
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 已记录标志位并登记唤醒
+    /// - `Err(RtosError::QueueFull)`: 中断延迟处理队列已满，标志位已记录，
+    ///   但要等到任务下一次操作事件组时才会合并并唤醒等待者
+    pub fn set_bits_from_isr(&self, bits: u32) -> Result<()> {
+        self.inner.pending.fetch_or(bits, Ordering::AcqRel);
+        self.inner.defer_merge()
+    }
+
+    /// 清除标志位，返回清除之前的标志字
+    pub fn clear_bits(&self, bits: u32) -> u32 {
+        self.inner.locked(|state| {
+            let previous = state.bits;
+            state.bits &= !bits;
+            previous
+        })
+    }
+
+    /// 等待标志位
+    ///
+    /// # 参数
+    /// - `mask`: 等待的位
+    /// - `mode`: 等待全部位（`WaitAll`）还是任意一位（`WaitAny`）
+    /// - `clear_on_exit`: 条件满足时是否清除 `mask` 中的位
+    /// - `timeout_ms`: 超时时间（毫秒），`None` 表示一直等待，`Some(0)` 表示不等待
+    ///
+    /// # 返回值
+    /// - `Ok(bits)`: 条件满足时的标志字（清除之前）
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::InvalidArgument)`: `mask` 为 0
+    /// - `Err(RtosError::TimerSlotsFull)`: 需要阻塞时没有可用的定时器
+    /// - `Err(RtosError::WaiterQueueFull)`: 等待的任务太多
+    /// - `Err(RtosError::WaitAborted)`: 阻塞期间任务被挂起
+    pub fn wait_bits(
+        &self,
+        mask: u32,
+        mode: WaitMode,
+        clear_on_exit: bool,
+        timeout_ms: Option<usize>,
+    ) -> Result<u32> {
+        self.wait_inner(0, mask, mode, clear_on_exit, timeout_ms)
+    }
+
+    /// 会合：设置自己的位，并等待 `wait_mask` 中的所有位都被设置
+    ///
+    /// 设置和等待在同一个临界区内完成，最后一个到达的参与者直接返回并唤醒其他参与者。
+    /// 条件满足时清除 `wait_mask`，事件组可以用于下一轮会合。
+    ///
+    /// # 返回值
+    /// 同 `wait_bits`
+    pub fn sync(&self, bits: u32, wait_mask: u32, timeout_ms: Option<usize>) -> Result<u32> {
+        self.wait_inner(bits, wait_mask, WaitMode::WaitAll, true, timeout_ms)
+    }
+
+    /// 获取正在等待的任务数
+    pub fn waiter_count(&self) -> usize {
+        self.inner.locked(|state| {
+            state
+                .requests
+                .iter()
+                .flatten()
+                .filter(|request| request.result.is_none())
+                .count()
+        })
+    }
+
+    /// 获取事件组的唯一标识
+    pub fn id(&self) -> usize {
+        Shared::as_ptr(&self.inner) as usize
+    }
+
+    fn wait_inner(
+        &self,
+        mut set: u32,
+        mask: u32,
+        mode: WaitMode,
+        clear_on_exit: bool,
+        timeout_ms: Option<usize>,
+    ) -> Result<u32> {
+        if mask == 0 {
+            return Err(RtosError::InvalidArgument);
+        }
+
+        let mut current = Scheduler::get_current_task();
+        let task = current.get_taskid();
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+        let timer = timeout_ms.filter(|ms| *ms > 0).map(|ms| {
+            Timer::new(ms).and_then(|mut timer| {
+                timer.start_for(current)?;
+                Ok(timer)
+            })
+        });
+        let event = Event::EventGroup(self.id());
+
+        loop {
+            // 先进入阻塞状态再登记：之后设置标志位的一方（包括中断）唤醒时任务一定处于阻塞状态
+            current.block(event);
+            let outcome = self.inner.locked(|state| {
+                if let Some(bits) = state.take_result(task) {
+                    return Some(Ok(bits));
+                }
+                if deadline.is_some_and(|deadline| Systick::get_current_time() >= deadline) {
+                    state.remove(task);
+                    return Some(Err(RtosError::Timeout));
+                }
+                if let Err(e) = state.register(BitsRequest {
+                    task,
+                    mask,
+                    mode,
+                    clear_on_exit,
+                    result: None,
+                }) {
+                    return Some(Err(e));
+                }
+                // 登记之后再设置自己的位，自己的请求和其他等待者一起检查
+                state.set(core::mem::take(&mut set), Some(task));
+                if let Some(bits) = state.take_result(task) {
+                    return Some(Ok(bits));
+                }
+                // 需要阻塞但没有定时器时不能无限等待
+                if let Some(Err(e)) = &timer {
+                    state.remove(task);
+                    return Some(Err(*e));
+                }
+                None
+            });
+
+            if let Some(outcome) = outcome {
+                if current.get_state() != TaskState::Running {
+                    current.run();
+                }
+                return outcome;
+            }
+
+            trigger_schedule();
+
+            if current.wait_aborted() {
+                self.inner.locked(|state| state.remove(task));
+                return Err(RtosError::WaitAborted);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for EventGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for EventGroup {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EventGroup")
+            .field("id", &self.id())
+            .field("bits", &format_args!("{:#010x}", self.get_bits()))
+            .field("waiters", &self.waiter_count())
+            .finish()
+    }
+}
+
+/// 不需要堆分配、可以放在 `static` 中的事件组
+///
+/// 通过 [`handle`](Self::handle) 获取 [`EventGroup`] 句柄后使用。
+pub struct StaticEventGroup {
+    inner: EventGroupInner,
+}
+
+impl StaticEventGroup {
+    /// 创建带初始标志字的静态事件组
+    pub const fn new(bits: u32) -> Self {
+        Self { inner: EventGroupInner::new(bits) }
+    }
+
+    /// 获取指向这个静态事件组的 [`EventGroup`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> EventGroup {
+        EventGroup { inner: Shared::from_static(&self.inner) }
+    }
+}
+
+impl core::fmt::Debug for StaticEventGroup {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticEventGroup")
+            .field("bits", &format_args!("{:#010x}", self.inner.locked(|state| state.bits)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    /// 让 `task` 像在 `wait_bits` 中一样阻塞并登记请求
+    fn park(group: &EventGroup, mut task: Task, mask: u32, mode: WaitMode, clear_on_exit: bool) {
+        task.block(Event::EventGroup(group.id()));
+        group
+            .inner
+            .locked(|state| {
+                state.register(BitsRequest {
+                    task: task.get_taskid(),
+                    mask,
+                    mode,
+                    clear_on_exit,
+                    result: None,
+                })
+            })
+            .unwrap();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_event_group_wait_any_and_all() {
+        kernel_init();
+        let any = Task::new("any", |_| {}).unwrap();
+        let all = Task::new("all", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+
+        let group = EventGroup::new();
+        park(&group, any, 0b011, WaitMode::WaitAny, false);
+        park(&group, all, 0b011, WaitMode::WaitAll, true);
+        assert_eq!(group.waiter_count(), 2);
+
+        // 一位满足 WaitAny，不满足 WaitAll
+        assert_eq!(group.set_bits(0b001), 0b001);
+        assert_eq!(any.get_state(), TaskState::Ready);
+        assert!(matches!(all.get_state(), TaskState::Blocked(_)));
+        assert_eq!(group.inner.locked(|state| state.take_result(any.get_taskid())), Some(0b001));
+
+        // 第二位满足 WaitAll，返回清除之前的标志字，随后清除
+        assert_eq!(group.set_bits(0b110), 0b100);
+        assert_eq!(all.get_state(), TaskState::Ready);
+        assert_eq!(group.inner.locked(|state| state.take_result(all.get_taskid())), Some(0b111));
+        assert_eq!(group.waiter_count(), 0);
+
+        assert_eq!(group.clear_bits(0b100), 0b100);
+        assert_eq!(group.get_bits(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_event_group_wait_bits_current_task() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let group = EventGroup::with_bits(0b101);
+        assert_eq!(group.wait_bits(0b100, WaitMode::WaitAll, true, None), Ok(0b101));
+        assert_eq!(group.get_bits(), 0b001);
+        assert_eq!(group.wait_bits(0b110, WaitMode::WaitAny, false, Some(0)), Err(RtosError::Timeout));
+        assert_eq!(group.wait_bits(0, WaitMode::WaitAny, false, None), Err(RtosError::InvalidArgument));
+        assert_eq!(group.waiter_count(), 0);
+        assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Running);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_event_group_sync_and_isr() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let first = Task::new("first", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 第一个参与者已经设置了自己的位并在等待
+        let group = EventGroup::new();
+        group.set_bits(0b001);
+        park(&group, first, 0b111, WaitMode::WaitAll, true);
+
+        // 中断中设置的位先暂存，任务对事件组的操作会先合并
+        {
+            let _state = group.inner.state.lock();
+            group.set_bits_from_isr(0b010).unwrap();
+        }
+        assert_eq!(group.get_bits(), 0b011);
+
+        // 最后一个参与者直接完成会合，所有参与者看到同一个标志字
+        assert_eq!(group.sync(0b100, 0b111, None), Ok(0b111));
+        assert_eq!(first.get_state(), TaskState::Ready);
+        assert_eq!(group.inner.locked(|state| state.take_result(first.get_taskid())), Some(0b111));
+        assert_eq!(group.get_bits(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_event_group_isr_defers_wakeup() {
+        use crate::kernel::time::timer::Timer;
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let waiter = Task::new("waiter", |_| {}).unwrap();
+        Scheduler::start();
+
+        let group = EventGroup::new();
+        park(&group, waiter, 0b100, WaitMode::WaitAny, false);
+
+        // 中断打断持有事件组锁的任务：只记录标志位，不等待锁，也不唤醒；
+        // 锁仍被占用时 SysTick 也不等待，由持有者释放锁时合并
+        group.inner.locked(|_| {
+            group.set_bits_from_isr(0b100).unwrap();
+            group.set_bits_from_isr(0b001).unwrap();
+            Timer::timer_check_and_send_event();
+            assert!(matches!(waiter.get_state(), TaskState::Blocked(_)));
+        });
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(group.inner.locked(|state| state.take_result(waiter.get_taskid())), Some(0b101));
+
+        // 没有任务持有锁时，下一次 SysTick 合并并唤醒
+        park(&group, waiter, 0b010, WaitMode::WaitAny, false);
+        group.set_bits_from_isr(0b010).unwrap();
+        assert!(matches!(waiter.get_state(), TaskState::Blocked(_)));
+        assert_eq!(group.inner.pending.load(Ordering::Acquire), 0b010);
+        Timer::timer_check_and_send_event();
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(group.inner.pending.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_static_event_group() {
+        use crate::kernel::time::timer::Timer;
+
+        static FLAGS: StaticEventGroup = StaticEventGroup::new(0b001);
+
+        kernel_init();
+        let waiter = Task::new("waiter", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let group = FLAGS.handle();
+        assert_eq!(group.get_bits(), 0b001);
+        assert_eq!(group.wait_bits(0b001, WaitMode::WaitAll, true, None), Ok(0b001));
+        assert_eq!(group.get_bits(), 0);
+
+        park(&group, waiter, 0b110, WaitMode::WaitAll, true);
+        assert_eq!(group.set_bits(0b010), 0b010);
+        assert!(matches!(waiter.get_state(), TaskState::Blocked(_)));
+
+        // 中断设置的最后一位由 SysTick 合并并唤醒
+        FLAGS.handle().set_bits_from_isr(0b100).unwrap();
+        Timer::timer_check_and_send_event();
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(group.inner.locked(|state| state.take_result(waiter.get_taskid())), Some(0b110));
+        assert_eq!(group.get_bits(), 0);
+    }
+}
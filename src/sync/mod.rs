@@ -4,7 +4,11 @@
 //!
 //! ## 特性
 //!
-//! - 基于 `Arc` 实现，可以在局部创建
+//! - 基于 `Arc` 实现，可以在局部创建（`new` 等构造函数需要 `alloc` feature）
+//! - `StaticMutex` / `StaticSignal` / `StaticSemaphore` / `StaticCondVar` 提供
+//!   `const fn` 构造，可以放在 `static` 中，不使用堆
+//! - 每个原语都有对应的 `Static*` 类型，`handle()` 返回可克隆的句柄，
+//!   关闭 `alloc` 时通过它使用同步原语
 //! - 支持通过闭包捕获传递给任务
 //! - API 设计与 `std` 风格一致
 //! - 同时支持同步和异步等待
@@ -37,6 +41,11 @@ pub mod mutex;
 pub mod semaphore;
 pub mod condvar;
 pub mod event;
+pub mod wait_queue;
+pub mod wait_any;
+pub mod event_group;
+pub mod barrier;
+pub mod once;
 
 // ============================================================================
 // 主要类型导出
@@ -48,9 +57,11 @@ pub use signal::{
     SignalSender,
     SignalReceiver,
     OwnedSignal,
+    StaticSignal,
     SignalFuture,
-    signal_pair,
 };
+#[cfg(feature = "alloc")]
+pub use signal::signal_pair;
 
 // Mutex
 pub use mutex::{
@@ -59,6 +70,12 @@ pub use mutex::{
     OwnedMutexGuard,
     MappedMutexGuard,
     MutexLockFuture,
+    RecursiveMutex,
+    RecursiveMutexGuard,
+    StaticMutex,
+    StaticMutexGuard,
+    StaticRecursiveMutex,
+    StaticRwLock,
     RwLock,
     RwLockReadGuard,
     RwLockWriteGuard,
@@ -69,6 +86,7 @@ pub use semaphore::{
     Semaphore,
     SemaphorePermit,
     OwnedSemaphorePermit,
+    StaticSemaphore,
     SemaphoreAcquireFuture,
 };
 
@@ -76,22 +94,41 @@ pub use semaphore::{
 pub use condvar::{
     CondVar,
     CondVarFuture,
+    StaticCondVar,
 };
 
 // Event (内部使用)
 pub use event::Event;
 
+// WaitQueue
+pub use wait_queue::{WaitQueue, WaitOrder};
+
+// wait_any
+pub use wait_any::{wait_any, Waitable};
+
+// EventGroup
+pub use event_group::{EventGroup, StaticEventGroup, WaitMode};
+
+// Barrier
+pub use barrier::{Barrier, BarrierWaitResult, BarrierFuture, StaticBarrier};
+
+// OnceCell / Lazy
+pub use once::{OnceCell, Lazy, OnceCellFuture, StaticOnceCell};
+
 // ============================================================================
 // 便捷函数导出
 // ============================================================================
 
 /// 创建信号量配对
+#[cfg(feature = "alloc")]
 pub use signal::signal_pair as new_signal_pair;
 
 /// 创建互斥锁和条件变量配对
+#[cfg(feature = "alloc")]
 pub use condvar::mutex_condvar_pair;
 
 /// 创建二值信号量
+#[cfg(feature = "alloc")]
 pub use semaphore::binary_semaphore;
 
 // ============================================================================
@@ -108,9 +145,16 @@ pub mod prelude {
     pub use super::Semaphore;
     pub use super::CondVar;
     pub use super::RwLock;
+    pub use super::EventGroup;
+    pub use super::Barrier;
+    pub use super::OnceCell;
+    pub use super::Lazy;
     
     // 便捷函数
+    #[cfg(feature = "alloc")]
     pub use super::signal_pair;
+    #[cfg(feature = "alloc")]
     pub use super::mutex_condvar_pair;
+    #[cfg(feature = "alloc")]
     pub use super::binary_semaphore;
 }
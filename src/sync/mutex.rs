@@ -5,9 +5,16 @@
 //!
 //! ## 设计思路
 //!
-//! 使用 `Arc<MutexInner<T>>` 封装数据和锁状态，
+//! 使用 `Shared<MutexInner<T>>` 封装数据和锁状态，
 //! 可以像 `std::sync::Mutex` 一样在局部创建并传递给任务。
 //!
+//! ## 健壮锁
+//!
+//! 持有 `Mutex` 或 `RwLock` 写锁的任务结束或被删除时，锁进入 OwnerDied 状态：
+//! 锁保持占用，加锁返回 `Err(RtosError::OwnerDied)`。
+//! 调用 `recover()` 接管锁并修复数据，再用 `mark_consistent` 标记后锁恢复正常；
+//! 没有标记就释放时锁被永久毒化，之后加锁返回 `Err(RtosError::MutexPoisoned)`。
+//!
 //! ## 使用示例
 //!
 //! ```rust,no_run
@@ -38,20 +45,25 @@
 //! }
 //! ```
 
-use crate::compat::{Arc, VecDeque};
+use crate::compat::Shared;
 use crate::kernel::scheduler::Scheduler;
-use crate::kernel::task::TaskState;
+use crate::kernel::task::{HeldLock, Priority, Task};
 use crate::kernel::time::systick::Systick;
-use crate::hal::trigger_schedule;
 use crate::error::{Result, RtosError};
-use crate::sync::signal::WaiterList;
+use crate::sync::wait_queue::{WaitOrder, WaitQueue, WakerQueue};
 use core::cell::UnsafeCell;
 use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
-use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use core::task::Waker;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use spin::Mutex as SpinMutex;
 
+/// 数据一致
+const CONSISTENT: u8 = 0;
+/// 持有者结束时没有释放锁，等待其他任务调用 `recover()` 接管
+const OWNER_DIED: u8 = 1;
+/// 已被 `recover()` 接管，正在修复数据
+const RECOVERING: u8 = 2;
+
 /// 互斥锁内部状态
 struct MutexInner<T> {
     /// 被保护的数据
@@ -60,16 +72,18 @@ struct MutexInner<T> {
     locked: AtomicBool,
     /// 当前持有者的任务 ID（usize::MAX 表示无持有者）
     owner: AtomicUsize,
-    /// 持有者的原始优先级（用于优先级继承恢复）
-    owner_original_priority: SpinMutex<Option<crate::kernel::task::Priority>>,
-    /// 同步等待者列表
-    waiters: SpinMutex<WaiterList>,
+    /// 同步等待队列（按优先级唤醒）
+    waiters: WaitQueue,
     /// 异步等待者列表
-    async_waiters: SpinMutex<VecDeque<Waker>>,
-    /// 是否已被毒化（持有锁的任务 panic 了）
+    async_waiters: SpinMutex<WakerQueue>,
+    /// 是否已被毒化（持有锁的任务 panic 了，或接管后没有标记为一致）
     poisoned: AtomicBool,
+    /// 健壮锁状态（`CONSISTENT` / `OWNER_DIED` / `RECOVERING`）
+    robust: AtomicU8,
     /// 是否启用优先级继承
     priority_inheritance: bool,
+    /// 优先级天花板（启用天花板协议时）
+    priority_ceiling: Option<Priority>,
 }
 
 // Safety: MutexInner 通过锁机制保证线程安全
@@ -77,33 +91,317 @@ unsafe impl<T: Send> Send for MutexInner<T> {}
 unsafe impl<T: Send> Sync for MutexInner<T> {}
 
 impl<T> MutexInner<T> {
-    fn new(data: T) -> Self {
+    const fn new(data: T) -> Self {
         Self {
             data: UnsafeCell::new(data),
             locked: AtomicBool::new(false),
             owner: AtomicUsize::new(usize::MAX),
-            owner_original_priority: SpinMutex::new(None),
-            waiters: SpinMutex::new(WaiterList::new()),
-            async_waiters: SpinMutex::new(VecDeque::new()),
+            waiters: WaitQueue::with_order(WaitOrder::Priority),
+            async_waiters: SpinMutex::new(WakerQueue::new()),
             poisoned: AtomicBool::new(false),
+            robust: AtomicU8::new(CONSISTENT),
             priority_inheritance: false,
+            priority_ceiling: None,
         }
     }
 
-    fn new_with_priority_inheritance(data: T) -> Self {
-        Self {
-            data: UnsafeCell::new(data),
-            locked: AtomicBool::new(false),
-            owner: AtomicUsize::new(usize::MAX),
-            owner_original_priority: SpinMutex::new(None),
-            waiters: SpinMutex::new(WaiterList::new()),
-            async_waiters: SpinMutex::new(VecDeque::new()),
-            poisoned: AtomicBool::new(false),
-            priority_inheritance: true,
+    const fn new_with_priority_inheritance(data: T) -> Self {
+        let mut inner = Self::new(data);
+        inner.priority_inheritance = true;
+        inner
+    }
+
+    const fn new_with_priority_ceiling(data: T, ceiling: Priority) -> Self {
+        let mut inner = Self::new(data);
+        inner.priority_ceiling = Some(ceiling);
+        inner
+    }
+
+    /// 天花板协议：基础优先级高于天花板的任务不能获取锁
+    fn check_ceiling(&self) -> Result<()> {
+        match self.priority_ceiling {
+            Some(ceiling) if Scheduler::get_current_task().get_base_priority() > ceiling => {
+                Err(RtosError::PriorityCeilingViolated)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 锁在任务上的登记记录
+    fn held_lock(&self) -> HeldLock {
+        HeldLock {
+            addr: self as *const Self as usize,
+            release: release_on_owner_death::<T>,
+            boost: priority_boost::<T>,
+            owner: lock_owner::<T>,
+        }
+    }
+
+    /// 锁要求持有者至少具有的优先级
+    ///
+    /// - 天花板协议：天花板优先级
+    /// - 优先级继承：仍在等待的任务中的最高优先级
+    fn boost(&self) -> Option<Priority> {
+        if self.priority_ceiling.is_some() {
+            return self.priority_ceiling;
+        }
+        if self.priority_inheritance {
+            return self.waiters.highest_priority();
+        }
+        None
+    }
+
+    /// 记录持有者
+    /// 
+    /// 锁会登记到持有任务上，持有任务结束或被删除时锁进入 OwnerDied 状态。
+    /// 登记之后需要调用 `update_priority` 使天花板和继承的优先级生效。
+    /// 当前任务持有的锁过多无法登记时放弃本次获取：接管的锁回到 OwnerDied 状态，
+    /// 其余情况释放锁，返回 `TooManyHeldLocks`。
+    fn record_owner(&self) -> Result<()> {
+        let current = Scheduler::get_current_task();
+        self.owner.store(current.get_taskid(), Ordering::Release);
+        current.track_lock(self.held_lock()).inspect_err(|_| {
+            self.owner.store(usize::MAX, Ordering::Release);
+            if self
+                .robust
+                .compare_exchange(RECOVERING, OWNER_DIED, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+            {
+                self.locked.store(false, Ordering::Release);
+            }
+        })
+    }
+
+    /// 成功获取锁后调用：记录持有者并重新计算持有者的优先级
+    ///
+    /// 天花板协议下持有者立即提升到天花板优先级；
+    /// 优先级继承下持有者继承仍在等待的任务中的最高优先级。
+    fn on_acquired(&self) -> Result<()> {
+        self.record_owner()?;
+        Scheduler::get_current_task().update_priority();
+        Ok(())
+    }
+
+    /// 尝试获取锁（不记录持有者）
+    ///
+    /// 持有者结束后锁保持占用，`recover` 为 `true` 时接管这样的锁，否则返回 `OwnerDied`。
+    fn take(&self, recover: bool) -> Result<bool> {
+        let acquired = self.locked.compare_exchange(
+            false,
+            true,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok();
+
+        // 毒化是最终状态，毒化时已经唤醒所有等待者，获取到的锁直接放回
+        if self.poisoned.load(Ordering::Acquire) {
+            if acquired {
+                self.locked.store(false, Ordering::Release);
+            }
+            return Err(RtosError::MutexPoisoned);
+        }
+        if acquired {
+            return Ok(true);
+        }
+
+        match self.robust.load(Ordering::Acquire) {
+            OWNER_DIED if recover => Ok(self
+                .robust
+                .compare_exchange(OWNER_DIED, RECOVERING, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()),
+            OWNER_DIED => Err(RtosError::OwnerDied),
+            _ => Ok(false),
+        }
+    }
+
+    /// 尝试获取锁，成功时记录持有者
+    fn try_acquire(&self, recover: bool) -> Result<bool> {
+        let acquired = self.take(recover)?;
+        if acquired {
+            self.wake_if_abandoned(self.on_acquired())?;
+        }
+        Ok(acquired)
+    }
+
+    /// 获取锁，锁被占用时阻塞
+    ///
+    /// 在等待队列锁内尝试获取锁，失败时加入等待队列，被唤醒后重试。
+    /// 阻塞之后沿等待链提升持有者的优先级，超时离开队列后重新计算。
+    fn acquire(&self, timeout_ms: Option<usize>, recover: bool) -> Result<()> {
+        self.check_ceiling()?;
+
+        let current = Scheduler::get_current_task();
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+
+        loop {
+            let remaining = deadline.map(|deadline| deadline.saturating_sub(Systick::get_current_time()));
+            let waited = self.waiters.wait_if_then(
+                remaining,
+                || {
+                    // 持有队列锁时不能重新计算优先级（需要读取本队列）
+                    let acquired = self.take(recover)?;
+                    if acquired {
+                        self.record_owner()?;
+                    }
+                    Ok(!acquired)
+                },
+                || self.begin_wait(current),
+            );
+            if waited.as_ref().is_ok_and(|blocked| !blocked) {
+                current.update_priority();
+                return Ok(());
+            }
+            self.end_wait(current);
+            self.wake_if_abandoned(waited)?;
+
+            // 被唤醒后重新尝试获取锁（毒化和持有者结束在下一次尝试时检查）
+        }
+    }
+
+    /// 任务阻塞在锁上之后调用：记录等待关系，沿等待链传递优先级
+    fn begin_wait(&self, waiter: Task) {
+        waiter.set_blocked_on(Some(self.held_lock()));
+        if let Some(owner) = self.owner() {
+            Task::propagate_priority(owner);
+        }
+    }
+
+    /// 任务结束等待后调用：清除等待关系，重新计算持有者的优先级
+    fn end_wait(&self, waiter: Task) {
+        waiter.set_blocked_on(None);
+        if let Some(owner) = self.owner() {
+            Task::propagate_priority(owner);
+        }
+    }
+
+    /// 当前持有者
+    fn owner(&self) -> Option<Task> {
+        match self.owner.load(Ordering::Acquire) {
+            usize::MAX => None,
+            owner_id => Some(Task::from_id(owner_id)),
+        }
+    }
+
+    /// 持有者已经结束、数据还没有标记为一致
+    fn is_owner_died(&self) -> bool {
+        self.robust.load(Ordering::Acquire) != CONSISTENT
+    }
+
+    /// 是否已被毒化（包括持有者结束后还没有修复的情况）
+    fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Acquire) || self.is_owner_died()
+    }
+
+    /// 释放锁，持有者回到不含本锁的有效优先级
+    ///
+    /// 接管后没有标记为一致就释放时锁被永久毒化。
+    /// 持有者是当前任务且优先级降低时请求抢占，被挡住的更高优先级任务在随后的任务切换中运行。
+    fn release(&self) {
+        let owner = self.owner();
+        if self
+            .robust
+            .compare_exchange(RECOVERING, CONSISTENT, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            self.poisoned.store(true, Ordering::Release);
+        }
+        self.unlock_and_wake();
+
+        if let Some(owner) = owner
+            && owner.update_priority()
+            && owner == Scheduler::get_current_task()
+        {
+            Scheduler::request_preempt();
+        }
+    }
+
+    /// 释放锁并唤醒一个等待者（锁被毒化时唤醒所有等待者）
+    fn unlock_and_wake(&self) {
+        if let Some(owner) = self.owner() {
+            owner.untrack_lock(self as *const Self as usize);
+        }
+
+        // 清除持有者
+        self.owner.store(usize::MAX, Ordering::Release);
+        
+        // 释放锁
+        self.locked.store(false, Ordering::Release);
+
+        if self.poisoned.load(Ordering::Acquire) {
+            self.wake_all();
+            return;
+        }
+
+        // 首先尝试唤醒同步等待者（优先级最高的先被唤醒），然后尝试唤醒异步等待者
+        if self.waiters.wake_one().is_none() {
+            let waker = {
+                let mut async_waiters = self.async_waiters.lock();
+                async_waiters.pop_front()
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// 唤醒所有同步和异步等待者
+    fn wake_all(&self) {
+        self.waiters.wake_all();
+        let async_wakers = core::mem::take(&mut *self.async_waiters.lock());
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 持有者结束时没有释放锁
+    ///
+    /// 锁保持占用，所有等待者被唤醒并得到 `OwnerDied`，直到有任务调用 `recover()` 接管。
+    /// 接管的任务在修复期间结束时，锁重新回到 OwnerDied 状态。
+    fn mark_owner_died(&self) {
+        self.robust.store(OWNER_DIED, Ordering::Release);
+        self.owner.store(usize::MAX, Ordering::Release);
+        self.wake_all();
+    }
+
+    /// 因持有的锁过多放弃获取后唤醒所有等待者
+    ///
+    /// 锁被短暂占用期间可能有任务阻塞，放回锁之后由它们重新竞争。不能在等待队列锁内调用。
+    fn wake_if_abandoned<R>(&self, result: Result<R>) -> Result<R> {
+        if matches!(result, Err(RtosError::TooManyHeldLocks)) {
+            self.wake_all();
         }
+        result
     }
 }
 
+/// 锁要求持有者至少具有的优先级
+///
+/// # Safety
+/// `addr` 必须指向仍然有效的 `MutexInner<T>`
+unsafe fn priority_boost<T>(addr: usize) -> Option<Priority> {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const MutexInner<T>) }.boost()
+}
+
+/// 锁当前的持有者
+///
+/// # Safety
+/// `addr` 必须指向仍然有效的 `MutexInner<T>`
+unsafe fn lock_owner<T>(addr: usize) -> Option<Task> {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const MutexInner<T>) }.owner()
+}
+
+/// 持有者结束或被删除时调用：标记为 OwnerDied 并唤醒等待者
+/// 
+/// # Safety
+/// `addr` 必须指向仍然有效的 `MutexInner<T>`
+unsafe fn release_on_owner_death<T>(addr: usize) {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const MutexInner<T>) }.mark_owner_died();
+}
+
 /// 可克隆、可传递的互斥锁
 ///
 /// 类似于 `std::sync::Mutex`，但可以 clone 后 move 到不同任务中。
@@ -132,13 +430,13 @@ impl<T> MutexInner<T> {
 /// }
 /// ```
 pub struct Mutex<T> {
-    inner: Arc<MutexInner<T>>,
+    inner: Shared<MutexInner<T>>,
 }
 
 impl<T> Clone for Mutex<T> {
     fn clone(&self) -> Self {
         Self {
-            inner: Arc::clone(&self.inner),
+            inner: Shared::clone(&self.inner),
         }
     }
 }
@@ -155,9 +453,10 @@ impl<T> Mutex<T> {
     ///
     /// let mutex = Mutex::new(42);
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn new(data: T) -> Self {
         Self {
-            inner: Arc::new(MutexInner::new(data)),
+            inner: Shared::new(MutexInner::new(data)),
         }
     }
 
@@ -176,9 +475,36 @@ impl<T> Mutex<T> {
     /// // 创建带优先级继承的互斥锁
     /// let mutex = Mutex::with_priority_inheritance(42);
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn with_priority_inheritance(data: T) -> Self {
         Self {
-            inner: Arc::new(MutexInner::new_with_priority_inheritance(data)),
+            inner: Shared::new(MutexInner::new_with_priority_inheritance(data)),
+        }
+    }
+
+    /// 创建使用优先级天花板协议的互斥锁
+    ///
+    /// 立即天花板协议（OSEK/AUTOSAR 风格）：任何任务获取锁后立即以天花板优先级运行，
+    /// 释放锁时恢复到获取前的优先级。天花板应设为所有使用该锁的任务中的最高优先级，
+    /// 这样持有锁期间不会被其他使用该锁的任务抢占，阻塞时间有上界，
+    /// 使用天花板锁的任务之间也不会死锁。
+    ///
+    /// # 参数
+    /// - `data`: 要保护的数据
+    /// - `ceiling`: 天花板优先级
+    ///
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::sync::Mutex;
+    /// use neon_rtos2::kernel::task::Priority;
+    ///
+    /// // 使用该锁的任务中最高优先级为 High
+    /// let mutex = Mutex::with_priority_ceiling(42, Priority::High);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_priority_ceiling(data: T, ceiling: Priority) -> Self {
+        Self {
+            inner: Shared::new(MutexInner::new_with_priority_ceiling(data, ceiling)),
         }
     }
 
@@ -187,6 +513,11 @@ impl<T> Mutex<T> {
         self.inner.priority_inheritance
     }
 
+    /// 获取优先级天花板（未启用天花板协议时返回 `None`）
+    pub fn priority_ceiling(&self) -> Option<Priority> {
+        self.inner.priority_ceiling
+    }
+
     /// 获取锁
     ///
     /// 如果锁已被其他任务持有，当前任务会被阻塞直到锁可用。
@@ -198,6 +529,9 @@ impl<T> Mutex<T> {
     /// # 返回值
     /// - `Ok(MutexGuard)`: 成功获取锁
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::PriorityCeilingViolated)`: 当前任务优先级高于天花板
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     ///
     /// # 示例
     /// ```rust,no_run
@@ -210,75 +544,8 @@ impl<T> Mutex<T> {
     /// } // 自动释放锁
     /// ```
     pub fn lock(&self) -> Result<MutexGuard<'_, T>> {
-        // 检查是否已毒化
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
-        loop {
-            // 尝试获取锁
-            if self.inner.locked.compare_exchange(
-                false,
-                true,
-                Ordering::Acquire,
-                Ordering::Relaxed,
-            ).is_ok() {
-                // 成功获取锁
-                let mut current = Scheduler::get_current_task();
-                let task_id = current.get_taskid();
-                self.inner.owner.store(task_id, Ordering::Release);
-                
-                // 如果启用优先级继承，保存原始优先级
-                if self.inner.priority_inheritance {
-                    let mut orig_priority = self.inner.owner_original_priority.lock();
-                    *orig_priority = Some(current.get_priority());
-                }
-                
-                return Ok(MutexGuard { mutex: self, _marker: PhantomData });
-            }
-
-            // 锁被占用，加入等待队列并阻塞
-            let current = Scheduler::get_current_task();
-            let task_id = current.get_taskid();
-            let current_priority = current.get_priority();
-
-            {
-                let mut waiters = self.inner.waiters.lock();
-                if !waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            // 优先级继承：提升持有者的优先级
-            if self.inner.priority_inheritance {
-                let owner_id = self.inner.owner.load(Ordering::Acquire);
-                if owner_id != usize::MAX {
-                    crate::kernel::task::Task::for_each(|mut task, id| {
-                        if id == owner_id {
-                            let owner_priority = task.get_priority();
-                            // 如果等待者优先级更高，提升持有者优先级
-                            if current_priority > owner_priority {
-                                task.set_priority(current_priority);
-                            }
-                        }
-                    });
-                }
-            }
-
-            // 使用 Arc 的地址作为唯一标识
-            let mutex_id = Arc::as_ptr(&self.inner) as usize;
-            
-            // 阻塞当前任务
-            Scheduler::get_current_task().block(crate::sync::event::Event::Mutex(mutex_id));
-            trigger_schedule();
-
-            // 被唤醒后检查是否毒化
-            if self.inner.poisoned.load(Ordering::Acquire) {
-                return Err(RtosError::MutexPoisoned);
-            }
-
-            // 被唤醒后重新尝试获取锁
-        }
+        self.inner.acquire(None, false)?;
+        Ok(MutexGuard { mutex: self, _marker: PhantomData })
     }
 
     /// 尝试获取锁（非阻塞）
@@ -290,6 +557,9 @@ impl<T> Mutex<T> {
     /// - `Ok(MutexGuard)`: 成功获取锁
     /// - `Err(RtosError::WouldBlock)`: 锁被占用
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::PriorityCeilingViolated)`: 当前任务优先级高于天花板
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     ///
     /// # 示例
     /// ```rust,no_run
@@ -302,20 +572,10 @@ impl<T> Mutex<T> {
     /// }
     /// ```
     pub fn try_lock(&self) -> Result<MutexGuard<'_, T>> {
-        // 检查是否已毒化
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
+        self.inner.check_ceiling()?;
 
         // 尝试获取锁
-        if self.inner.locked.compare_exchange(
-            false,
-            true,
-            Ordering::Acquire,
-            Ordering::Relaxed,
-        ).is_ok() {
-            let task_id = Scheduler::get_current_task().get_taskid();
-            self.inner.owner.store(task_id, Ordering::Release);
+        if self.inner.try_acquire(false)? {
             Ok(MutexGuard { mutex: self, _marker: PhantomData })
         } else {
             Err(RtosError::WouldBlock)
@@ -334,6 +594,9 @@ impl<T> Mutex<T> {
     /// - `Ok(MutexGuard)`: 成功获取锁
     /// - `Err(RtosError::Timeout)`: 等待超时
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::PriorityCeilingViolated)`: 当前任务优先级高于天花板
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     ///
     /// # 示例
     /// ```rust,no_run
@@ -347,65 +610,8 @@ impl<T> Mutex<T> {
     /// }
     /// ```
     pub fn lock_timeout(&self, timeout_ms: usize) -> Result<MutexGuard<'_, T>> {
-        // 检查是否已毒化
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
-        let deadline = Systick::get_current_time() + timeout_ms;
-
-        loop {
-            // 尝试获取锁
-            if self.inner.locked.compare_exchange(
-                false,
-                true,
-                Ordering::Acquire,
-                Ordering::Relaxed,
-            ).is_ok() {
-                // 成功获取锁
-                let task_id = Scheduler::get_current_task().get_taskid();
-                self.inner.owner.store(task_id, Ordering::Release);
-                return Ok(MutexGuard { mutex: self, _marker: PhantomData });
-            }
-
-            // 检查是否超时
-            if Systick::get_current_time() >= deadline {
-                return Err(RtosError::Timeout);
-            }
-
-            // 锁被占用，加入等待队列并阻塞
-            let current = Scheduler::get_current_task();
-            let task_id = current.get_taskid();
-
-            {
-                let mut waiters = self.inner.waiters.lock();
-                if !waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            // 使用 Arc 的地址作为唯一标识
-            let mutex_id = Arc::as_ptr(&self.inner) as usize;
-            
-            // 阻塞当前任务
-            Scheduler::get_current_task().block(crate::sync::event::Event::Mutex(mutex_id));
-            trigger_schedule();
-
-            // 被唤醒后检查是否毒化
-            if self.inner.poisoned.load(Ordering::Acquire) {
-                return Err(RtosError::MutexPoisoned);
-            }
-
-            // 检查是否超时
-            if Systick::get_current_time() >= deadline {
-                // 从等待队列中移除自己
-                let mut waiters = self.inner.waiters.lock();
-                waiters.remove(task_id);
-                return Err(RtosError::Timeout);
-            }
-
-            // 被唤醒后重新尝试获取锁
-        }
+        self.inner.acquire(Some(timeout_ms), false)?;
+        Ok(MutexGuard { mutex: self, _marker: PhantomData })
     }
 
     /// 带超时的尝试获取锁（轮询模式）
@@ -420,6 +626,8 @@ impl<T> Mutex<T> {
     /// - `Ok(MutexGuard)`: 成功获取锁
     /// - `Err(RtosError::Timeout)`: 超时
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     pub fn try_lock_timeout(&self, timeout_ms: usize) -> Result<MutexGuard<'_, T>> {
         let deadline = Systick::get_current_time() + timeout_ms;
         
@@ -474,82 +682,69 @@ impl<T> Mutex<T> {
     }
 
     /// 检查锁是否被占用
+    ///
+    /// 持有者结束后锁虽然保持占用，但不属于任何任务，返回 `false`。
     pub fn is_locked(&self) -> bool {
         self.inner.locked.load(Ordering::Acquire)
+            && self.inner.robust.load(Ordering::Acquire) != OWNER_DIED
     }
 
-    /// 检查锁是否已被毒化
+    /// 检查锁是否已被毒化（包括持有者结束后还没有修复的情况）
     pub fn is_poisoned(&self) -> bool {
-        self.inner.poisoned.load(Ordering::Acquire)
+        self.inner.is_poisoned()
+    }
+
+    /// 检查持有者是否结束时没有释放锁，且数据还没有标记为一致
+    pub fn is_owner_died(&self) -> bool {
+        self.inner.is_owner_died()
+    }
+
+    /// 获取锁，持有者已经结束时接管锁以修复数据
+    ///
+    /// 持有者结束或被删除时锁进入 OwnerDied 状态，`lock()` 等方法返回
+    /// `Err(RtosError::OwnerDied)`。调用本方法接管锁，修复数据后调用
+    /// `MutexGuard::mark_consistent` 标记，锁恢复正常；没有标记就释放时锁被永久毒化。
+    ///
+    /// 锁处于正常状态时与 `lock()` 相同。
+    ///
+    /// # 返回值
+    /// - `Ok(MutexGuard)`: 成功获取锁
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化，不能恢复
+    /// - `Err(RtosError::PriorityCeilingViolated)`: 当前任务优先级高于天花板
+    ///
+    /// # 示例
+    /// ```rust,no_run
+    /// use neon_rtos2::error::RtosError;
+    /// use neon_rtos2::sync::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new([0u8; 4]);
+    /// let guard = match mutex.lock() {
+    ///     Err(RtosError::OwnerDied) => {
+    ///         let mut guard = mutex.recover().unwrap();
+    ///         *guard = [0; 4]; // 恢复到已知状态
+    ///         MutexGuard::mark_consistent(&guard);
+    ///         guard
+    ///     }
+    ///     result => result.unwrap(),
+    /// };
+    /// ```
+    pub fn recover(&self) -> Result<MutexGuard<'_, T>> {
+        self.inner.acquire(None, true)?;
+        Ok(MutexGuard { mutex: self, _marker: PhantomData })
     }
 
     /// 获取互斥锁的唯一标识（用于调试）
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
     }
 
     /// 内部方法：释放锁
     fn unlock(&self) {
-        // 优先级继承：恢复原始优先级
-        if self.inner.priority_inheritance {
-            let owner_id = self.inner.owner.load(Ordering::Acquire);
-            if owner_id != usize::MAX {
-                let original_priority = {
-                    let mut orig = self.inner.owner_original_priority.lock();
-                    orig.take()
-                };
-                
-                if let Some(priority) = original_priority {
-                    crate::kernel::task::Task::for_each(|mut task, id| {
-                        if id == owner_id {
-                            task.set_priority(priority);
-                        }
-                    });
-                }
-            }
-        }
-
-        // 清除持有者
-        self.inner.owner.store(usize::MAX, Ordering::Release);
-        
-        // 释放锁
-        self.inner.locked.store(false, Ordering::Release);
-
-        // 首先尝试唤醒同步等待者
-        let task_id = {
-            let mut waiters = self.inner.waiters.lock();
-            waiters.pop_front()
-        };
-
-        if let Some(task_id) = task_id {
-            crate::kernel::task::Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-            return;
-        }
-
-        // 然后尝试唤醒异步等待者
-        let waker = {
-            let mut async_waiters = self.inner.async_waiters.lock();
-            async_waiters.pop_front()
-        };
-
-        if let Some(waker) = waker {
-            waker.wake();
-        }
-    }
-
-    /// 内部方法：标记为毒化并释放锁
-    fn poison_and_unlock(&self) {
-        self.inner.poisoned.store(true, Ordering::Release);
-        self.unlock();
+        self.inner.release();
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Default> Default for Mutex<T> {
     fn default() -> Self {
         Self::new(T::default())
@@ -562,6 +757,7 @@ impl<T: core::fmt::Debug> core::fmt::Debug for Mutex<T> {
         d.field("id", &self.id());
         d.field("locked", &self.is_locked());
         d.field("poisoned", &self.is_poisoned());
+        d.field("owner_died", &self.is_owner_died());
         d.field("waiters", &self.waiter_count());
         
         // 尝试显示数据（如果能获取锁）
@@ -632,6 +828,19 @@ impl<'a, T> MutexGuard<'a, T> {
         core::mem::forget(guard);
         mutex
     }
+
+    /// 标记被保护的数据已经修复
+    ///
+    /// 通过 `Mutex::recover()` 接管持有者已经结束的锁并修复数据后调用，锁恢复正常。
+    /// 没有标记就释放锁时锁被永久毒化。锁处于正常状态时没有作用。
+    pub fn mark_consistent(guard: &Self) {
+        let _ = guard.mutex.inner.robust.compare_exchange(
+            RECOVERING,
+            CONSISTENT,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
 }
 
 // 显式实现 !Send - MutexGuard 不能被发送到其他任务
@@ -684,7 +893,7 @@ impl<T: core::fmt::Display> core::fmt::Display for MutexGuard<'_, T> {
 
 /// 拥有所有权的互斥锁守卫
 ///
-/// 与 `MutexGuard` 不同，`OwnedMutexGuard` 持有 `Arc<MutexInner<T>>` 的所有权，
+/// 与 `MutexGuard` 不同，`OwnedMutexGuard` 持有 `Shared<MutexInner<T>>` 的所有权，
 /// 因此可以被 move 到其他任务中（虽然通常不建议这样做）。
 ///
 /// 主要用于需要将锁守卫存储在结构体中或跨 await 点持有的场景。
@@ -701,7 +910,7 @@ impl<T: core::fmt::Display> core::fmt::Display for MutexGuard<'_, T> {
 /// drop(guard); // 显式释放
 /// ```
 pub struct OwnedMutexGuard<T> {
-    mutex: Arc<MutexInner<T>>,
+    mutex: Shared<MutexInner<T>>,
 }
 
 // OwnedMutexGuard 可以 Send（如果 T: Send）
@@ -727,56 +936,7 @@ impl<T> DerefMut for OwnedMutexGuard<T> {
 
 impl<T> Drop for OwnedMutexGuard<T> {
     fn drop(&mut self) {
-        // 优先级继承：恢复原始优先级
-        if self.mutex.priority_inheritance {
-            let owner_id = self.mutex.owner.load(Ordering::Acquire);
-            if owner_id != usize::MAX {
-                let original_priority = {
-                    let mut orig = self.mutex.owner_original_priority.lock();
-                    orig.take()
-                };
-                
-                if let Some(priority) = original_priority {
-                    crate::kernel::task::Task::for_each(|mut task, id| {
-                        if id == owner_id {
-                            task.set_priority(priority);
-                        }
-                    });
-                }
-            }
-        }
-
-        // 清除持有者
-        self.mutex.owner.store(usize::MAX, Ordering::Release);
-        
-        // 释放锁
-        self.mutex.locked.store(false, Ordering::Release);
-
-        // 唤醒等待者
-        let task_id = {
-            let mut waiters = self.mutex.waiters.lock();
-            waiters.pop_front()
-        };
-
-        if let Some(task_id) = task_id {
-            crate::kernel::task::Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-            return;
-        }
-
-        let waker = {
-            let mut async_waiters = self.mutex.async_waiters.lock();
-            async_waiters.pop_front()
-        };
-
-        if let Some(waker) = waker {
-            waker.wake();
-        }
+        self.mutex.release();
     }
 }
 
@@ -812,91 +972,20 @@ impl<T> Mutex<T> {
     /// let guard = mutex.lock_owned_guard().unwrap();
     /// // guard 可以被存储在结构体中
     /// ```
-
-    /// 从 Mutex 获取拥有所有权的锁守卫
     pub fn lock_owned_guard(&self) -> Result<OwnedMutexGuard<T>> {
-        // 检查是否已毒化
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
-        loop {
-            // 尝试获取锁
-            if self.inner.locked.compare_exchange(
-                false,
-                true,
-                Ordering::Acquire,
-                Ordering::Relaxed,
-            ).is_ok() {
-                // 成功获取锁
-                let current = Scheduler::get_current_task();
-                let task_id = current.get_taskid();
-                self.inner.owner.store(task_id, Ordering::Release);
-                
-                // 如果启用优先级继承，保存原始优先级
-                if self.inner.priority_inheritance {
-                    let mut orig_priority = self.inner.owner_original_priority.lock();
-                    *orig_priority = Some(current.get_priority());
-                }
-                
-                return Ok(OwnedMutexGuard { 
-                    mutex: Arc::clone(&self.inner),
-                });
-            }
+        self.inner.acquire(None, false)?;
+        Ok(OwnedMutexGuard {
+            mutex: Shared::clone(&self.inner),
+        })
+    }
 
-            // 锁被占用，加入等待队列并阻塞
-            let current = Scheduler::get_current_task();
-            let task_id = current.get_taskid();
-            let current_priority = current.get_priority();
+    /// 尝试获取拥有所有权的锁守卫���非阻塞）
+    pub fn try_lock_owned(&self) -> Result<OwnedMutexGuard<T>> {
+        self.inner.check_ceiling()?;
 
-            {
-                let mut waiters = self.inner.waiters.lock();
-                if !waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            // 优先级继承
-            if self.inner.priority_inheritance {
-                let owner_id = self.inner.owner.load(Ordering::Acquire);
-                if owner_id != usize::MAX {
-                    crate::kernel::task::Task::for_each(|mut task, id| {
-                        if id == owner_id {
-                            let owner_priority = task.get_priority();
-                            if current_priority > owner_priority {
-                                task.set_priority(current_priority);
-                            }
-                        }
-                    });
-                }
-            }
-
-            let mutex_id = Arc::as_ptr(&self.inner) as usize;
-            Scheduler::get_current_task().block(crate::sync::event::Event::Mutex(mutex_id));
-            trigger_schedule();
-
-            if self.inner.poisoned.load(Ordering::Acquire) {
-                return Err(RtosError::MutexPoisoned);
-            }
-        }
-    }
-
-    /// 尝试获取拥有所有权的锁守卫���非阻塞）
-    pub fn try_lock_owned(&self) -> Result<OwnedMutexGuard<T>> {
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
-        if self.inner.locked.compare_exchange(
-            false,
-            true,
-            Ordering::Acquire,
-            Ordering::Relaxed,
-        ).is_ok() {
-            let task_id = Scheduler::get_current_task().get_taskid();
-            self.inner.owner.store(task_id, Ordering::Release);
+        if self.inner.try_acquire(false)? {
             Ok(OwnedMutexGuard { 
-                mutex: Arc::clone(&self.inner),
+                mutex: Shared::clone(&self.inner),
             })
         } else {
             Err(RtosError::WouldBlock)
@@ -1035,32 +1124,26 @@ impl<'a, T> core::future::Future for MutexLockFuture<'a, T> {
     type Output = Result<MutexGuard<'a, T>>;
 
     fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
-        // 检查是否已毒化
-        if self.mutex.inner.poisoned.load(Ordering::Acquire) {
-            return core::task::Poll::Ready(Err(RtosError::MutexPoisoned));
+        if let Err(e) = self.mutex.inner.check_ceiling() {
+            return core::task::Poll::Ready(Err(e));
         }
 
         // 尝试获取锁
-        if self.mutex.inner.locked.compare_exchange(
-            false,
-            true,
-            Ordering::Acquire,
-            Ordering::Relaxed,
-        ).is_ok() {
-            // 成功获取锁
-            let task_id = Scheduler::get_current_task().get_taskid();
-            self.mutex.inner.owner.store(task_id, Ordering::Release);
-            return core::task::Poll::Ready(Ok(MutexGuard { 
-                mutex: self.mutex, 
-                _marker: PhantomData 
-            }));
+        match self.mutex.inner.try_acquire(false) {
+            Ok(true) => {
+                return core::task::Poll::Ready(Ok(MutexGuard { 
+                    mutex: self.mutex, 
+                    _marker: PhantomData 
+                }));
+            }
+            Ok(false) => {}
+            Err(e) => return core::task::Poll::Ready(Err(e)),
         }
 
         // 锁被占用，注册 waker
         if !self.registered {
             let mut async_waiters = self.mutex.inner.async_waiters.lock();
-            async_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_waiters.push_back(cx.waker().clone());
         }
 
         core::task::Poll::Pending
@@ -1076,6 +1159,7 @@ impl<T> Mutex<T> {
     /// # 返回值
     /// - `Ok(MutexGuard)`: 成功获取锁
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁，需要 `recover()`
     ///
     /// # 示例
     /// ```rust,no_run
@@ -1092,12 +1176,12 @@ impl<T> Mutex<T> {
 
     /// 获取等待者数量（包括同步和异步等待者）
     pub fn waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len() + self.inner.async_waiters.lock().len()
+        self.inner.waiters.len() + self.inner.async_waiters.lock().len()
     }
 
     /// 获取同步等待者数量
     pub fn sync_waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len()
+        self.inner.waiters.len()
     }
 
     /// 获取异步等待者数量
@@ -1106,6 +1190,551 @@ impl<T> Mutex<T> {
     }
 }
 
+// ============================================================================
+// RecursiveMutex - 递归互斥锁
+// ============================================================================
+
+/// 递归互斥锁内部状态
+struct RecursiveMutexInner<T> {
+    /// 被保护的数据
+    data: UnsafeCell<T>,
+    /// 底层互斥锁（负责持有者记录、等待队列和优先级继承）
+    lock: MutexInner<()>,
+    /// 持有者的加锁次数
+    depth: AtomicUsize,
+}
+
+// Safety: 数据只能通过共享引用访问，且只有持有锁的任务能访问
+unsafe impl<T: Send> Send for RecursiveMutexInner<T> {}
+unsafe impl<T: Send> Sync for RecursiveMutexInner<T> {}
+
+impl<T> RecursiveMutexInner<T> {
+    const fn new(data: T, lock: MutexInner<()>) -> Self {
+        Self {
+            data: UnsafeCell::new(data),
+            lock,
+            depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// 当前任务已经持有锁时增加加锁次数
+    fn reenter(&self) -> bool {
+        let current_id = Scheduler::get_current_task().get_taskid();
+        if self.lock.locked.load(Ordering::Acquire) && self.lock.owner.load(Ordering::Acquire) == current_id {
+            self.depth.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// 获取锁，当前任务已持有时只增加加锁次数
+    fn acquire(&self, timeout_ms: Option<usize>) -> Result<()> {
+        if self.reenter() {
+            return Ok(());
+        }
+        self.lock.acquire(timeout_ms, false)?;
+        self.depth.store(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// 尝试获取锁（非阻塞）
+    fn try_acquire(&self) -> Result<()> {
+        if self.reenter() {
+            return Ok(());
+        }
+        if !self.lock.try_acquire(false)? {
+            return Err(RtosError::WouldBlock);
+        }
+        self.depth.store(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// 减少加锁次数，减到 0 时释放锁
+    fn release(&self) {
+        if self.depth.fetch_sub(1, Ordering::Relaxed) == 1 {
+            self.lock.release();
+        }
+    }
+}
+
+/// 可克隆、可传递的递归互斥锁
+///
+/// 持有锁的任务可以再次加锁而不会死锁，加锁多少次就需要释放多少次，
+/// 最后一个守卫被释放时锁才真正释放。
+///
+/// 同一个任务可能同时持有多个守卫，因此守卫只提供共享引用，
+/// 需要修改数据时配合 `Cell` / `RefCell` 使用。
+///
+/// # 示例
+/// ```rust,no_run
+/// use core::cell::RefCell;
+/// use neon_rtos2::sync::RecursiveMutex;
+///
+/// let bus = RecursiveMutex::new(RefCell::new([0u8; 4]));
+///
+/// fn write_reg(bus: &RecursiveMutex<RefCell<[u8; 4]>>, reg: usize, value: u8) {
+///     let guard = bus.lock().unwrap(); // 调用者已经持有锁时不会死锁
+///     guard.borrow_mut()[reg] = value;
+/// }
+///
+/// let guard = bus.lock().unwrap();
+/// write_reg(&bus, 0, 0x12);
+/// write_reg(&bus, 1, 0x34);
+/// drop(guard);
+/// ```
+pub struct RecursiveMutex<T> {
+    inner: Shared<RecursiveMutexInner<T>>,
+}
+
+impl<T> Clone for RecursiveMutex<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Shared::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> RecursiveMutex<T> {
+    /// 创建新的递归互斥锁
+    #[cfg(feature = "alloc")]
+    pub fn new(data: T) -> Self {
+        Self::from_lock(data, MutexInner::new(()))
+    }
+
+    /// 创建带优先级继承的递归互斥锁
+    #[cfg(feature = "alloc")]
+    pub fn with_priority_inheritance(data: T) -> Self {
+        Self::from_lock(data, MutexInner::new_with_priority_inheritance(()))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_lock(data: T, lock: MutexInner<()>) -> Self {
+        Self {
+            inner: Shared::new(RecursiveMutexInner::new(data, lock)),
+        }
+    }
+
+    /// 检查是否启用了优先级继承
+    pub fn has_priority_inheritance(&self) -> bool {
+        self.inner.lock.priority_inheritance
+    }
+
+    /// 获取锁
+    ///
+    /// 当前任务已经持有锁时立即返回，否则阻塞直到锁可用。
+    ///
+    /// # 返回值
+    /// - `Ok(RecursiveMutexGuard)`: 成功获取锁
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 持有者结束时没有释放锁（递归锁不能恢复）
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
+    pub fn lock(&self) -> Result<RecursiveMutexGuard<'_, T>> {
+        self.inner.acquire(None)?;
+        Ok(RecursiveMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 尝试获取锁（非阻塞）
+    ///
+    /// # 返回值
+    /// - `Ok(RecursiveMutexGuard)`: 成功获取锁（或当前任务已经持有锁）
+    /// - `Err(RtosError::WouldBlock)`: 锁被其他任务占用
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    pub fn try_lock(&self) -> Result<RecursiveMutexGuard<'_, T>> {
+        self.inner.try_acquire()?;
+        Ok(RecursiveMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 带超时的获取锁
+    ///
+    /// # 返回值
+    /// - `Ok(RecursiveMutexGuard)`: 成功获取锁
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    pub fn lock_timeout(&self, timeout_ms: usize) -> Result<RecursiveMutexGuard<'_, T>> {
+        self.inner.acquire(Some(timeout_ms))?;
+        Ok(RecursiveMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 带超时的尝试获取锁（轮询模式）
+    ///
+    /// # 返回值
+    /// - `Ok(RecursiveMutexGuard)`: 成功获取锁
+    /// - `Err(RtosError::Timeout)`: 超时
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    pub fn try_lock_timeout(&self, timeout_ms: usize) -> Result<RecursiveMutexGuard<'_, T>> {
+        let deadline = Systick::get_current_time() + timeout_ms;
+
+        loop {
+            match self.try_lock() {
+                Ok(guard) => return Ok(guard),
+                Err(RtosError::WouldBlock) => {
+                    if Systick::get_current_time() >= deadline {
+                        return Err(RtosError::Timeout);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 闭包风格 API
+    ///
+    /// 在持有锁期间执行闭包，闭包执行完毕后自动释放一次锁。
+    pub fn with_lock<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let guard = self.lock()?;
+        Ok(f(&*guard))
+    }
+
+    /// 检查锁是否被当前任务持有
+    pub fn is_locked_by_current(&self) -> bool {
+        let current_id = Scheduler::get_current_task().get_taskid();
+        self.inner.lock.locked.load(Ordering::Acquire)
+            && self.inner.lock.owner.load(Ordering::Acquire) == current_id
+    }
+
+    /// 检查锁是否被占用
+    pub fn is_locked(&self) -> bool {
+        self.inner.lock.locked.load(Ordering::Acquire)
+    }
+
+    /// 获取持有者的加锁次数（未被持有时为 0）
+    pub fn lock_count(&self) -> usize {
+        if self.is_locked() {
+            self.inner.depth.load(Ordering::Relaxed)
+        } else {
+            0
+        }
+    }
+
+    /// 检查锁是否已被毒化
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.lock.is_poisoned()
+    }
+
+    /// 获取递归互斥锁的唯一标识（用于调试）
+    pub fn id(&self) -> usize {
+        Shared::as_ptr(&self.inner) as usize
+    }
+
+    /// 获取等待者数量
+    pub fn waiter_count(&self) -> usize {
+        self.inner.lock.waiters.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Default> Default for RecursiveMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for RecursiveMutex<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("RecursiveMutex");
+        d.field("id", &self.id());
+        d.field("lock_count", &self.lock_count());
+        d.field("poisoned", &self.is_poisoned());
+        match self.try_lock() {
+            Ok(guard) => {
+                d.field("data", &*guard);
+            }
+            Err(_) => {
+                d.field("data", &"<locked>");
+            }
+        }
+        d.finish()
+    }
+}
+
+/// 不需要堆分配、可以放在 `static` 中的递归互斥锁
+///
+/// 通过 [`handle`](Self::handle) 获取 [`RecursiveMutex`] 句柄后使用。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticRecursiveMutex;
+///
+/// static BUS: StaticRecursiveMutex<u32> = StaticRecursiveMutex::new(0);
+///
+/// let bus = BUS.handle();
+/// let outer = bus.lock().unwrap();
+/// let inner = bus.lock().unwrap();
+/// ```
+pub struct StaticRecursiveMutex<T> {
+    inner: RecursiveMutexInner<T>,
+}
+
+impl<T> StaticRecursiveMutex<T> {
+    /// 创建新的静态递归互斥锁
+    pub const fn new(data: T) -> Self {
+        Self { inner: RecursiveMutexInner::new(data, MutexInner::new(())) }
+    }
+
+    /// 创建带优先级继承的静态递归互斥锁
+    pub const fn with_priority_inheritance(data: T) -> Self {
+        Self { inner: RecursiveMutexInner::new(data, MutexInner::new_with_priority_inheritance(())) }
+    }
+
+    /// 获取指向这个静态递归互斥锁的 [`RecursiveMutex`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> RecursiveMutex<T> {
+        RecursiveMutex { inner: Shared::from_static(&self.inner) }
+    }
+}
+
+impl<T> core::fmt::Debug for StaticRecursiveMutex<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticRecursiveMutex")
+            .field("id", &(&self.inner as *const _ as usize))
+            .field("lock_count", &self.inner.depth.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// 递归互斥锁守卫
+///
+/// 离开作用域时释放一次锁。和 `MutexGuard` 一样不能被发送到其他任务。
+pub struct RecursiveMutexGuard<'a, T> {
+    mutex: &'a RecursiveMutex<T>,
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for RecursiveMutexGuard<'_, T> {}
+
+impl<T> Deref for RecursiveMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // Safety: 我们持有锁，所以可以安全访问数据
+        unsafe { &*self.mutex.inner.data.get() }
+    }
+}
+
+impl<T> Drop for RecursiveMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.inner.release();
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for RecursiveMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecursiveMutexGuard")
+            .field("data", &**self)
+            .finish()
+    }
+}
+
+// ============================================================================
+// StaticMutex - 静态互斥锁
+// ============================================================================
+
+/// 不需要堆分配、可以放在 `static` 中的互斥锁
+///
+/// 与 [`Mutex`] 使用相同的等待队列、优先级继承、天花板协议和健壮锁机制，
+/// 但直接持有内部状态而不是 `Arc`，占用的内存在编译时确定。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticMutex;
+/// use neon_rtos2::kernel::task::Task;
+///
+/// static COUNTER: StaticMutex<u32> = StaticMutex::new(0);
+///
+/// Task::builder("incrementer")
+///     .spawn(|_| loop {
+///         *COUNTER.lock().unwrap() += 1;
+///     })
+///     .unwrap();
+/// ```
+pub struct StaticMutex<T> {
+    inner: MutexInner<T>,
+}
+
+impl<T> StaticMutex<T> {
+    /// 创建新的静态互斥锁
+    pub const fn new(data: T) -> Self {
+        Self { inner: MutexInner::new(data) }
+    }
+
+    /// 创建启用优先级继承的静态互斥锁
+    pub const fn with_priority_inheritance(data: T) -> Self {
+        Self { inner: MutexInner::new_with_priority_inheritance(data) }
+    }
+
+    /// 创建使用优先级天花板协议的静态互斥锁，见 [`Mutex::with_priority_ceiling`]
+    pub const fn with_priority_ceiling(data: T, ceiling: Priority) -> Self {
+        Self { inner: MutexInner::new_with_priority_ceiling(data, ceiling) }
+    }
+
+    /// 获取指向这个静态互斥锁的 [`Mutex`] 句柄
+    ///
+    /// 句柄可以克隆并通过闭包传递，不需要 `alloc`。
+    /// 通过句柄和通过静态对象加锁的是同一把锁。
+    pub fn handle(&'static self) -> Mutex<T> {
+        Mutex { inner: Shared::from_static(&self.inner) }
+    }
+
+    /// 检查是否启用了优先级继承
+    pub fn has_priority_inheritance(&self) -> bool {
+        self.inner.priority_inheritance
+    }
+
+    /// 获取优先级天花板（未启用天花板协议时返回 `None`）
+    pub fn priority_ceiling(&self) -> Option<Priority> {
+        self.inner.priority_ceiling
+    }
+
+    /// 获取锁，见 [`Mutex::lock`]
+    pub fn lock(&self) -> Result<StaticMutexGuard<'_, T>> {
+        self.inner.acquire(None, false)?;
+        Ok(StaticMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 尝试获取锁（非阻塞）
+    ///
+    /// 锁被占用时返回 `Err(RtosError::WouldBlock)`。
+    pub fn try_lock(&self) -> Result<StaticMutexGuard<'_, T>> {
+        self.inner.check_ceiling()?;
+        if self.inner.try_acquire(false)? {
+            Ok(StaticMutexGuard { mutex: self, _marker: PhantomData })
+        } else {
+            Err(RtosError::WouldBlock)
+        }
+    }
+
+    /// 带超时的获取锁
+    pub fn lock_timeout(&self, timeout_ms: usize) -> Result<StaticMutexGuard<'_, T>> {
+        self.inner.acquire(Some(timeout_ms), false)?;
+        Ok(StaticMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 获取锁，持有者已经结束时接管锁以修复数据，见 [`Mutex::recover`]
+    pub fn recover(&self) -> Result<StaticMutexGuard<'_, T>> {
+        self.inner.acquire(None, true)?;
+        Ok(StaticMutexGuard { mutex: self, _marker: PhantomData })
+    }
+
+    /// 在持有锁期间执行闭包
+    pub fn with_lock<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut guard = self.lock()?;
+        Ok(f(&mut *guard))
+    }
+
+    /// 检查锁是否被当前任务持有
+    pub fn is_locked_by_current(&self) -> bool {
+        self.inner.locked.load(Ordering::Acquire)
+            && self.inner.owner() == Some(Scheduler::get_current_task())
+    }
+
+    /// 检查锁是否被占用
+    pub fn is_locked(&self) -> bool {
+        self.inner.locked.load(Ordering::Acquire)
+            && self.inner.robust.load(Ordering::Acquire) != OWNER_DIED
+    }
+
+    /// 检查锁是否已被毒化（包括持有者结束后还没有修复的情况）
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// 检查持有者是否结束时没有释放锁，且数据还没有标记为一致
+    pub fn is_owner_died(&self) -> bool {
+        self.inner.is_owner_died()
+    }
+
+    /// 获取等待者数量（包括同步和异步等待者）
+    pub fn waiter_count(&self) -> usize {
+        self.inner.waiters.len() + self.inner.async_waiters.lock().len()
+    }
+
+    /// 获取互斥锁的唯一标识（用于调试）
+    pub fn id(&self) -> usize {
+        &self.inner as *const MutexInner<T> as usize
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for StaticMutex<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut d = f.debug_struct("StaticMutex");
+        d.field("id", &self.id());
+        d.field("locked", &self.is_locked());
+        d.field("poisoned", &self.is_poisoned());
+        d.field("waiters", &self.waiter_count());
+        match self.try_lock() {
+            Ok(guard) => d.field("data", &*guard),
+            Err(_) => d.field("data", &"<locked>"),
+        };
+        d.finish()
+    }
+}
+
+/// `StaticMutex` 的 RAII 守卫
+pub struct StaticMutexGuard<'a, T> {
+    mutex: &'a StaticMutex<T>,
+    // 锁必须在获取它的任务中释放
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for StaticMutexGuard<'_, T> {}
+
+impl<'a, T> StaticMutexGuard<'a, T> {
+    /// 获取关联的互斥锁引用
+    pub fn mutex(&self) -> &'a StaticMutex<T> {
+        self.mutex
+    }
+
+    /// 手动解锁并返回互斥锁引用
+    pub fn unlock(guard: Self) -> &'a StaticMutex<T> {
+        let mutex = guard.mutex;
+        drop(guard);
+        mutex
+    }
+
+    /// 标记被保护的数据已经修复，见 [`MutexGuard::mark_consistent`]
+    pub fn mark_consistent(guard: &Self) {
+        let _ = guard.mutex.inner.robust.compare_exchange(
+            RECOVERING,
+            CONSISTENT,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+}
+
+impl<T> Deref for StaticMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.mutex.inner.data.get() }
+    }
+}
+
+impl<T> DerefMut for StaticMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.mutex.inner.data.get() }
+    }
+}
+
+impl<T> Drop for StaticMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.inner.release();
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for StaticMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticMutexGuard")
+            .field("data", &**self)
+            .finish()
+    }
+}
+
 // ============================================================================
 // RwLock - 读写锁
 // ============================================================================
@@ -1118,15 +1747,19 @@ struct RwLockInner<T> {
     /// 0 = 无锁，>0 = 读锁数量，-1 = 写锁
     state: AtomicIsize,
     /// 写者等待队列（同步）
-    write_waiters: SpinMutex<WaiterList>,
+    write_waiters: WaitQueue,
     /// 读者等待队列（同步）
-    read_waiters: SpinMutex<WaiterList>,
+    read_waiters: WaitQueue,
     /// 异步写者等待队列
-    async_write_waiters: SpinMutex<VecDeque<Waker>>,
+    async_write_waiters: SpinMutex<WakerQueue>,
     /// 异步读者等待队列
-    async_read_waiters: SpinMutex<VecDeque<Waker>>,
+    async_read_waiters: SpinMutex<WakerQueue>,
     /// 是否已被毒化
     poisoned: AtomicBool,
+    /// 当前写者的任务 ID（usize::MAX 表示没有写者）
+    writer: AtomicUsize,
+    /// 健壮锁状态（`CONSISTENT` / `OWNER_DIED` / `RECOVERING`）
+    robust: AtomicU8,
 }
 
 use core::sync::atomic::AtomicIsize;
@@ -1136,17 +1769,275 @@ unsafe impl<T: Send> Send for RwLockInner<T> {}
 unsafe impl<T: Send + Sync> Sync for RwLockInner<T> {}
 
 impl<T> RwLockInner<T> {
-    fn new(data: T) -> Self {
+    const fn new(data: T) -> Self {
         Self {
             data: UnsafeCell::new(data),
             state: AtomicIsize::new(0),
-            write_waiters: SpinMutex::new(WaiterList::new()),
-            read_waiters: SpinMutex::new(WaiterList::new()),
-            async_write_waiters: SpinMutex::new(VecDeque::new()),
-            async_read_waiters: SpinMutex::new(VecDeque::new()),
+            write_waiters: WaitQueue::new(),
+            read_waiters: WaitQueue::new(),
+            async_write_waiters: SpinMutex::new(WakerQueue::new()),
+            async_read_waiters: SpinMutex::new(WakerQueue::new()),
             poisoned: AtomicBool::new(false),
+            writer: AtomicUsize::new(usize::MAX),
+            robust: AtomicU8::new(CONSISTENT),
+        }
+    }
+
+    fn addr(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// 锁在任务上的登记记录，`release` 在持有者结束时调用
+    fn held_lock(&self, release: unsafe fn(usize)) -> HeldLock {
+        HeldLock {
+            addr: self.addr(),
+            release,
+            boost: rwlock_no_boost,
+            owner: rwlock_writer::<T>,
+        }
+    }
+
+    /// 当前写者
+    fn writer(&self) -> Option<Task> {
+        match self.writer.load(Ordering::Acquire) {
+            usize::MAX => None,
+            writer_id => Some(Task::from_id(writer_id)),
+        }
+    }
+
+    /// 持有者已经结束、数据还没有标记为一致
+    fn is_owner_died(&self) -> bool {
+        self.robust.load(Ordering::Acquire) != CONSISTENT
+    }
+
+    /// 尝试获取读锁，成功时登记到当前任务
+    fn try_read(&self) -> Result<bool> {
+        loop {
+            let state = self.state.load(Ordering::Acquire);
+
+            if state < 0 {
+                // 写者结束后锁保持写锁状态，不会再被释放
+                return match self.robust.load(Ordering::Acquire) {
+                    OWNER_DIED => Err(RtosError::OwnerDied),
+                    _ => Ok(false),
+                };
+            }
+
+            if self.state.compare_exchange(
+                state,
+                state + 1,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ).is_err() {
+                continue;
+            }
+
+            // 毒化是最终状态，毒化时已经唤醒所有等待者，获取到的锁直接放回
+            if self.poisoned.load(Ordering::Acquire) {
+                self.state.fetch_sub(1, Ordering::Release);
+                return Err(RtosError::MutexPoisoned);
+            }
+            // 持有的锁过多时放回读锁（由调用者在等待队列锁外唤醒等待者）
+            if let Err(e) = Scheduler::get_current_task().track_lock(self.held_lock(rwlock_release_read::<T>)) {
+                self.state.fetch_sub(1, Ordering::Release);
+                return Err(e);
+            }
+            return Ok(true);
+        }
+    }
+
+    /// 尝试获取写锁，成功时登记到当前任务
+    ///
+    /// 写者结束后锁保持占用，`recover` 为 `true` 时接管这样的锁，否则返回 `OwnerDied`。
+    fn try_write(&self, recover: bool) -> Result<bool> {
+        let acquired = self.state.compare_exchange(
+            0,
+            -1,
+            Ordering::AcqRel,
+            Ordering::Relaxed,
+        ).is_ok();
+
+        if self.poisoned.load(Ordering::Acquire) {
+            if acquired {
+                self.state.store(0, Ordering::Release);
+            }
+            return Err(RtosError::MutexPoisoned);
         }
+
+        let acquired = acquired || match self.robust.load(Ordering::Acquire) {
+            OWNER_DIED if recover => self
+                .robust
+                .compare_exchange(OWNER_DIED, RECOVERING, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok(),
+            OWNER_DIED => return Err(RtosError::OwnerDied),
+            _ => false,
+        };
+        if acquired {
+            let current = Scheduler::get_current_task();
+            self.writer.store(current.get_taskid(), Ordering::Release);
+            if let Err(e) = current.track_lock(self.held_lock(rwlock_release_write::<T>)) {
+                // 持有的锁过多：接管的锁回到 OwnerDied 状态，其余情况放回写锁
+                // （由调用者在等待队列锁外唤醒等待者）
+                self.writer.store(usize::MAX, Ordering::Release);
+                if self
+                    .robust
+                    .compare_exchange(RECOVERING, OWNER_DIED, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    self.state.store(0, Ordering::Release);
+                }
+                return Err(e);
+            }
+        }
+        Ok(acquired)
     }
+
+    /// 释放读锁，最后一个读者离开时唤醒写者
+    fn unlock_read(&self) {
+        let prev = self.state.fetch_sub(1, Ordering::Release);
+        
+        // 如果这是最后一个读者，尝试唤醒写者
+        if prev == 1 {
+            // 首先尝试唤醒同步写者
+            if self.write_waiters.wake_one().is_some() {
+                return;
+            }
+
+            // 然后尝试唤醒异步写者
+            let waker = {
+                let mut async_write_waiters = self.async_write_waiters.lock();
+                async_write_waiters.pop_front()
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// 释放写锁
+    ///
+    /// 接管后没有标记为一致就释放时锁被永久毒化。
+    fn release_write(&self) {
+        if let Some(writer) = self.writer() {
+            writer.untrack_lock(self.addr());
+        }
+        self.writer.store(usize::MAX, Ordering::Release);
+        if self
+            .robust
+            .compare_exchange(RECOVERING, CONSISTENT, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            self.poisoned.store(true, Ordering::Release);
+        }
+        self.unlock_write();
+    }
+
+    /// 清除写锁状态并唤醒等待者
+    fn unlock_write(&self) {
+        self.state.store(0, Ordering::Release);
+
+        if self.poisoned.load(Ordering::Acquire) {
+            self.wake_all();
+            return;
+        }
+
+        // 优先唤醒所有等待的读者
+        let mut woke_readers = self.read_waiters.wake_all() > 0;
+
+        // 唤醒异步读者
+        let async_read_wakers: WakerQueue;
+        {
+            let mut async_read_waiters = self.async_read_waiters.lock();
+            async_read_wakers = core::mem::take(&mut *async_read_waiters);
+        }
+
+        for waker in async_read_wakers {
+            woke_readers = true;
+            waker.wake();
+        }
+
+        // 如果没有读者等待，唤醒一个写者
+        if !woke_readers {
+            if self.write_waiters.wake_one().is_some() {
+                return;
+            }
+
+            let waker = {
+                let mut async_write_waiters = self.async_write_waiters.lock();
+                async_write_waiters.pop_front()
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// 唤醒所有读者和写者
+    fn wake_all(&self) {
+        self.read_waiters.wake_all();
+        self.write_waiters.wake_all();
+        let async_read_wakers = core::mem::take(&mut *self.async_read_waiters.lock());
+        let async_write_wakers = core::mem::take(&mut *self.async_write_waiters.lock());
+        for waker in async_read_wakers.into_iter().chain(async_write_wakers) {
+            waker.wake();
+        }
+    }
+
+    /// 写者结束时没有释放锁
+    ///
+    /// 锁保持写锁状态，所有等待者被唤醒并得到 `OwnerDied`，直到有任务调用 `recover()` 接管。
+    fn mark_owner_died(&self) {
+        self.robust.store(OWNER_DIED, Ordering::Release);
+        self.writer.store(usize::MAX, Ordering::Release);
+        self.wake_all();
+    }
+
+    /// 因持有的锁过多放弃获取后唤醒所有等待者
+    ///
+    /// 锁被短暂占用期间可能有任务阻塞，放回锁之后由它们重新竞争。不能在等待队列锁内调用。
+    fn wake_if_abandoned<R>(&self, result: Result<R>) -> Result<R> {
+        if matches!(result, Err(RtosError::TooManyHeldLocks)) {
+            self.wake_all();
+        }
+        result
+    }
+}
+
+/// 读写锁不提升持有者的优先级
+///
+/// # Safety
+/// 不访问 `addr`
+unsafe fn rwlock_no_boost(_addr: usize) -> Option<Priority> {
+    None
+}
+
+/// 读写锁当前的写者
+///
+/// # Safety
+/// `addr` 必须指向仍然有效的 `RwLockInner<T>`
+unsafe fn rwlock_writer<T>(addr: usize) -> Option<Task> {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const RwLockInner<T>) }.writer()
+}
+
+/// 读者结束或被删除时调用：读者不修改数据，直接释放读锁
+///
+/// # Safety
+/// `addr` 必须指向仍然有效的 `RwLockInner<T>`
+unsafe fn rwlock_release_read<T>(addr: usize) {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const RwLockInner<T>) }.unlock_read();
+}
+
+/// 写者结束或被删除时调用：标记为 OwnerDied 并唤醒等待者
+///
+/// # Safety
+/// `addr` 必须指向仍然有效的 `RwLockInner<T>`
+unsafe fn rwlock_release_write<T>(addr: usize) {
+    // SAFETY: 由调用者保证
+    unsafe { &*(addr as *const RwLockInner<T>) }.mark_owner_died();
 }
 
 /// 可克隆、可传递的读写锁
@@ -1178,13 +2069,13 @@ impl<T> RwLockInner<T> {
 /// }
 /// ```
 pub struct RwLock<T> {
-    inner: Arc<RwLockInner<T>>,
+    inner: Shared<RwLockInner<T>>,
 }
 
 impl<T> Clone for RwLock<T> {
     fn clone(&self) -> Self {
         Self {
-            inner: Arc::clone(&self.inner),
+            inner: Shared::clone(&self.inner),
         }
     }
 }
@@ -1194,9 +2085,10 @@ impl<T> RwLock<T> {
     ///
     /// # 参数
     /// - `data`: 要保护的数据
+    #[cfg(feature = "alloc")]
     pub fn new(data: T) -> Self {
         Self {
-            inner: Arc::new(RwLockInner::new(data)),
+            inner: Shared::new(RwLockInner::new(data)),
         }
     }
 
@@ -1208,48 +2100,18 @@ impl<T> RwLock<T> {
     /// # 返回值
     /// - `Ok(RwLockReadGuard)`: 成功获取读锁
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 写者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     pub fn read(&self) -> Result<RwLockReadGuard<'_, T>> {
-        // 检查是否已毒化
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
         loop {
-            let state = self.inner.state.load(Ordering::Acquire);
-            
-            // 如果没有写者（state >= 0），尝试增加读者计数
-            if state >= 0 {
-                if self.inner.state.compare_exchange(
-                    state,
-                    state + 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(RwLockReadGuard { lock: self, _marker: PhantomData });
-                }
-                // CAS 失败，重试
-                continue;
-            }
-
-            // 有写者，需要阻塞
-            let current = Scheduler::get_current_task();
-            let task_id = current.get_taskid();
-
-            {
-                let mut read_waiters = self.inner.read_waiters.lock();
-                if !read_waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
+            // 在等待队列锁内尝试获取读锁，有写者时阻塞
+            let blocked = self.inner.read_waiters.wait_if(None, || Ok(!self.inner.try_read()?));
+            let blocked = self.inner.wake_if_abandoned(blocked)?;
+            if !blocked {
+                return Ok(RwLockReadGuard { lock: self, _marker: PhantomData });
             }
 
-            let lock_id = Arc::as_ptr(&self.inner) as usize;
-            Scheduler::get_current_task().block(crate::sync::event::Event::Mutex(lock_id));
-            trigger_schedule();
-
-            // 被唤醒后检查是否毒化
-            if self.inner.poisoned.load(Ordering::Acquire) {
-                return Err(RtosError::MutexPoisoned);
-            }
+            // 被唤醒后重新尝试（毒化和写者结束在下一次尝试时检查）
         }
     }
 
@@ -1259,71 +2121,55 @@ impl<T> RwLock<T> {
     /// - `Ok(RwLockReadGuard)`: 成功获取读锁
     /// - `Err(RtosError::WouldBlock)`: 有写者持有锁
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 写者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     pub fn try_read(&self) -> Result<RwLockReadGuard<'_, T>> {
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
+        if self.inner.wake_if_abandoned(self.inner.try_read())? {
+            Ok(RwLockReadGuard { lock: self, _marker: PhantomData })
+        } else {
+            Err(RtosError::WouldBlock)
         }
+    }
 
-        loop {
-            let state = self.inner.state.load(Ordering::Acquire);
-            
-            if state >= 0 {
-                if self.inner.state.compare_exchange(
-                    state,
-                    state + 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(RwLockReadGuard { lock: self, _marker: PhantomData });
-                }
-                continue;
-            }
-
-            return Err(RtosError::WouldBlock);
-        }
+    /// 获取写锁
+    ///
+    /// 如果有任何读者或写者持有锁，当前任务会被阻塞。
+    ///
+    /// # 返回值
+    /// - `Ok(RwLockWriteGuard)`: 成功获取写锁
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 写者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
+    pub fn write(&self) -> Result<RwLockWriteGuard<'_, T>> {
+        self.write_inner(false)
     }
 
-    /// 获取写锁
+    /// 获取写锁，写者已经结束时接管锁以修复数据
     ///
-    /// 如果有任何读者或写者持有锁，当前任务会被阻塞。
+    /// 持有写锁的任务结束或被删除时锁进入 OwnerDied 状态，`read()` / `write()` 等方法返回
+    /// `Err(RtosError::OwnerDied)`。调用本方法接管锁，修复数据后调用
+    /// `RwLockWriteGuard::mark_consistent` 标记，锁恢复正常；没有标记就释放时锁被永久毒化。
+    ///
+    /// 持有读锁的任务结束时读锁直接释放，数据不受影响。
+    /// 锁处于正常状态时与 `write()` 相同。
     ///
     /// # 返回值
     /// - `Ok(RwLockWriteGuard)`: 成功获取写锁
-    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
-    pub fn write(&self) -> Result<RwLockWriteGuard<'_, T>> {
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
+    /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化，不能恢复
+    pub fn recover(&self) -> Result<RwLockWriteGuard<'_, T>> {
+        self.write_inner(true)
+    }
 
+    fn write_inner(&self, recover: bool) -> Result<RwLockWriteGuard<'_, T>> {
         loop {
-            // 尝试从 0 变为 -1（获取写锁）
-            if self.inner.state.compare_exchange(
-                0,
-                -1,
-                Ordering::AcqRel,
-                Ordering::Relaxed,
-            ).is_ok() {
+            // 在等待队列锁内尝试获取写锁，锁被占用时阻塞
+            let blocked = self.inner.write_waiters.wait_if(None, || Ok(!self.inner.try_write(recover)?));
+            let blocked = self.inner.wake_if_abandoned(blocked)?;
+            if !blocked {
                 return Ok(RwLockWriteGuard { lock: self, _marker: PhantomData });
             }
 
-            // 锁被占用，需要阻塞
-            let current = Scheduler::get_current_task();
-            let task_id = current.get_taskid();
-
-            {
-                let mut write_waiters = self.inner.write_waiters.lock();
-                if !write_waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            let lock_id = Arc::as_ptr(&self.inner) as usize;
-            Scheduler::get_current_task().block(crate::sync::event::Event::Mutex(lock_id));
-            trigger_schedule();
-
-            if self.inner.poisoned.load(Ordering::Acquire) {
-                return Err(RtosError::MutexPoisoned);
-            }
+            // 被唤醒后重新尝试（毒化和写者结束在下一次尝试时检查）
         }
     }
 
@@ -1333,17 +2179,10 @@ impl<T> RwLock<T> {
     /// - `Ok(RwLockWriteGuard)`: 成功获取写锁
     /// - `Err(RtosError::WouldBlock)`: 锁被占用
     /// - `Err(RtosError::MutexPoisoned)`: 锁已被毒化
+    /// - `Err(RtosError::OwnerDied)`: 写者结束时没有释放锁，需要 `recover()`
+    /// - `Err(RtosError::TooManyHeldLocks)`: 当前任务持有的锁已达到 `MAX_HELD_LOCKS`
     pub fn try_write(&self) -> Result<RwLockWriteGuard<'_, T>> {
-        if self.inner.poisoned.load(Ordering::Acquire) {
-            return Err(RtosError::MutexPoisoned);
-        }
-
-        if self.inner.state.compare_exchange(
-            0,
-            -1,
-            Ordering::AcqRel,
-            Ordering::Relaxed,
-        ).is_ok() {
+        if self.inner.wake_if_abandoned(self.inner.try_write(false))? {
             Ok(RwLockWriteGuard { lock: self, _marker: PhantomData })
         } else {
             Err(RtosError::WouldBlock)
@@ -1360,9 +2199,14 @@ impl<T> RwLock<T> {
         RwLockWriteFuture::new(self)
     }
 
-    /// 检查是否已被毒化
+    /// 检查是否已被毒化（包括写者结束后还没有修复的情况）
     pub fn is_poisoned(&self) -> bool {
-        self.inner.poisoned.load(Ordering::Acquire)
+        self.inner.poisoned.load(Ordering::Acquire) || self.inner.is_owner_died()
+    }
+
+    /// 检查写者是否结束时没有释放锁，且数据还没有标记为一致
+    pub fn is_owner_died(&self) -> bool {
+        self.inner.is_owner_died()
     }
 
     /// 获取读者数量（如果有写者返回 0）
@@ -1374,113 +2218,27 @@ impl<T> RwLock<T> {
     /// 检查是否有写者
     pub fn is_write_locked(&self) -> bool {
         self.inner.state.load(Ordering::Relaxed) == -1
+            && self.inner.robust.load(Ordering::Acquire) != OWNER_DIED
     }
 
     /// 获取唯一标识（用于调试）
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
     }
 
     /// 内部方法：释放读锁
     fn unlock_read(&self) {
-        let prev = self.inner.state.fetch_sub(1, Ordering::Release);
-        
-        // 如果这是最后一个读者，尝试唤醒写者
-        if prev == 1 {
-            // 首先尝试唤醒同步写者
-            let task_id = {
-                let mut write_waiters = self.inner.write_waiters.lock();
-                write_waiters.pop_front()
-            };
-
-            if let Some(task_id) = task_id {
-                crate::kernel::task::Task::for_each(|task, id| {
-                    if id == task_id {
-                        if let TaskState::Blocked(_) = task.get_state() {
-                            task.ready();
-                        }
-                    }
-                });
-                return;
-            }
-
-            // 然后尝试唤醒异步写者
-            let waker = {
-                let mut async_write_waiters = self.inner.async_write_waiters.lock();
-                async_write_waiters.pop_front()
-            };
-
-            if let Some(waker) = waker {
-                waker.wake();
-            }
-        }
+        Scheduler::get_current_task().untrack_lock(self.inner.addr());
+        self.inner.unlock_read();
     }
 
     /// 内部方法：释放写锁
     fn unlock_write(&self) {
-        self.inner.state.store(0, Ordering::Release);
-
-        // 优先唤醒所有等待的读者
-        let read_task_ids: [Option<usize>; 16];
-        {
-            let mut read_waiters = self.inner.read_waiters.lock();
-            read_task_ids = read_waiters.drain();
-        }
-
-        let mut woke_readers = false;
-        for task_id in read_task_ids.iter().filter_map(|&id| id) {
-            woke_readers = true;
-            crate::kernel::task::Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-        }
-
-        // 唤醒异步读者
-        let async_read_wakers: VecDeque<Waker>;
-        {
-            let mut async_read_waiters = self.inner.async_read_waiters.lock();
-            async_read_wakers = core::mem::take(&mut *async_read_waiters);
-        }
-
-        for waker in async_read_wakers {
-            woke_readers = true;
-            waker.wake();
-        }
-
-        // 如果没有读者等待，唤醒一个写者
-        if !woke_readers {
-            let task_id = {
-                let mut write_waiters = self.inner.write_waiters.lock();
-                write_waiters.pop_front()
-            };
-
-            if let Some(task_id) = task_id {
-                crate::kernel::task::Task::for_each(|task, id| {
-                    if id == task_id {
-                        if let TaskState::Blocked(_) = task.get_state() {
-                            task.ready();
-                        }
-                    }
-                });
-                return;
-            }
-
-            let waker = {
-                let mut async_write_waiters = self.inner.async_write_waiters.lock();
-                async_write_waiters.pop_front()
-            };
-
-            if let Some(waker) = waker {
-                waker.wake();
-            }
-        }
+        self.inner.release_write();
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Default> Default for RwLock<T> {
     fn default() -> Self {
         Self::new(T::default())
@@ -1492,6 +2250,7 @@ impl<T: core::fmt::Debug> core::fmt::Debug for RwLock<T> {
         let mut d = f.debug_struct("RwLock");
         d.field("id", &self.id());
         d.field("poisoned", &self.is_poisoned());
+        d.field("owner_died", &self.is_owner_died());
         
         let state = self.inner.state.load(Ordering::Relaxed);
         if state == -1 {
@@ -1506,6 +2265,43 @@ impl<T: core::fmt::Debug> core::fmt::Debug for RwLock<T> {
     }
 }
 
+/// 不需要堆分配、可以放在 `static` 中的读写锁
+///
+/// 通过 [`handle`](Self::handle) 获取 [`RwLock`] 句柄后使用。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticRwLock;
+///
+/// static CONFIG: StaticRwLock<u32> = StaticRwLock::new(115200);
+///
+/// let baud = *CONFIG.handle().read().unwrap();
+/// ```
+pub struct StaticRwLock<T> {
+    inner: RwLockInner<T>,
+}
+
+impl<T> StaticRwLock<T> {
+    /// 创建新的静态读写锁
+    pub const fn new(data: T) -> Self {
+        Self { inner: RwLockInner::new(data) }
+    }
+
+    /// 获取指向这个静态读写锁的 [`RwLock`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> RwLock<T> {
+        RwLock { inner: Shared::from_static(&self.inner) }
+    }
+}
+
+impl<T> core::fmt::Debug for StaticRwLock<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticRwLock")
+            .field("id", &self.inner.addr())
+            .field("state", &self.inner.state.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
 /// 读锁守卫
 pub struct RwLockReadGuard<'a, T> {
     lock: &'a RwLock<T>,
@@ -1558,6 +2354,21 @@ impl<T> DerefMut for RwLockWriteGuard<'_, T> {
     }
 }
 
+impl<T> RwLockWriteGuard<'_, T> {
+    /// 标记被保护的数据已经修复
+    ///
+    /// 通过 `RwLock::recover()` 接管写者已经结束的锁并修复数据后调用，锁恢复正常。
+    /// 没有标记就释放锁时锁被永久毒化。锁处于正常状态时没有作用。
+    pub fn mark_consistent(guard: &Self) {
+        let _ = guard.lock.inner.robust.compare_exchange(
+            RECOVERING,
+            CONSISTENT,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+}
+
 impl<T> Drop for RwLockWriteGuard<'_, T> {
     fn drop(&mut self) {
         self.lock.unlock_write();
@@ -1588,37 +2399,24 @@ impl<'a, T> core::future::Future for RwLockReadFuture<'a, T> {
     type Output = Result<RwLockReadGuard<'a, T>>;
 
     fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
-        if self.lock.inner.poisoned.load(Ordering::Acquire) {
-            return core::task::Poll::Ready(Err(RtosError::MutexPoisoned));
-        }
-
-        loop {
-            let state = self.lock.inner.state.load(Ordering::Acquire);
-            
-            if state >= 0 {
-                if self.lock.inner.state.compare_exchange(
-                    state,
-                    state + 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return core::task::Poll::Ready(Ok(RwLockReadGuard { 
-                        lock: self.lock, 
-                        _marker: PhantomData 
-                    }));
-                }
-                continue;
-            }
-
-            // 有写者，注册 waker
-            if !self.registered {
-                let mut async_read_waiters = self.lock.inner.async_read_waiters.lock();
-                async_read_waiters.push_back(cx.waker().clone());
-                self.registered = true;
+        match self.lock.inner.wake_if_abandoned(self.lock.inner.try_read()) {
+            Ok(true) => {
+                return core::task::Poll::Ready(Ok(RwLockReadGuard { 
+                    lock: self.lock, 
+                    _marker: PhantomData 
+                }));
             }
+            Ok(false) => {}
+            Err(e) => return core::task::Poll::Ready(Err(e)),
+        }
 
-            return core::task::Poll::Pending;
+        // 有写者，注册 waker
+        if !self.registered {
+            let mut async_read_waiters = self.lock.inner.async_read_waiters.lock();
+            self.registered = async_read_waiters.push_back(cx.waker().clone());
         }
+
+        core::task::Poll::Pending
     }
 }
 
@@ -1638,27 +2436,21 @@ impl<'a, T> core::future::Future for RwLockWriteFuture<'a, T> {
     type Output = Result<RwLockWriteGuard<'a, T>>;
 
     fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
-        if self.lock.inner.poisoned.load(Ordering::Acquire) {
-            return core::task::Poll::Ready(Err(RtosError::MutexPoisoned));
-        }
-
-        if self.lock.inner.state.compare_exchange(
-            0,
-            -1,
-            Ordering::AcqRel,
-            Ordering::Relaxed,
-        ).is_ok() {
-            return core::task::Poll::Ready(Ok(RwLockWriteGuard { 
-                lock: self.lock, 
-                _marker: PhantomData 
-            }));
+        match self.lock.inner.wake_if_abandoned(self.lock.inner.try_write(false)) {
+            Ok(true) => {
+                return core::task::Poll::Ready(Ok(RwLockWriteGuard { 
+                    lock: self.lock, 
+                    _marker: PhantomData 
+                }));
+            }
+            Ok(false) => {}
+            Err(e) => return core::task::Poll::Ready(Err(e)),
         }
 
         // 锁被占用，注册 waker
         if !self.registered {
             let mut async_write_waiters = self.lock.inner.async_write_waiters.lock();
-            async_write_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_write_waiters.push_back(cx.waker().clone());
         }
 
         core::task::Poll::Pending
@@ -1668,10 +2460,13 @@ impl<'a, T> core::future::Future for RwLockWriteFuture<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::config::MAX_HELD_LOCKS;
     use crate::kernel::task::Task;
     use crate::utils::kernel_init;
     use serial_test::serial;
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_basic() {
@@ -1700,6 +2495,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_try_lock() {
@@ -1723,6 +2519,7 @@ mod tests {
         assert_eq!(*guard2, 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_clone() {
@@ -1748,6 +2545,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_with_lock() {
@@ -1774,6 +2572,7 @@ mod tests {
         assert_eq!(len, 4);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_is_locked() {
@@ -1794,6 +2593,7 @@ mod tests {
         assert!(!mutex.is_locked());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_default() {
@@ -1807,6 +2607,7 @@ mod tests {
         assert_eq!(*guard, 0); // i32 的默认值是 0
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_debug() {
@@ -1821,6 +2622,7 @@ mod tests {
         assert!(debug_str.contains("42"));
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_try_lock_timeout() {
@@ -1840,6 +2642,7 @@ mod tests {
         assert_eq!(*guard2, 42);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_lock_timeout() {
@@ -1859,6 +2662,7 @@ mod tests {
         assert_eq!(*guard2, 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_owned_guard() {
@@ -1878,6 +2682,57 @@ mod tests {
         assert_eq!(*guard2, 42);
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_mutex_v2_held_lock_tracking() {
+        kernel_init();
+        let task = Task::new("test", |_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        let mutex = Mutex::new(0);
+        let other = Mutex::new(0);
+        let guard = mutex.lock().unwrap();
+        let owned = other.lock_owned_guard().unwrap();
+        assert_eq!(task.held_lock_count(), 2);
+        drop(guard);
+        assert_eq!(task.held_lock_count(), 1);
+        drop(owned);
+        assert_eq!(task.held_lock_count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_too_many_held_locks() {
+        kernel_init();
+        let task = Task::new("test", |_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        let mutexes: Vec<Mutex<i32>> = (0..MAX_HELD_LOCKS).map(|_| Mutex::new(0)).collect();
+        let guards: Vec<_> = mutexes.iter().map(|mutex| mutex.lock().unwrap()).collect();
+        assert_eq!(task.held_lock_count(), MAX_HELD_LOCKS);
+
+        // 第 MAX_HELD_LOCKS + 1 个锁获取失败，锁保持未占用
+        let extra = Mutex::new(0);
+        assert_eq!(extra.lock().err(), Some(RtosError::TooManyHeldLocks));
+        assert_eq!(extra.try_lock().err(), Some(RtosError::TooManyHeldLocks));
+        assert!(!extra.is_locked());
+
+        let rwlock = RwLock::new(0);
+        assert_eq!(rwlock.read().err(), Some(RtosError::TooManyHeldLocks));
+        assert_eq!(rwlock.write().err(), Some(RtosError::TooManyHeldLocks));
+        assert_eq!(rwlock.try_write().err(), Some(RtosError::TooManyHeldLocks));
+        assert_eq!(task.held_lock_count(), MAX_HELD_LOCKS);
+
+        // 释放持有的锁之后可以再获取
+        drop(guards);
+        assert!(extra.try_lock().is_ok());
+        assert!(rwlock.try_write().is_ok());
+        assert_eq!(task.held_lock_count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_mapped_guard() {
@@ -1907,6 +2762,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_try_map() {
@@ -1945,6 +2801,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mutex_v2_priority_inheritance() {
@@ -1962,5 +2819,435 @@ mod tests {
             assert_eq!(*guard, 42);
         }
     }
-}
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_recursive_mutex_reentry() {
+        kernel_init();
+        Task::new("test", |_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        let mutex = RecursiveMutex::new(core::cell::Cell::new(0));
+        {
+            let outer = mutex.lock().unwrap();
+            let inner = mutex.try_lock().unwrap();
+            let timed = mutex.lock_timeout(10).unwrap();
+            assert_eq!(mutex.lock_count(), 3);
+            inner.set(1);
+            assert_eq!(outer.get(), 1);
+
+            // 释放内层守卫不会释放锁
+            drop(timed);
+            drop(inner);
+            assert!(mutex.is_locked_by_current());
+            assert_eq!(mutex.lock_count(), 1);
+        }
+        assert!(!mutex.is_locked());
+        assert_eq!(mutex.lock_count(), 0);
+        assert_eq!(mutex.with_lock(|value| value.get()), Ok(1));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_recursive_mutex_other_task_blocked() {
+        kernel_init();
+        let mut owner = Task::new("owner", |_| {}).unwrap();
+        let other = Task::new("other", |_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        let mutex = RecursiveMutex::with_priority_inheritance(0u32);
+        let guard = mutex.lock().unwrap();
+        let nested = mutex.lock().unwrap();
+
+        // 切换到其他任务后不能获取锁
+        owner.block(crate::sync::event::Event::Signal(0));
+        crate::kernel::scheduler::Scheduler::task_switch();
+        assert_eq!(crate::kernel::scheduler::Scheduler::get_current_task(), other);
+        assert_eq!(mutex.try_lock().err(), Some(RtosError::WouldBlock));
+        assert_eq!(mutex.lock_timeout(0).err(), Some(RtosError::Timeout));
+        assert!(!mutex.is_locked_by_current());
+
+        // 持有者释放所有守卫后其他任务可以获取
+        drop(nested);
+        drop(guard);
+        assert_eq!(*mutex.try_lock().unwrap(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_mutex_priority_ceiling() {
+        use Priority;
+
+        kernel_init();
+        let task = Task::builder("test").priority(Priority::Low).spawn(|_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        let outer = Mutex::with_priority_ceiling(0, Priority::Normal);
+        let inner = Mutex::with_priority_ceiling(0, Priority::High);
+        assert_eq!(outer.priority_ceiling(), Some(Priority::Normal));
+        assert!(!outer.has_priority_inheritance());
+
+        // 获取锁后立即以天花板优先级运行，释放后逐级恢复
+        {
+            let _outer = outer.lock().unwrap();
+            assert_eq!(task.get_priority(), Priority::Normal);
+            {
+                let _inner = inner.try_lock().unwrap();
+                assert_eq!(task.get_priority(), Priority::High);
+            }
+            assert_eq!(task.get_priority(), Priority::Normal);
+        }
+        assert_eq!(task.get_priority(), Priority::Low);
+
+        // 天花板低于任务优先级时拒绝获取
+        let low = Mutex::with_priority_ceiling(0, Priority::Idle);
+        assert_eq!(low.lock().err(), Some(RtosError::PriorityCeilingViolated));
+        assert_eq!(low.try_lock().err(), Some(RtosError::PriorityCeilingViolated));
+        assert!(!low.is_locked());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_mutex_priority_ceiling_preempts_on_release() {
+        use crate::kernel::scheduler::Scheduler;
+        use Priority;
+
+        kernel_init();
+        let low = Task::builder("low").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut mid = Task::builder("mid").priority(Priority::Normal).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        mid.suspend().unwrap();
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        let mutex = Mutex::with_priority_ceiling(0, Priority::High);
+        let guard = mutex.lock().unwrap();
+
+        // 持有天花板锁期间中等优先级任务不能抢占
+        mid.resume().unwrap();
+        Scheduler::preempt_check();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        // 释放后恢复原始优先级并让出 CPU
+        drop(guard);
+        assert_eq!(low.get_priority(), Priority::Low);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), mid);
+    }
+
+    // ------------------------------------------------------------------------
+    // 优先级继承：经典的三任务优先级反转场景
+    // ------------------------------------------------------------------------
+
+    use crate::kernel::scheduler::Scheduler;
+    #[cfg(feature = "alloc")]
+    use crate::kernel::task::TaskState;
+    use crate::sync::event::Event;
+
+    /// 让 `waiter` 像在 `lock()` 中一样阻塞在 `mutex` 上
+    #[cfg(feature = "alloc")]
+    fn block_on<T>(mutex: &Mutex<T>, mut waiter: Task) {
+        waiter.block(Event::Mutex(mutex.id()));
+        assert!(mutex.inner.waiters.enqueue(waiter));
+        mutex.inner.begin_wait(waiter);
+    }
+
+    /// 创建 L / M / H 三个任务，只有 L 在运行
+    fn low_mid_high() -> (Task, Task, Task) {
+        kernel_init();
+        let low = Task::builder("low").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let mut mid = Task::builder("mid").priority(Priority::Normal).spawn(|_| {}).unwrap();
+        let mut high = Task::builder("high").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::enable_priority_scheduling();
+        mid.suspend().unwrap();
+        high.suspend().unwrap();
+        Scheduler::start();
+        assert_eq!(Scheduler::get_current_task(), low);
+        (low, mid, high)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_priority_inversion_bounded_by_inheritance() {
+        let (low, mut mid, mut high) = low_mid_high();
+        let mutex = Mutex::with_priority_inheritance(0);
+        let guard = mutex.lock().unwrap();
+
+        // M 抢占持有锁的 L，H 又抢占 M 并阻塞在锁上
+        mid.resume().unwrap();
+        high.resume().unwrap();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+        block_on(&mutex, high);
+
+        // L 继承 H 的优先级，就绪队列中的位置随之调整，先于 M 运行
+        assert_eq!(low.get_priority(), Priority::High);
+        assert_eq!(low.get_base_priority(), Priority::Low);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        // L 释放锁后回到基础优先级，H 在随后的任务切换中运行
+        drop(guard);
+        assert_eq!(low.get_priority(), Priority::Low);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+        assert!(mutex.try_lock().is_ok());
+        assert_eq!(mid.get_state(), TaskState::Ready);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_priority_inheritance_is_transitive() {
+        let (low, mut mid, mut high) = low_mid_high();
+        let first = Mutex::with_priority_inheritance(0);
+        let second = Mutex::with_priority_inheritance(0);
+
+        // L 持有 first；M 持有 second 并等待 first
+        let low_guard = first.lock().unwrap();
+        mid.resume().unwrap();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), mid);
+        let mid_guard = second.lock().unwrap();
+        block_on(&first, mid);
+        assert_eq!(low.get_priority(), Priority::Normal);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        // H 等待 second：M 和 M 等待的 first 的持有者 L 都继承 H 的优先级
+        high.resume().unwrap();
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+        block_on(&second, high);
+        assert_eq!(mid.get_priority(), Priority::High);
+        assert_eq!(low.get_priority(), Priority::High);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), low);
+
+        // L 释放 first：L 回到基础优先级，M（仍然继承 H）获得 first
+        drop(low_guard);
+        assert_eq!(low.get_priority(), Priority::Low);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), mid);
+        first.inner.end_wait(mid);
+        let mid_first = first.try_lock().unwrap();
+        assert_eq!(mid.get_priority(), Priority::High);
+
+        // M 释放 second 后回到基础优先级，H 运行
+        drop(mid_guard);
+        assert_eq!(mid.get_priority(), Priority::Normal);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+        drop(mid_first);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_priority_inheritance_restores_per_mutex() {
+        let (low, mut mid, mut high) = low_mid_high();
+        let a = Mutex::with_priority_inheritance(0);
+        let b = Mutex::with_priority_inheritance(0);
+        let guard_a = a.lock().unwrap();
+        let guard_b = b.lock().unwrap();
+
+        // M 等待 b，H 等待 a
+        mid.resume().unwrap();
+        block_on(&b, mid);
+        Scheduler::task_switch();
+        assert_eq!(low.get_priority(), Priority::Normal);
+        high.resume().unwrap();
+        block_on(&a, high);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), low);
+        assert_eq!(low.get_priority(), Priority::High);
+
+        // 释放 a 后仍然继承 b 的等待者 M 的优先级，不会过早回到基础优先级
+        drop(guard_a);
+        assert_eq!(low.get_priority(), Priority::Normal);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+
+        drop(guard_b);
+        assert_eq!(low.get_priority(), Priority::Low);
+        assert_eq!(mid.get_state(), TaskState::Ready);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_priority_inheritance_timeout_and_waiter_priority_change() {
+        let (mut low, _mid, mut high) = low_mid_high();
+        let mutex = Mutex::with_priority_inheritance(0);
+        let _guard = mutex.lock().unwrap();
+
+        high.resume().unwrap();
+        block_on(&mutex, high);
+        assert_eq!(low.get_priority(), Priority::High);
+
+        // 等待者的优先级改变时持有者随之改变
+        high.set_priority(Priority::Critical);
+        assert_eq!(low.get_priority(), Priority::Critical);
+
+        // 等待者超时离开后持有者回到基础优先级
+        assert!(mutex.inner.waiters.remove(high));
+        mutex.inner.end_wait(high);
+        assert_eq!(low.get_priority(), Priority::Low);
+
+        // 持有锁期间修改基础优先级
+        low.set_priority(Priority::Normal);
+        assert_eq!(low.get_priority(), Priority::Normal);
+    }
+
+    // ------------------------------------------------------------------------
+    // 健壮锁：持有者结束时没有释放锁
+    // ------------------------------------------------------------------------
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_mutex_owner_died_recover() {
+        kernel_init();
+        let owner = Task::new("owner", |_| {}).unwrap();
+        let main = Task::new("main", |_| {}).unwrap();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        Scheduler::start();
+
+        let mutex = Mutex::new(1);
+        core::mem::forget(mutex.lock().unwrap());
+        waiter.block(Event::Mutex(mutex.id()));
+        assert!(mutex.inner.waiters.enqueue(waiter));
+
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), main);
+        Task::kill(owner).unwrap();
+
+        // 等待者被唤醒，之后的加锁都得到 OwnerDied
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert!(mutex.is_owner_died());
+        assert!(!mutex.is_locked());
+        assert!(matches!(mutex.lock(), Err(RtosError::OwnerDied)));
+        assert!(matches!(mutex.try_lock(), Err(RtosError::OwnerDied)));
+
+        // 接管并修复数据后恢复正常
+        let mut guard = mutex.recover().unwrap();
+        assert_eq!(main.held_lock_count(), 1);
+        assert!(matches!(mutex.try_lock(), Err(RtosError::WouldBlock)));
+        *guard = 0;
+        MutexGuard::mark_consistent(&guard);
+        drop(guard);
+        assert!(!mutex.is_owner_died());
+        assert!(!mutex.is_poisoned());
+        assert_eq!(*mutex.lock().unwrap(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_mutex_recover_without_mark_consistent_poisons() {
+        kernel_init();
+        let owner = Task::new("owner", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let mutex = Mutex::new(1);
+        core::mem::forget(mutex.lock().unwrap());
+        Scheduler::task_switch();
+        Task::kill(owner).unwrap();
+
+        drop(mutex.recover().unwrap());
+        assert!(!mutex.is_owner_died());
+        assert!(mutex.is_poisoned());
+        assert!(matches!(mutex.lock(), Err(RtosError::MutexPoisoned)));
+        assert!(matches!(mutex.recover(), Err(RtosError::MutexPoisoned)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_rwlock_owner_died() {
+        kernel_init();
+        let writer = Task::new("writer", |_| {}).unwrap();
+        let reader = Task::new("reader", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        // 读者结束时读锁直接释放
+        let lock = RwLock::new(1);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), reader);
+        core::mem::forget(lock.read().unwrap());
+        assert_eq!(reader.held_lock_count(), 1);
+        Scheduler::task_switch();
+        Task::kill(reader).unwrap();
+        assert_eq!(lock.reader_count(), 0);
+        assert!(!lock.is_owner_died());
+
+        // 写者结束时锁进入 OwnerDied 状态
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), writer);
+        core::mem::forget(lock.write().unwrap());
+        Scheduler::task_switch();
+        Task::kill(writer).unwrap();
+        assert!(lock.is_owner_died());
+        assert!(!lock.is_write_locked());
+        assert!(matches!(lock.read(), Err(RtosError::OwnerDied)));
+        assert!(matches!(lock.try_write(), Err(RtosError::OwnerDied)));
+
+        let mut guard = lock.recover().unwrap();
+        *guard = 0;
+        RwLockWriteGuard::mark_consistent(&guard);
+        drop(guard);
+        assert!(!lock.is_poisoned());
+        assert_eq!(*lock.read().unwrap(), 0);
+    }
+
+    // ------------------------------------------------------------------------
+    // StaticMutex
+    // ------------------------------------------------------------------------
+
+    #[test]
+    #[serial]
+    fn test_static_mutex() {
+        static COUNTER: StaticMutex<u32> = StaticMutex::new(0);
+
+        kernel_init();
+        let task = Task::new("test", |_| {}).unwrap();
+        Scheduler::start();
+
+        let mut guard = COUNTER.lock().unwrap();
+        *guard += 1;
+        assert!(COUNTER.is_locked_by_current());
+        assert_eq!(task.held_lock_count(), 1);
+        assert!(matches!(COUNTER.try_lock(), Err(RtosError::WouldBlock)));
+        drop(guard);
+        assert_eq!(COUNTER.with_lock(|value| *value).unwrap(), 1);
+        assert_eq!(task.held_lock_count(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_static_mutex_priority_inheritance() {
+        static SHARED: StaticMutex<u32> = StaticMutex::with_priority_inheritance(0);
+
+        let (low, _mid, mut high) = low_mid_high();
+        let guard = SHARED.lock().unwrap();
+
+        high.resume().unwrap();
+        high.block(Event::Mutex(SHARED.id()));
+        assert!(SHARED.inner.waiters.enqueue(high));
+        SHARED.inner.begin_wait(high);
+        assert_eq!(low.get_priority(), Priority::High);
+
+        drop(guard);
+        assert_eq!(low.get_priority(), Priority::Low);
+        Scheduler::task_switch();
+        assert_eq!(Scheduler::get_current_task(), high);
+        assert!(SHARED.try_lock().is_ok());
+    }
+}
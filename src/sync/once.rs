@@ -0,0 +1,474 @@
+//! # OnceCell / Lazy - 一次性初始化
+//!
+//! 基于 Arc 的一次性初始化单元，可以通过闭包传递。
+//! 第一个调用 `get_or_init` 的任务执行初始化，期间其他任务阻塞，初始化完成后一起返回同一个值。
+//!
+//! - [`OnceCell`]：每次调用时提供初始化函数
+//! - [`Lazy`]：创建时提供初始化函数，第一次解引用时初始化
+//!
+//! 初始化函数中不能再访问同一个单元，否则当前任务会一直等待自己完成初始化。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::sync::{Lazy, OnceCell};
+//! use neon_rtos2::kernel::task::Task;
+//!
+//! let config: OnceCell<u32> = OnceCell::new();
+//! let calibration = Lazy::new(|| 42u32);
+//!
+//! let config_clone = config.clone();
+//! Task::builder("loader")
+//!     .spawn(move |_| {
+//!         let _ = config_clone.set(115200);
+//!     })
+//!     .unwrap();
+//!
+//! Task::builder("uart")
+//!     .spawn(move |_| {
+//!         // 阻塞到 loader 写入配置
+//!         let baud = *config.wait();
+//!         let offset = *calibration;
+//!         let _ = (baud, offset);
+//!     })
+//!     .unwrap();
+//! ```
+
+use crate::compat::Shared;
+use crate::hal::trigger_schedule;
+use crate::sync::wait_queue::{WaitQueue, WakerQueue};
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::sync::atomic::{AtomicU8, Ordering};
+use spin::Mutex;
+
+/// 未初始化
+const INCOMPLETE: u8 = 0;
+/// 正在初始化
+const RUNNING: u8 = 1;
+/// 初始化完成
+const COMPLETE: u8 = 2;
+
+struct OnceInner<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+    /// 等待初始化完成的任务
+    waiters: WaitQueue,
+    /// 异步等待者
+    async_waiters: Mutex<WakerQueue>,
+}
+
+// 值只在 RUNNING 状态下由唯一的初始化者写入，COMPLETE 之后只读
+unsafe impl<T: Send> Send for OnceInner<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceInner<T> {}
+
+impl<T> OnceInner<T> {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(INCOMPLETE),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+            waiters: WaitQueue::new(),
+            async_waiters: Mutex::new(WakerQueue::new()),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.state.load(Ordering::Acquire) == COMPLETE
+    }
+
+    /// 获取已初始化的值
+    fn get(&self) -> Option<&T> {
+        if self.is_complete() {
+            // SAFETY: COMPLETE 状态下值已经写入且不会再被修改
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// 写入值并唤醒所有等待者
+    fn complete(&self, value: T) {
+        // SAFETY: 只有把状态从 INCOMPLETE 改为 RUNNING 的任务会写入
+        unsafe { (*self.value.get()).write(value) };
+        self.state.store(COMPLETE, Ordering::Release);
+
+        self.waiters.wake_all();
+        let async_wakers = core::mem::take(&mut *self.async_waiters.lock());
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 阻塞到初始化完成
+    fn wait(&self) -> &T {
+        loop {
+            if let Some(value) = self.get() {
+                return value;
+            }
+            // 等待队列已满时退化为让出 CPU 后重新检查
+            if self.waiters.wait_if(None, || Ok(!self.is_complete())).is_err() {
+                trigger_schedule();
+            }
+        }
+    }
+}
+
+impl<T> Drop for OnceInner<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == COMPLETE {
+            // SAFETY: 值已经初始化，且这是最后一个引用
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+// ============================================================================
+// OnceCell
+// ============================================================================
+
+/// 可克隆、只能写入一次的单元
+///
+/// 克隆后指向同一个单元。
+pub struct OnceCell<T> {
+    inner: Shared<OnceInner<T>>,
+}
+
+impl<T> Clone for OnceCell<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> OnceCell<T> {
+    /// 创建未初始化的单元
+    #[cfg(feature = "alloc")]
+    pub fn new() -> Self {
+        Self {
+            inner: Shared::new(OnceInner::new()),
+        }
+    }
+
+    /// 获取值，未初始化（或正在初始化）时返回 `None`
+    pub fn get(&self) -> Option<&T> {
+        self.inner.get()
+    }
+
+    /// 是否已经初始化
+    pub fn is_initialized(&self) -> bool {
+        self.inner.is_complete()
+    }
+
+    /// 获取值，未初始化时由当前任务调用 `f` 初始化
+    ///
+    /// 其他任务正在初始化时阻塞到初始化完成，返回它写入的值。
+    pub fn get_or_init<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        if let Some(value) = self.inner.get() {
+            return value;
+        }
+
+        if self
+            .inner
+            .state
+            .compare_exchange(INCOMPLETE, RUNNING, Ordering::Acquire, Ordering::Acquire)
+            .is_ok()
+        {
+            self.inner.complete(f());
+        }
+        self.inner.wait()
+    }
+
+    /// 写入值
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 写入成功
+    /// - `Err(value)`: 已经（或正在被其他任务）初始化，返回传入的值
+    pub fn set(&self, value: T) -> core::result::Result<(), T> {
+        let mut value = Some(value);
+        self.get_or_init(|| value.take().unwrap());
+        match value {
+            None => Ok(()),
+            Some(value) => Err(value),
+        }
+    }
+
+    /// 阻塞到其他任务完成初始化
+    pub fn wait(&self) -> &T {
+        self.inner.wait()
+    }
+
+    /// 异步等待初始化完成
+    pub fn wait_async(&self) -> OnceCellFuture<'_, T> {
+        OnceCellFuture::new(self)
+    }
+
+    /// 获取单元的唯一标识
+    pub fn id(&self) -> usize {
+        Shared::as_ptr(&self.inner) as usize
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OnceCell")
+            .field("id", &self.id())
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+/// 不需要堆分配、可以放在 `static` 中的 `OnceCell`
+///
+/// 通过 [`handle`](Self::handle) 获取 [`OnceCell`] 句柄后使用。
+pub struct StaticOnceCell<T> {
+    inner: OnceInner<T>,
+}
+
+impl<T> StaticOnceCell<T> {
+    /// 创建未初始化的静态单元
+    pub const fn new() -> Self {
+        Self { inner: OnceInner::new() }
+    }
+
+    /// 获取指向这个静态单元的 [`OnceCell`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> OnceCell<T> {
+        OnceCell { inner: Shared::from_static(&self.inner) }
+    }
+}
+
+impl<T> Default for StaticOnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for StaticOnceCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticOnceCell")
+            .field("value", &self.inner.get())
+            .finish()
+    }
+}
+
+// ============================================================================
+// Lazy
+// ============================================================================
+
+/// 第一次访问时初始化的值
+///
+/// 克隆后共享同一个值和初始化函数。
+pub struct Lazy<T, F = fn() -> T> {
+    cell: OnceCell<T>,
+    init: Shared<Mutex<Option<F>>>,
+}
+
+impl<T, F> Clone for Lazy<T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: self.cell.clone(),
+            init: self.init.clone(),
+        }
+    }
+}
+
+impl<T, F: FnOnce() -> T> Lazy<T, F> {
+    /// 创建延迟初始化的值
+    #[cfg(feature = "alloc")]
+    pub fn new(init: F) -> Self {
+        Self {
+            cell: OnceCell::new(),
+            init: Shared::new(Mutex::new(Some(init))),
+        }
+    }
+
+    /// 强制初始化并返回值
+    pub fn force(this: &Self) -> &T {
+        this.cell.get_or_init(|| {
+            // 取出初始化函数后立即释放锁，初始化期间其他任务在 OnceCell 上阻塞
+            let init = this.init.lock().take();
+            match init {
+                Some(init) => init(),
+                None => panic!("Lazy instance has previously been poisoned"),
+            }
+        })
+    }
+
+    /// 获取值，未初始化时返回 `None`
+    pub fn get(this: &Self) -> Option<&T> {
+        this.cell.get()
+    }
+}
+
+impl<T, F: FnOnce() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        Lazy::force(self)
+    }
+}
+
+impl<T: core::fmt::Debug, F> core::fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Lazy").field("value", &self.cell.get()).finish()
+    }
+}
+
+// ============================================================================
+// 异步支持
+// ============================================================================
+
+/// 异步等待 `OnceCell` 初始化完成的 Future
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::OnceCell;
+///
+/// async fn example(config: OnceCell<u32>) {
+///     let baud = *config.wait_async().await;
+///     let _ = baud;
+/// }
+/// ```
+pub struct OnceCellFuture<'a, T> {
+    cell: &'a OnceCell<T>,
+    registered: bool,
+}
+
+impl<'a, T> OnceCellFuture<'a, T> {
+    fn new(cell: &'a OnceCell<T>) -> Self {
+        Self {
+            cell,
+            registered: false,
+        }
+    }
+}
+
+impl<'a, T> core::future::Future for OnceCellFuture<'a, T> {
+    type Output = &'a T;
+
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<Self::Output> {
+        let cell = self.cell;
+
+        // 持有异步等待者锁检查，初始化者写入完成后才会取走等待者，不会丢失唤醒
+        let mut async_waiters = cell.inner.async_waiters.lock();
+        if let Some(value) = cell.get() {
+            return core::task::Poll::Ready(value);
+        }
+        if !self.registered {
+            self.registered = async_waiters.push_back(cx.waker().clone());
+            drop(async_waiters);
+        }
+
+        core::task::Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::scheduler::Scheduler;
+    use crate::kernel::task::{Task, TaskState};
+    use crate::sync::event::Event;
+    use crate::utils::kernel_init;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    #[cfg(feature = "alloc")]
+    use core::sync::atomic::AtomicUsize;
+    use serial_test::serial;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_once_cell_set_and_get() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let cell = OnceCell::new();
+        assert_eq!(cell.get(), None);
+        assert_eq!(cell.set(1), Ok(()));
+        assert_eq!(cell.set(2), Err(2));
+        assert_eq!(*cell.get_or_init(|| 3), 1);
+        assert_eq!(cell.clone().get(), Some(&1));
+        assert_eq!(*cell.wait(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_once_cell_wakes_waiters() {
+        kernel_init();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+
+        let cell = OnceCell::new();
+        waiter.block(Event::WaitQueue(cell.inner.waiters.id()));
+        assert!(cell.inner.waiters.enqueue(waiter));
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(cell.wait_async());
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+
+        cell.get_or_init(|| 7u32);
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(&7));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_lazy_initializes_once() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let lazy = Lazy::new(|| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            5u32
+        });
+        let shared = lazy.clone();
+        assert_eq!(Lazy::get(&lazy), None);
+        assert_eq!(*lazy + *shared, 10);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_static_once_cell() {
+        static CONFIG: StaticOnceCell<u32> = StaticOnceCell::new();
+
+        kernel_init();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let cell = CONFIG.handle();
+        assert_eq!(cell.get(), None);
+        waiter.block(Event::WaitQueue(cell.inner.waiters.id()));
+        assert!(cell.inner.waiters.enqueue(waiter));
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(cell.wait_async());
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+
+        // 初始化后唤醒等待者，所有句柄看到同一个值
+        assert_eq!(CONFIG.handle().set(7), Ok(()));
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(&7));
+        assert_eq!(cell.set(8), Err(8));
+        assert_eq!(*cell.wait(), 7);
+    }
+}
@@ -32,15 +32,11 @@
 //! }
 //! ```
 
-use crate::compat::{Arc, VecDeque};
-use crate::kernel::scheduler::Scheduler;
-use crate::kernel::task::{Task, TaskState};
+use crate::compat::Shared;
 use crate::kernel::time::systick::Systick;
-use crate::hal::trigger_schedule;
 use crate::error::{Result, RtosError};
-use crate::sync::signal::WaiterList;
+use crate::sync::wait_queue::{WaitQueue, WakerQueue};
 use core::sync::atomic::{AtomicUsize, AtomicBool, Ordering};
-use core::task::Waker;
 use spin::Mutex;
 
 /// 信号量内部状态
@@ -49,24 +45,137 @@ struct SemaphoreInner {
     permits: AtomicUsize,
     /// 最大许可数（0 表示无限制）
     max_permits: usize,
-    /// 同步等待者列表
-    waiters: Mutex<WaiterList>,
+    /// 同步等待队列
+    waiters: WaitQueue,
     /// 异步等待者列表
-    async_waiters: Mutex<VecDeque<Waker>>,
+    async_waiters: Mutex<WakerQueue>,
     /// 是否已关闭
     closed: AtomicBool,
 }
 
 impl SemaphoreInner {
-    fn new(initial_permits: usize, max_permits: usize) -> Self {
+    const fn new(initial_permits: usize, max_permits: usize) -> Self {
         Self {
             permits: AtomicUsize::new(initial_permits),
             max_permits,
-            waiters: Mutex::new(WaiterList::new()),
-            async_waiters: Mutex::new(VecDeque::new()),
+            waiters: WaitQueue::new(),
+            async_waiters: Mutex::new(WakerQueue::new()),
             closed: AtomicBool::new(false),
         }
     }
+
+    /// 每个释放的许可唤醒一个等待者（先同步等待者，后异步等待者）
+    fn wake_waiters(&self, n: usize) {
+        for _ in 0..n {
+            if self.waiters.wake_one().is_some() {
+                continue;
+            }
+
+            let waker = {
+                let mut async_waiters = self.async_waiters.lock();
+                async_waiters.pop_front()
+            };
+
+            match waker {
+                Some(waker) => waker.wake(),
+                None => break,
+            }
+        }
+    }
+
+    /// 获取 `n` 个许可
+    ///
+    /// 在等待队列锁内检查关闭状态和许可数量，许可不足时阻塞，被唤醒后重试。
+    fn acquire(&self, n: usize, timeout_ms: Option<usize>) -> Result<()> {
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+
+        loop {
+            let remaining = deadline.map(|deadline| deadline.saturating_sub(Systick::get_current_time()));
+            let blocked = self.waiters.wait_if(remaining, || {
+                if self.closed.load(Ordering::Acquire) {
+                    return Err(RtosError::SemaphoreClosed);
+                }
+                Ok(!self.try_acquire(n)?)
+            })?;
+
+            if !blocked {
+                return Ok(());
+            }
+            if self.closed.load(Ordering::Acquire) {
+                return Err(RtosError::SemaphoreClosed);
+            }
+        }
+    }
+
+    /// 尝试获取 `n` 个许可（非阻塞）
+    fn try_acquire(&self, n: usize) -> Result<bool> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(RtosError::SemaphoreClosed);
+        }
+
+        loop {
+            let current = self.permits.load(Ordering::Acquire);
+            if current >= n {
+                if self.permits.compare_exchange(
+                    current,
+                    current - n,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                ).is_ok() {
+                    return Ok(true);
+                }
+                continue;
+            }
+            return Ok(false);
+        }
+    }
+
+    /// 释放 `n` 个许可并唤醒等待者
+    fn release(&self, n: usize) -> Result<()> {
+        if self.closed.load(Ordering::Acquire) {
+            return Ok(()); // 已关闭，忽略释放
+        }
+
+        // 检查是否会超过最大限制
+        if self.max_permits > 0 {
+            let current = self.permits.load(Ordering::Acquire);
+            if current + n > self.max_permits {
+                return Err(RtosError::SemaphoreOverflow);
+            }
+        }
+
+        // 增加许可计数
+        self.permits.fetch_add(n, Ordering::Release);
+
+        // 唤醒等待者
+        self.wake_waiters(n);
+
+        Ok(())
+    }
+
+    /// 关闭并唤醒所有等待者
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        
+        // 唤醒所有同步等待者
+        self.waiters.wake_all();
+
+        // 唤醒所有异步等待者
+        let async_wakers: WakerQueue;
+        {
+            let mut async_waiters = self.async_waiters.lock();
+            async_wakers = core::mem::take(&mut *async_waiters);
+        }
+
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 同步和异步等待者的总数
+    fn waiter_count(&self) -> usize {
+        self.waiters.len() + self.async_waiters.lock().len()
+    }
 }
 
 /// 可克隆、可传递的计数信号量
@@ -89,7 +198,7 @@ impl SemaphoreInner {
 /// ```
 #[derive(Clone)]
 pub struct Semaphore {
-    inner: Arc<SemaphoreInner>,
+    inner: Shared<SemaphoreInner>,
 }
 
 impl Semaphore {
@@ -104,9 +213,10 @@ impl Semaphore {
     ///
     /// let sem = Semaphore::new(5); // 5 个初始许可
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn new(initial_permits: usize) -> Self {
         Self {
-            inner: Arc::new(SemaphoreInner::new(initial_permits, 0)),
+            inner: Shared::new(SemaphoreInner::new(initial_permits, 0)),
         }
     }
 
@@ -123,10 +233,11 @@ impl Semaphore {
     /// // 初始 3 个许可，最多 5 个
     /// let sem = Semaphore::with_max(3, 5);
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn with_max(initial_permits: usize, max_permits: usize) -> Self {
         let permits = initial_permits.min(max_permits);
         Self {
-            inner: Arc::new(SemaphoreInner::new(permits, max_permits)),
+            inner: Shared::new(SemaphoreInner::new(permits, max_permits)),
         }
     }
 
@@ -159,47 +270,7 @@ impl Semaphore {
     /// - `Ok(())`: 成功获取许可
     /// - `Err(RtosError::SemaphoreClosed)`: 信号量已关闭
     pub fn acquire_many(&self, n: usize) -> Result<()> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SemaphoreClosed);
-        }
-
-        loop {
-            let current = self.inner.permits.load(Ordering::Acquire);
-            if current >= n {
-                if self.inner.permits.compare_exchange(
-                    current,
-                    current - n,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(());
-                }
-                continue;
-            }
-
-            // 许可不足，需要阻塞
-            let task_id = Scheduler::get_current_task().get_taskid();
-
-            {
-                let mut waiters = self.inner.waiters.lock();
-                
-                if self.inner.closed.load(Ordering::Acquire) {
-                    return Err(RtosError::SemaphoreClosed);
-                }
-                
-                if !waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            let sem_id = Arc::as_ptr(&self.inner) as usize;
-            Scheduler::get_current_task().block(crate::sync::event::Event::Signal(sem_id));
-            trigger_schedule();
-
-            if self.inner.closed.load(Ordering::Acquire) {
-                return Err(RtosError::SemaphoreClosed);
-            }
-        }
+        self.inner.acquire(n, None)
     }
 
     /// 尝试获取一个许可（非阻塞）
@@ -214,25 +285,7 @@ impl Semaphore {
 
     /// 尝试获取多个许可（非阻塞）
     pub fn try_acquire_many(&self, n: usize) -> Result<bool> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SemaphoreClosed);
-        }
-
-        loop {
-            let current = self.inner.permits.load(Ordering::Acquire);
-            if current >= n {
-                if self.inner.permits.compare_exchange(
-                    current,
-                    current - n,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(true);
-                }
-                continue;
-            }
-            return Ok(false);
-        }
+        self.inner.try_acquire(n)
     }
 
     /// 带超时的获取许可
@@ -245,58 +298,7 @@ impl Semaphore {
     /// - `Err(RtosError::Timeout)`: 等待超时
     /// - `Err(RtosError::SemaphoreClosed)`: 信号量已关闭
     pub fn acquire_timeout(&self, timeout_ms: usize) -> Result<()> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SemaphoreClosed);
-        }
-
-        let deadline = Systick::get_current_time() + timeout_ms;
-
-        loop {
-            let current = self.inner.permits.load(Ordering::Acquire);
-            if current > 0 {
-                if self.inner.permits.compare_exchange(
-                    current,
-                    current - 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(());
-                }
-                continue;
-            }
-
-            if Systick::get_current_time() >= deadline {
-                return Err(RtosError::Timeout);
-            }
-
-            let task_id = Scheduler::get_current_task().get_taskid();
-
-            {
-                let mut waiters = self.inner.waiters.lock();
-                
-                if self.inner.closed.load(Ordering::Acquire) {
-                    return Err(RtosError::SemaphoreClosed);
-                }
-                
-                if !waiters.push(task_id) {
-                    return Err(RtosError::WaiterQueueFull);
-                }
-            }
-
-            let sem_id = Arc::as_ptr(&self.inner) as usize;
-            Scheduler::get_current_task().block(crate::sync::event::Event::Signal(sem_id));
-            trigger_schedule();
-
-            if self.inner.closed.load(Ordering::Acquire) {
-                return Err(RtosError::SemaphoreClosed);
-            }
-
-            if Systick::get_current_time() >= deadline {
-                let mut waiters = self.inner.waiters.lock();
-                waiters.remove(task_id);
-                return Err(RtosError::Timeout);
-            }
-        }
+        self.inner.acquire(1, Some(timeout_ms))
     }
 
     /// 释放一个许可
@@ -312,87 +314,14 @@ impl Semaphore {
 
     /// 释放多个许可
     pub fn release_many(&self, n: usize) -> Result<()> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Ok(()); // 已关闭，忽略释放
-        }
-
-        // 检查是否会超过最大限制
-        if self.inner.max_permits > 0 {
-            let current = self.inner.permits.load(Ordering::Acquire);
-            if current + n > self.inner.max_permits {
-                return Err(RtosError::SemaphoreOverflow);
-            }
-        }
-
-        // 增加许可计数
-        self.inner.permits.fetch_add(n, Ordering::Release);
-
-        // 唤醒等待者
-        for _ in 0..n {
-            // 首先尝试唤醒同步等待者
-            let task_id = {
-                let mut waiters = self.inner.waiters.lock();
-                waiters.pop_front()
-            };
-
-            if let Some(task_id) = task_id {
-                Task::for_each(|task, id| {
-                    if id == task_id {
-                        if let TaskState::Blocked(_) = task.get_state() {
-                            task.ready();
-                        }
-                    }
-                });
-                continue;
-            }
-
-            // 然后尝试唤醒异步等待者
-            let waker = {
-                let mut async_waiters = self.inner.async_waiters.lock();
-                async_waiters.pop_front()
-            };
-
-            if let Some(waker) = waker {
-                waker.wake();
-            }
-        }
-
-        Ok(())
+        self.inner.release(n)
     }
 
     /// ���闭信号量
     ///
     /// 关闭后，所有等待的任务会被唤醒并收到错误。
     pub fn close(&self) {
-        self.inner.closed.store(true, Ordering::Release);
-        
-        // 唤醒所有同步等待者
-        let task_ids: [Option<usize>; 16];
-        {
-            let mut waiters = self.inner.waiters.lock();
-            task_ids = waiters.drain();
-        }
-
-        for task_id in task_ids.iter().filter_map(|&id| id) {
-            Task::for_each(|task, id| {
-                if id == task_id {
-                    if let TaskState::Blocked(_) = task.get_state() {
-                        task.ready();
-                    }
-                }
-            });
-        }
-
-        // 唤醒所有异步等待者
-        let async_wakers: VecDeque<Waker>;
-        {
-            let mut async_waiters = self.inner.async_waiters.lock();
-            async_wakers = core::mem::take(&mut *async_waiters);
-        }
-
-        for waker in async_wakers {
-            waker.wake();
-        }
+        self.inner.close();
     }
 
     /// 检查是否已关闭
@@ -412,15 +341,21 @@ impl Semaphore {
 
     /// 获取等待者数量
     pub fn waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len() + self.inner.async_waiters.lock().len()
+        self.inner.waiter_count()
     }
 
     /// 获取唯一标识（用于调试）
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
+    }
+
+    /// 获取同步等待队列（内部使用）
+    pub(crate) fn wait_queue(&self) -> &WaitQueue {
+        &self.inner.waiters
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for Semaphore {
     fn default() -> Self {
         Self::new(1)
@@ -439,6 +374,127 @@ impl core::fmt::Debug for Semaphore {
     }
 }
 
+// ============================================================================
+// StaticSemaphore - 静态计数信号量
+// ============================================================================
+
+/// 不需要堆分配、可以放在 `static` 中的计数信号量
+///
+/// 与 [`Semaphore`] 使用相同的等待队列和唤醒逻辑，但直接持有内部状态而不是 `Arc`，
+/// 占用的内存在编译时确定。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticSemaphore;
+///
+/// // 最多 2 个任务同时使用 DMA 通道
+/// static DMA_CHANNELS: StaticSemaphore = StaticSemaphore::with_max(2, 2);
+///
+/// fn transfer() {
+///     DMA_CHANNELS.acquire().unwrap();
+///     // 使用 DMA 通道...
+///     DMA_CHANNELS.release().unwrap();
+/// }
+/// ```
+pub struct StaticSemaphore {
+    inner: SemaphoreInner,
+}
+
+impl StaticSemaphore {
+    /// 创建新的静态计数信号量
+    pub const fn new(initial_permits: usize) -> Self {
+        Self { inner: SemaphoreInner::new(initial_permits, 0) }
+    }
+
+    /// 创建带最大限制的静态计数信号量
+    pub const fn with_max(initial_permits: usize, max_permits: usize) -> Self {
+        let permits = if initial_permits < max_permits { initial_permits } else { max_permits };
+        Self { inner: SemaphoreInner::new(permits, max_permits) }
+    }
+
+    /// 获取指向这个静态信号量的 [`Semaphore`] 句柄，不需要 `alloc`
+    pub fn handle(&'static self) -> Semaphore {
+        Semaphore { inner: Shared::from_static(&self.inner) }
+    }
+
+    /// 获取一个许可，见 [`Semaphore::acquire`]
+    pub fn acquire(&self) -> Result<()> {
+        self.inner.acquire(1, None)
+    }
+
+    /// 获取多个许可
+    pub fn acquire_many(&self, n: usize) -> Result<()> {
+        self.inner.acquire(n, None)
+    }
+
+    /// 带超时的获取许可
+    pub fn acquire_timeout(&self, timeout_ms: usize) -> Result<()> {
+        self.inner.acquire(1, Some(timeout_ms))
+    }
+
+    /// 尝试获取一个许可（非阻塞）
+    pub fn try_acquire(&self) -> Result<bool> {
+        self.inner.try_acquire(1)
+    }
+
+    /// 尝试获取多个许可（非阻塞）
+    pub fn try_acquire_many(&self, n: usize) -> Result<bool> {
+        self.inner.try_acquire(n)
+    }
+
+    /// 释放一个许可
+    pub fn release(&self) -> Result<()> {
+        self.inner.release(1)
+    }
+
+    /// 释放多个许可
+    pub fn release_many(&self, n: usize) -> Result<()> {
+        self.inner.release(n)
+    }
+
+    /// 关闭信号量，唤醒所有等待者
+    pub fn close(&self) {
+        self.inner.close();
+    }
+
+    /// 检查是否已关闭
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
+    /// 获取当前可用许可数
+    pub fn available_permits(&self) -> usize {
+        self.inner.permits.load(Ordering::Relaxed)
+    }
+
+    /// 获取最大许可数（0 表示无限制）
+    pub fn max_permits(&self) -> usize {
+        self.inner.max_permits
+    }
+
+    /// 获取等待者数量
+    pub fn waiter_count(&self) -> usize {
+        self.inner.waiter_count()
+    }
+
+    /// 获取唯一标识（用于调试）
+    pub fn id(&self) -> usize {
+        &self.inner as *const SemaphoreInner as usize
+    }
+}
+
+impl core::fmt::Debug for StaticSemaphore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticSemaphore")
+            .field("id", &self.id())
+            .field("permits", &self.available_permits())
+            .field("max_permits", &self.max_permits())
+            .field("waiters", &self.waiter_count())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
 // ============================================================================
 // RAII 许可守卫
 // ============================================================================
@@ -506,7 +562,7 @@ impl core::fmt::Debug for SemaphorePermit<'_> {
 /// drop(permit); // 显式释放
 /// ```
 pub struct OwnedSemaphorePermit {
-    semaphore: Arc<SemaphoreInner>,
+    semaphore: Shared<SemaphoreInner>,
     permits: usize,
 }
 
@@ -530,8 +586,8 @@ impl OwnedSemaphorePermit {
     /// 两个许可必须来自同一个信号量。
     pub fn merge(&mut self, other: OwnedSemaphorePermit) {
         assert_eq!(
-            Arc::as_ptr(&self.semaphore),
-            Arc::as_ptr(&other.semaphore),
+            Shared::as_ptr(&self.semaphore),
+            Shared::as_ptr(&other.semaphore),
             "Cannot merge permits from different semaphores"
         );
         self.permits += other.permits;
@@ -552,7 +608,7 @@ impl OwnedSemaphorePermit {
         }
         self.permits -= n;
         Some(OwnedSemaphorePermit {
-            semaphore: Arc::clone(&self.semaphore),
+            semaphore: Shared::clone(&self.semaphore),
             permits: n,
         })
     }
@@ -565,32 +621,7 @@ impl Drop for OwnedSemaphorePermit {
             self.semaphore.permits.fetch_add(self.permits, Ordering::Release);
 
             // 唤醒等待者
-            for _ in 0..self.permits {
-                let task_id = {
-                    let mut waiters = self.semaphore.waiters.lock();
-                    waiters.pop_front()
-                };
-
-                if let Some(task_id) = task_id {
-                    Task::for_each(|task, id| {
-                        if id == task_id {
-                            if let TaskState::Blocked(_) = task.get_state() {
-                                task.ready();
-                            }
-                        }
-                    });
-                    continue;
-                }
-
-                let waker = {
-                    let mut async_waiters = self.semaphore.async_waiters.lock();
-                    async_waiters.pop_front()
-                };
-
-                if let Some(waker) = waker {
-                    waker.wake();
-                }
-            }
+            self.semaphore.wake_waiters(self.permits);
         }
     }
 }
@@ -614,7 +645,7 @@ impl Semaphore {
     pub fn acquire_owned(&self) -> Result<OwnedSemaphorePermit> {
         self.acquire()?;
         Ok(OwnedSemaphorePermit {
-            semaphore: Arc::clone(&self.inner),
+            semaphore: Shared::clone(&self.inner),
             permits: 1,
         })
     }
@@ -623,7 +654,7 @@ impl Semaphore {
     pub fn acquire_many_owned(&self, n: usize) -> Result<OwnedSemaphorePermit> {
         self.acquire_many(n)?;
         Ok(OwnedSemaphorePermit {
-            semaphore: Arc::clone(&self.inner),
+            semaphore: Shared::clone(&self.inner),
             permits: n,
         })
     }
@@ -632,7 +663,7 @@ impl Semaphore {
     pub fn try_acquire_owned(&self) -> Result<Option<OwnedSemaphorePermit>> {
         if self.try_acquire()? {
             Ok(Some(OwnedSemaphorePermit {
-                semaphore: Arc::clone(&self.inner),
+                semaphore: Shared::clone(&self.inner),
                 permits: 1,
             }))
         } else {
@@ -644,7 +675,7 @@ impl Semaphore {
     pub fn try_acquire_many_owned(&self, n: usize) -> Result<Option<OwnedSemaphorePermit>> {
         if self.try_acquire_many(n)? {
             Ok(Some(OwnedSemaphorePermit {
-                semaphore: Arc::clone(&self.inner),
+                semaphore: Shared::clone(&self.inner),
                 permits: n,
             }))
         } else {
@@ -656,7 +687,7 @@ impl Semaphore {
     pub fn acquire_owned_timeout(&self, timeout_ms: usize) -> Result<OwnedSemaphorePermit> {
         self.acquire_timeout(timeout_ms)?;
         Ok(OwnedSemaphorePermit {
-            semaphore: Arc::clone(&self.inner),
+            semaphore: Shared::clone(&self.inner),
             permits: 1,
         })
     }
@@ -677,6 +708,7 @@ impl Semaphore {
 ///
 /// let sem = semaphore(5);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn semaphore(permits: usize) -> Semaphore {
     Semaphore::new(permits)
 }
@@ -693,6 +725,7 @@ pub fn semaphore(permits: usize) -> Semaphore {
 ///
 /// let sem = semaphore_with_max(3, 5);
 /// ```
+#[cfg(feature = "alloc")]
 pub fn semaphore_with_max(initial: usize, max: usize) -> Semaphore {
     Semaphore::with_max(initial, max)
 }
@@ -713,6 +746,7 @@ pub fn semaphore_with_max(initial: usize, max: usize) -> Semaphore {
 /// // 现在不可用
 /// sem.release().unwrap();
 /// ```
+#[cfg(feature = "alloc")]
 pub fn binary_semaphore(available: bool) -> Semaphore {
     Semaphore::with_max(if available { 1 } else { 0 }, 1)
 }
@@ -811,8 +845,7 @@ impl<'a> core::future::Future for SemaphoreAcquireFuture<'a> {
 
         if !self.registered {
             let mut async_waiters = self.semaphore.inner.async_waiters.lock();
-            async_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_waiters.push_back(cx.waker().clone());
         }
 
         core::task::Poll::Pending
@@ -838,6 +871,7 @@ mod tests {
     use crate::utils::kernel_init;
     use serial_test::serial;
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_basic() {
@@ -870,6 +904,7 @@ mod tests {
         assert!(sem.try_acquire().unwrap());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_with_max() {
@@ -890,6 +925,7 @@ mod tests {
         assert!(sem.release().is_err());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_clone() {
@@ -909,6 +945,7 @@ mod tests {
         assert_eq!(sem1.available_permits(), 2);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_permit() {
@@ -926,6 +963,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 1);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_close() {
@@ -941,6 +979,7 @@ mod tests {
         assert!(sem.try_acquire().is_err());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_acquire_many() {
@@ -959,6 +998,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 5);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_owned_permit() {
@@ -977,6 +1017,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 3);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_owned_permit_many() {
@@ -994,6 +1035,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 5);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_owned_permit_merge() {
@@ -1018,6 +1060,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 5);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_owned_permit_split() {
@@ -1044,6 +1087,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 5);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_owned_permit_forget() {
@@ -1061,6 +1105,7 @@ mod tests {
         assert_eq!(sem.available_permits(), 2); // 仍然是 2
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_v2_try_acquire_owned() {
@@ -1084,6 +1129,7 @@ mod tests {
         assert!(permit3.is_some());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_binary_semaphore() {
@@ -1111,6 +1157,7 @@ mod tests {
         assert!(sem.release().is_err());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_semaphore_convenience_functions() {
@@ -1127,5 +1174,23 @@ mod tests {
         assert_eq!(sem2.available_permits(), 2);
         assert_eq!(sem2.max_permits(), 5);
     }
-}
 
+    #[test]
+    #[serial]
+    fn test_static_semaphore() {
+        static SEM: StaticSemaphore = StaticSemaphore::with_max(3, 2);
+
+        kernel_init();
+        Task::new("test", |_| {}).unwrap();
+        crate::kernel::scheduler::Scheduler::start();
+
+        assert_eq!(SEM.available_permits(), 2);
+        SEM.acquire().unwrap();
+        assert_eq!(SEM.try_acquire_many(2), Ok(false));
+        assert_eq!(SEM.try_acquire(), Ok(true));
+        SEM.release_many(2).unwrap();
+        assert_eq!(SEM.release(), Err(RtosError::SemaphoreOverflow));
+        assert_eq!(SEM.acquire_timeout(10), Ok(()));
+        SEM.release().unwrap();
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! ## 设计思路
 //!
-//! 使用 `Arc<SignalInner>` 的内存地址作为唯一标识，
+//! 使用 `Shared<SignalInner>` 的内存地址作为唯一标识，
 //! 可以像 `std::sync::mpsc::channel` 一样在局部创建并传递给任务。
 //!
 //! ## 使用示例
@@ -37,28 +37,24 @@
 //! }
 //! ```
 
-use crate::compat::{Arc, VecDeque};
-use crate::kernel::scheduler::Scheduler;
-use crate::kernel::task::{Task, TaskState};
+use crate::compat::Shared;
 use crate::kernel::time::systick::Systick;
 use crate::hal::trigger_schedule;
 use crate::error::{Result, RtosError};
+use crate::sync::wait_queue::{WaitQueue, WakerQueue};
 use core::sync::atomic::{AtomicUsize, AtomicBool, Ordering};
-use core::task::Waker;
 use spin::Mutex;
 
 /// 信号量内部状态
 struct SignalInner {
     /// 信号计数（用于计数信号量场景）
     count: AtomicUsize,
-    /// 等待者列表（存储被阻塞任务的 ID）- 用于同步等待
-    waiters: Mutex<WaiterList>,
+    /// 等待队列 - 用于同步等待
+    waiters: WaitQueue,
     /// 异步等待者列表（存储 Waker）- 用于异步等待
-    async_waiters: Mutex<VecDeque<Waker>>,
+    async_waiters: Mutex<WakerQueue>,
     /// 是否已关闭
     closed: AtomicBool,
-    /// 超时时间点（用于超时等待）
-    timeout: AtomicUsize,
 }
 
 /// 等待者列表
@@ -341,25 +337,142 @@ impl Default for WaiterList {
 }
 
 impl SignalInner {
-    fn new() -> Self {
-        Self {
-            count: AtomicUsize::new(0),
-            waiters: Mutex::new(WaiterList::new()),
-            async_waiters: Mutex::new(VecDeque::new()),
-            closed: AtomicBool::new(false),
-            timeout: AtomicUsize::new(0),
-        }
+    const fn new() -> Self {
+        Self::new_with_count(0)
     }
 
-    fn new_with_count(initial_count: usize) -> Self {
+    const fn new_with_count(initial_count: usize) -> Self {
         Self {
             count: AtomicUsize::new(initial_count),
-            waiters: Mutex::new(WaiterList::new()),
-            async_waiters: Mutex::new(VecDeque::new()),
+            waiters: WaitQueue::new(),
+            async_waiters: Mutex::new(WakerQueue::new()),
             closed: AtomicBool::new(false),
-            timeout: AtomicUsize::new(0),
         }
     }
+
+    /// 唤醒一个等待者，没有等待者时增加计数
+    fn send(&self) {
+        if self.closed.load(Ordering::Acquire) {
+            return; // 信号量已关闭，忽略发送
+        }
+
+        // 首先尝试唤醒同步等待者（FIFO 顺序）
+        if self.waiters.wake_one().is_some() {
+            return;
+        }
+
+        // 然后尝试唤醒异步等待者
+        let mut async_waiters = self.async_waiters.lock();
+        if let Some(waker) = async_waiters.pop_front() {
+            drop(async_waiters);
+            waker.wake();
+            return;
+        }
+        drop(async_waiters);
+
+        // 没有任何等待者，增加计数
+        self.count.fetch_add(1, Ordering::Release);
+    }
+
+    /// 唤醒所有等待者
+    fn broadcast(&self) {
+        if self.closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        // 唤醒所有同步等待者
+        self.waiters.wake_all();
+
+        // 唤醒所有异步等待者
+        let async_wakers: WakerQueue;
+        {
+            let mut async_waiters = self.async_waiters.lock();
+            async_wakers = core::mem::take(&mut *async_waiters);
+        }
+
+        for waker in async_wakers {
+            waker.wake();
+        }
+    }
+
+    /// 有待处理的信号时消费一个
+    fn try_wait(&self) -> Result<bool> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(RtosError::SignalClosed);
+        }
+
+        loop {
+            let count = self.count.load(Ordering::Acquire);
+            if count > 0 {
+                if self.count.compare_exchange(
+                    count,
+                    count - 1,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                ).is_ok() {
+                    return Ok(true);
+                }
+                // CAS 失败，重试
+                continue;
+            }
+            return Ok(false);
+        }
+    }
+
+    /// 等待信号
+    ///
+    /// 在等待队列锁内检查关闭状态和信号计数，没有信号时阻塞。
+    fn wait(&self, timeout_ms: Option<usize>) -> Result<()> {
+        self.waiters.wait_if(timeout_ms, || {
+            if self.closed.load(Ordering::Acquire) {
+                return Err(RtosError::SignalClosed);
+            }
+            // 有待处理的信号时消费一个，不需要阻塞
+            Ok(!self.try_wait()?)
+        })?;
+
+        // 被唤醒后检查是否因为关闭而唤醒
+        if self.closed.load(Ordering::Acquire) {
+            return Err(RtosError::SignalClosed);
+        }
+        Ok(())
+    }
+
+    /// 在指定时间内反复尝试获取信号
+    fn try_wait_timeout(&self, timeout_ms: usize) -> Result<bool> {
+        let deadline = Systick::get_current_time() + timeout_ms;
+        
+        loop {
+            match self.try_wait() {
+                Ok(true) => return Ok(true),
+                Ok(false) => {
+                    if Systick::get_current_time() >= deadline {
+                        return Ok(false);
+                    }
+                    // 继续轮询
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 关闭并唤醒所有等待者
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        // 唤醒所有等待者
+        self.broadcast();
+    }
+
+    /// 清除待处理的信号并重新打开
+    fn reset(&self) {
+        self.count.store(0, Ordering::Release);
+        self.closed.store(false, Ordering::Release);
+    }
+
+    /// 同步和异步等待者的总数
+    fn waiter_count(&self) -> usize {
+        self.waiters.len() + self.async_waiters.lock().len()
+    }
 }
 
 /// 可克隆、可传递的信号量
@@ -367,7 +480,7 @@ impl SignalInner {
 /// 类似于 `std::sync::mpsc::Sender`，可以 clone 后 move 到不同任务中。
 #[derive(Clone)]
 pub struct Signal {
-    inner: Arc<SignalInner>,
+    inner: Shared<SignalInner>,
 }
 
 impl Signal {
@@ -380,9 +493,10 @@ impl Signal {
     /// let signal = Signal::new();
     /// let signal_clone = signal.clone(); // 可以克隆
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(SignalInner::new()),
+            inner: Shared::new(SignalInner::new()),
         }
     }
 
@@ -399,9 +513,10 @@ impl Signal {
     /// let signal = Signal::with_count(3);
     /// assert!(signal.try_wait()); // 立即成功
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn with_count(initial_count: usize) -> Self {
         Self {
-            inner: Arc::new(SignalInner::new_with_count(initial_count)),
+            inner: Shared::new(SignalInner::new_with_count(initial_count)),
         }
     }
 
@@ -417,32 +532,7 @@ impl Signal {
     /// signal.send(); // 发送信号
     /// ```
     pub fn send(&self) {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return; // 信号量已关闭，忽略发送
-        }
-
-        // 首先尝试唤醒同步等待者
-        let mut waiters = self.inner.waiters.lock();
-        
-        if let Some(task_id) = waiters.pop_front() {
-            // 有同步等待者，直接唤醒（FIFO 顺序）
-            drop(waiters);
-            Self::wake_task_by_id(task_id);
-            return;
-        }
-        drop(waiters);
-
-        // 然后尝试唤醒异步等待者
-        let mut async_waiters = self.inner.async_waiters.lock();
-        if let Some(waker) = async_waiters.pop_front() {
-            drop(async_waiters);
-            waker.wake();
-            return;
-        }
-        drop(async_waiters);
-
-        // 没有任何等待者，增加计数
-        self.inner.count.fetch_add(1, Ordering::Release);
+        self.inner.send();
     }
 
     /// 发送信号并触发调度
@@ -466,31 +556,7 @@ impl Signal {
     /// signal.broadcast(); // 唤醒所有等待者
     /// ```
     pub fn broadcast(&self) {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return;
-        }
-
-        // 唤醒所有同步等待者
-        let task_ids: [Option<usize>; 16];
-        {
-            let mut waiters = self.inner.waiters.lock();
-            task_ids = waiters.drain();
-        }
-
-        for task_id in task_ids.iter().filter_map(|&id| id) {
-            Self::wake_task_by_id(task_id);
-        }
-
-        // 唤醒所有异步等待者
-        let async_wakers: VecDeque<Waker>;
-        {
-            let mut async_waiters = self.inner.async_waiters.lock();
-            async_wakers = core::mem::take(&mut *async_waiters);
-        }
-
-        for waker in async_wakers {
-            waker.wake();
-        }
+        self.inner.broadcast();
     }
 
     /// 广播信号并触发调度
@@ -518,61 +584,7 @@ impl Signal {
     /// }
     /// ```
     pub fn wait(&self) -> Result<()> {
-        // 检查是否已关闭
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SignalClosed);
-        }
-
-        // 先检查是否有待处理的信号
-        loop {
-            let count = self.inner.count.load(Ordering::Acquire);
-            if count > 0 {
-                // 尝试消费一个信号
-                if self.inner.count.compare_exchange(
-                    count,
-                    count - 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(()); // 成功获取信号，立即返回
-                }
-                // CAS 失败，重试
-                continue;
-            }
-            break;
-        }
-
-        // 没有待处理的信号，需要阻塞
-        let current = Scheduler::get_current_task();
-        let task_id = current.get_taskid();
-
-        // 将当前任务加入等待队列
-        {
-            let mut waiters = self.inner.waiters.lock();
-            
-            // 再次检查是否已关闭（双重检查）
-            if self.inner.closed.load(Ordering::Acquire) {
-                return Err(RtosError::SignalClosed);
-            }
-            
-            if !waiters.push(task_id) {
-                return Err(RtosError::WaiterQueueFull);
-            }
-        }
-
-        // 使用 Arc 的地址作为唯一标识
-        let signal_id = Arc::as_ptr(&self.inner) as usize;
-        
-        // 阻塞当前任务
-        Scheduler::get_current_task().block(crate::sync::event::Event::Signal(signal_id));
-        trigger_schedule();
-
-        // 被唤醒后检查是否因为关闭而唤醒
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SignalClosed);
-        }
-
-        Ok(())
+        self.inner.wait(None)
     }
 
     /// 尝试等待信号（非阻塞）
@@ -593,26 +605,7 @@ impl Signal {
     /// }
     /// ```
     pub fn try_wait(&self) -> Result<bool> {
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SignalClosed);
-        }
-
-        loop {
-            let count = self.inner.count.load(Ordering::Acquire);
-            if count > 0 {
-                if self.inner.count.compare_exchange(
-                    count,
-                    count - 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(true);
-                }
-                // CAS 失败，重试
-                continue;
-            }
-            return Ok(false);
-        }
+        self.inner.try_wait()
     }
 
     /// 带超时的等待信号
@@ -639,74 +632,7 @@ impl Signal {
     /// }
     /// ```
     pub fn wait_timeout(&self, timeout_ms: usize) -> Result<()> {
-        // 检查是否已关闭
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SignalClosed);
-        }
-
-        // 先检查是否有待处理的信号
-        loop {
-            let count = self.inner.count.load(Ordering::Acquire);
-            if count > 0 {
-                if self.inner.count.compare_exchange(
-                    count,
-                    count - 1,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                ).is_ok() {
-                    return Ok(()); // 成功获取信号，立即返回
-                }
-                continue;
-            }
-            break;
-        }
-
-        // 计算超时时间点
-        let deadline = Systick::get_current_time() + timeout_ms;
-
-        // 没有待处理的信号，需要阻塞
-        let current = Scheduler::get_current_task();
-        let task_id = current.get_taskid();
-
-        // 将当前任务加入等待队列
-        {
-            let mut waiters = self.inner.waiters.lock();
-            
-            // 再次检查是否已关闭
-            if self.inner.closed.load(Ordering::Acquire) {
-                return Err(RtosError::SignalClosed);
-            }
-            
-            if !waiters.push(task_id) {
-                return Err(RtosError::WaiterQueueFull);
-            }
-        }
-
-        // 设置超时时间
-        self.inner.timeout.store(deadline, Ordering::Release);
-
-        // 使用 Arc 的地址作为唯一标识
-        let signal_id = Arc::as_ptr(&self.inner) as usize;
-        
-        // 阻塞当前任务
-        Scheduler::get_current_task().block(crate::sync::event::Event::Signal(signal_id));
-        trigger_schedule();
-
-        // 被唤醒后检查原因
-        // 1. 检查是否因为关闭而唤醒
-        if self.inner.closed.load(Ordering::Acquire) {
-            return Err(RtosError::SignalClosed);
-        }
-
-        // 2. 检查是否超时
-        if Systick::get_current_time() >= deadline {
-            // 从等待队列中移除自己（可能已经被移除了）
-            let mut waiters = self.inner.waiters.lock();
-            waiters.remove(task_id);
-            return Err(RtosError::Timeout);
-        }
-
-        Ok(())
+        self.inner.wait(Some(timeout_ms))
     }
 
     /// 带超时的尝试等待（轮询模式）
@@ -722,20 +648,7 @@ impl Signal {
     /// - `Ok(false)`: 超时，未获取到信号
     /// - `Err(RtosError::SignalClosed)`: 信号量已关闭
     pub fn try_wait_timeout(&self, timeout_ms: usize) -> Result<bool> {
-        let deadline = Systick::get_current_time() + timeout_ms;
-        
-        loop {
-            match self.try_wait() {
-                Ok(true) => return Ok(true),
-                Ok(false) => {
-                    if Systick::get_current_time() >= deadline {
-                        return Ok(false);
-                    }
-                    // 继续轮询
-                }
-                Err(e) => return Err(e),
-            }
-        }
+        self.inner.try_wait_timeout(timeout_ms)
     }
 
     /// 关闭信号量
@@ -751,9 +664,7 @@ impl Signal {
     /// assert!(signal.wait().is_err());
     /// ```
     pub fn close(&self) {
-        self.inner.closed.store(true, Ordering::Release);
-        // 唤醒所有等待者
-        self.broadcast();
+        self.inner.close();
     }
 
     /// 检查信号量是否已关闭
@@ -766,8 +677,7 @@ impl Signal {
     /// 清除所有待处理的信号，重新打开信号量。
     /// 注意：不会影响正在等待的任务。
     pub fn reset(&self) {
-        self.inner.count.store(0, Ordering::Release);
-        self.inner.closed.store(false, Ordering::Release);
+        self.inner.reset();
     }
 
     /// 获取当前信号计数
@@ -777,12 +687,12 @@ impl Signal {
 
     /// 获取等待者数量（包括同步和异步等待者）
     pub fn waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len() + self.inner.async_waiters.lock().len()
+        self.inner.waiter_count()
     }
 
     /// 获取同步等待者数量
     pub fn sync_waiter_count(&self) -> usize {
-        self.inner.waiters.lock().len()
+        self.inner.waiters.len()
     }
 
     /// 获取异步等待者数量
@@ -792,21 +702,16 @@ impl Signal {
 
     /// 获取信号的唯一标识（用于调试）
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
     }
 
-    /// 内部辅助函数：根据任务 ID 唤醒任务
-    fn wake_task_by_id(task_id: usize) {
-        Task::for_each(|task, id| {
-            if id == task_id {
-                if let TaskState::Blocked(_) = task.get_state() {
-                    task.ready();
-                }
-            }
-        });
+    /// 获取同步等待队列（内部使用）
+    pub(crate) fn wait_queue(&self) -> &WaitQueue {
+        &self.inner.waiters
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for Signal {
     fn default() -> Self {
         Self::new()
@@ -824,6 +729,130 @@ impl core::fmt::Debug for Signal {
             .finish()
     }
 }
+
+// ============================================================================
+// StaticSignal - 静态信号
+// ============================================================================
+
+/// 不需要堆分配、可以放在 `static` 中的信号量
+///
+/// 与 [`Signal`] 使用相同的等待队列和唤醒逻辑，但直接持有内部状态而不是 `Arc`，
+/// 占用的内存在编译时确定。需要共享时传递 `&'static StaticSignal`。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticSignal;
+/// use neon_rtos2::kernel::task::Task;
+///
+/// static DATA_READY: StaticSignal = StaticSignal::new();
+///
+/// Task::builder("producer").spawn(|_| DATA_READY.send()).unwrap();
+/// Task::builder("consumer")
+///     .spawn(|_| {
+///         DATA_READY.wait().unwrap();
+///     })
+///     .unwrap();
+/// ```
+pub struct StaticSignal {
+    inner: SignalInner,
+}
+
+impl StaticSignal {
+    /// 创建新的静态信号
+    pub const fn new() -> Self {
+        Self { inner: SignalInner::new() }
+    }
+
+    /// 创建带初始计数的静态信号
+    pub const fn with_count(initial_count: usize) -> Self {
+        Self { inner: SignalInner::new_with_count(initial_count) }
+    }
+
+    /// 获取指向这个静态信号的 [`Signal`] 句柄
+    ///
+    /// 句柄可以克隆并通过闭包传递，不需要 `alloc`。
+    pub fn handle(&'static self) -> Signal {
+        Signal { inner: Shared::from_static(&self.inner) }
+    }
+
+    /// 发送信号，见 [`Signal::send`]
+    pub fn send(&self) {
+        self.inner.send();
+    }
+
+    /// 广播信号，唤醒所有等待者
+    pub fn broadcast(&self) {
+        self.inner.broadcast();
+    }
+
+    /// 等待信号，见 [`Signal::wait`]
+    pub fn wait(&self) -> Result<()> {
+        self.inner.wait(None)
+    }
+
+    /// 尝试等待信号（非阻塞）
+    pub fn try_wait(&self) -> Result<bool> {
+        self.inner.try_wait()
+    }
+
+    /// 带超时的等待信号
+    pub fn wait_timeout(&self, timeout_ms: usize) -> Result<()> {
+        self.inner.wait(Some(timeout_ms))
+    }
+
+    /// 带超时的尝试等待（轮询模式）
+    pub fn try_wait_timeout(&self, timeout_ms: usize) -> Result<bool> {
+        self.inner.try_wait_timeout(timeout_ms)
+    }
+
+    /// 关闭信号，唤醒所有等待者
+    pub fn close(&self) {
+        self.inner.close();
+    }
+
+    /// 检查是否已关闭
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::Acquire)
+    }
+
+    /// 清除所有待处理的信号，重新打开
+    pub fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// 获取当前信号计数
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::Relaxed)
+    }
+
+    /// 获取等待者数量（包括同步和异步等待者）
+    pub fn waiter_count(&self) -> usize {
+        self.inner.waiter_count()
+    }
+
+    /// 获取信号的唯一标识（用于调试）
+    pub fn id(&self) -> usize {
+        &self.inner as *const SignalInner as usize
+    }
+}
+
+impl Default for StaticSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for StaticSignal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticSignal")
+            .field("id", &self.id())
+            .field("count", &self.count())
+            .field("waiters", &self.waiter_count())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
 ///
 /// 这提供了更明确的 API，发送端只能发送，接收端只能接收。
 ///
@@ -839,6 +868,7 @@ impl core::fmt::Debug for Signal {
 /// // receiver 只能 wait
 /// receiver.wait();
 /// ```
+#[cfg(feature = "alloc")]
 pub fn signal_pair() -> (SignalSender, SignalReceiver) {
     let signal = Signal::new();
     (
@@ -1006,8 +1036,7 @@ impl<'a> core::future::Future for SignalFuture<'a> {
         // 没有信号，注册 waker
         if !self.registered {
             let mut async_waiters = self.signal.inner.async_waiters.lock();
-            async_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_waiters.push_back(cx.waker().clone());
         }
 
         core::task::Poll::Pending
@@ -1065,8 +1094,7 @@ impl<'a> core::future::Future for SignalTimeoutFuture<'a> {
         // 没有信号，注册 waker
         if !self.registered {
             let mut async_waiters = self.signal.inner.async_waiters.lock();
-            async_waiters.push_back(cx.waker().clone());
-            self.registered = true;
+            self.registered = async_waiters.push_back(cx.waker().clone());
         }
 
         core::task::Poll::Pending
@@ -1075,7 +1103,7 @@ impl<'a> core::future::Future for SignalTimeoutFuture<'a> {
 
 /// 拥有所有权的异步等待 Future
 ///
-/// 与 `SignalFuture` 不同，`OwnedSignalFuture` 持有 `Arc<SignalInner>` 的所有权，
+/// 与 `SignalFuture` 不同，`OwnedSignalFuture` 持有 `Shared<SignalInner>` 的所有权，
 /// 因此可以被存储在结构体中或跨 await 点持有。
 ///
 /// # 示例
@@ -1088,12 +1116,12 @@ impl<'a> core::future::Future for SignalTimeoutFuture<'a> {
 /// }
 /// ```
 pub struct OwnedSignalFuture {
-    inner: Arc<SignalInner>,
+    inner: Shared<SignalInner>,
     registered: bool,
 }
 
 impl OwnedSignalFuture {
-    fn new(inner: Arc<SignalInner>) -> Self {
+    fn new(inner: Shared<SignalInner>) -> Self {
         Self {
             inner,
             registered: false,
@@ -1129,11 +1157,8 @@ impl core::future::Future for OwnedSignalFuture {
 
         // 没有信号，注册 waker
         if !self.registered {
-            {
-                let mut async_waiters = self.inner.async_waiters.lock();
-                async_waiters.push_back(cx.waker().clone());
-            }
-            self.registered = true;
+            let registered = self.inner.async_waiters.lock().push_back(cx.waker().clone());
+            self.registered = registered;
         }
 
         core::task::Poll::Pending
@@ -1142,13 +1167,13 @@ impl core::future::Future for OwnedSignalFuture {
 
 /// 带超时的拥有所有权的异步等待 Future
 pub struct OwnedSignalTimeoutFuture {
-    inner: Arc<SignalInner>,
+    inner: Shared<SignalInner>,
     deadline: usize,
     registered: bool,
 }
 
 impl OwnedSignalTimeoutFuture {
-    fn new(inner: Arc<SignalInner>, timeout_ms: usize) -> Self {
+    fn new(inner: Shared<SignalInner>, timeout_ms: usize) -> Self {
         Self {
             inner,
             deadline: Systick::get_current_time() + timeout_ms,
@@ -1190,11 +1215,8 @@ impl core::future::Future for OwnedSignalTimeoutFuture {
 
         // 没有信号，注册 waker
         if !self.registered {
-            {
-                let mut async_waiters = self.inner.async_waiters.lock();
-                async_waiters.push_back(cx.waker().clone());
-            }
-            self.registered = true;
+            let registered = self.inner.async_waiters.lock().push_back(cx.waker().clone());
+            self.registered = registered;
         }
 
         core::task::Poll::Pending
@@ -1279,21 +1301,23 @@ impl SignalReceiver {
 /// ```
 #[derive(Clone)]
 pub struct OwnedSignal {
-    inner: Arc<SignalInner>,
+    inner: Shared<SignalInner>,
 }
 
 impl OwnedSignal {
     /// 创建新的拥有所有权的信号量
+    #[cfg(feature = "alloc")]
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(SignalInner::new()),
+            inner: Shared::new(SignalInner::new()),
         }
     }
 
     /// 创建带初始计数的信号量
+    #[cfg(feature = "alloc")]
     pub fn with_count(initial_count: usize) -> Self {
         Self {
-            inner: Arc::new(SignalInner::new_with_count(initial_count)),
+            inner: Shared::new(SignalInner::new_with_count(initial_count)),
         }
     }
 
@@ -1309,7 +1333,7 @@ impl OwnedSignal {
 
     /// 获取 Signal 引用
     pub fn as_signal(&self) -> Signal {
-        Signal { inner: Arc::clone(&self.inner) }
+        Signal { inner: Shared::clone(&self.inner) }
     }
 
     /// 发送信号
@@ -1346,12 +1370,12 @@ impl OwnedSignal {
     /// 
     /// 注意：由于生命周期限制，OwnedSignal 的异步等待需要使用 OwnedSignalFuture
     pub fn wait_async(&self) -> OwnedSignalFuture {
-        OwnedSignalFuture::new(Arc::clone(&self.inner))
+        OwnedSignalFuture::new(Shared::clone(&self.inner))
     }
 
     /// 带超时的异步等待信号
     pub fn wait_async_timeout(&self, timeout_ms: usize) -> OwnedSignalTimeoutFuture {
-        OwnedSignalTimeoutFuture::new(Arc::clone(&self.inner), timeout_ms)
+        OwnedSignalTimeoutFuture::new(Shared::clone(&self.inner), timeout_ms)
     }
 
     /// 关闭信号量
@@ -1371,10 +1395,11 @@ impl OwnedSignal {
 
     /// 获取唯一标识
     pub fn id(&self) -> usize {
-        Arc::as_ptr(&self.inner) as usize
+        Shared::as_ptr(&self.inner) as usize
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for OwnedSignal {
     fn default() -> Self {
         Self::new()
@@ -1406,6 +1431,7 @@ impl core::fmt::Debug for OwnedSignal {
 /// let sig = signal();
 /// sig.send();
 /// ```
+#[cfg(feature = "alloc")]
 pub fn signal() -> Signal {
     Signal::new()
 }
@@ -1419,6 +1445,7 @@ pub fn signal() -> Signal {
 /// let sig = signal_with_count(3);
 /// assert!(sig.try_wait().unwrap()); // 立即成功
 /// ```
+#[cfg(feature = "alloc")]
 pub fn signal_with_count(count: usize) -> Signal {
     Signal::with_count(count)
 }
@@ -1446,6 +1473,7 @@ pub fn signal_with_count(count: usize) -> Signal {
 ///     receiver.wait().unwrap();
 /// }
 /// ```
+#[cfg(feature = "alloc")]
 pub fn mpsc_signal(sender_count: usize) -> (crate::compat::Vec<SignalSender>, SignalReceiver) {
     let signal = Signal::new();
     let senders: crate::compat::Vec<SignalSender> = (0..sender_count)
@@ -1471,6 +1499,7 @@ pub fn mpsc_signal(sender_count: usize) -> (crate::compat::Vec<SignalSender>, Si
 /// // 广播唤醒所有接收者
 /// sender.broadcast();
 /// ```
+#[cfg(feature = "alloc")]
 pub fn broadcast_signal(receiver_count: usize) -> (SignalSender, crate::compat::Vec<SignalReceiver>) {
     let signal = Signal::new();
     let sender = SignalSender { inner: signal.clone() };
@@ -1480,12 +1509,41 @@ pub fn broadcast_signal(receiver_count: usize) -> (SignalSender, crate::compat::
     (sender, receivers)
 }
 
+/// 在已有信号上创建 `N` 个发送端和一个接收端
+///
+/// 与 [`mpsc_signal`] 相同，但发送端放在数组中，不需要 `alloc`。
+///
+/// # 示例
+/// ```rust,no_run
+/// use neon_rtos2::sync::StaticSignal;
+/// use neon_rtos2::sync::signal::mpsc_signal_from;
+///
+/// static DONE: StaticSignal = StaticSignal::new();
+///
+/// let ([a, b], receiver) = mpsc_signal_from::<2>(DONE.handle());
+/// a.send();
+/// b.send();
+/// ```
+pub fn mpsc_signal_from<const N: usize>(signal: Signal) -> ([SignalSender; N], SignalReceiver) {
+    let senders = core::array::from_fn(|_| SignalSender { inner: signal.clone() });
+    (senders, SignalReceiver { inner: signal })
+}
+
+/// 在已有信号上创建一个发送端和 `N` 个接收端
+///
+/// 与 [`broadcast_signal`] 相同，但接收端放在数组中，不需要 `alloc`。
+pub fn broadcast_signal_from<const N: usize>(signal: Signal) -> (SignalSender, [SignalReceiver; N]) {
+    let receivers = core::array::from_fn(|_| SignalReceiver { inner: signal.clone() });
+    (SignalSender { inner: signal }, receivers)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::kernel_init;
     use serial_test::serial;
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_basic() {
@@ -1506,6 +1564,7 @@ mod tests {
         assert_eq!(signal.count(), 0);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_with_count() {
@@ -1522,6 +1581,7 @@ mod tests {
         assert_eq!(signal.try_wait().unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_clone() {
@@ -1538,6 +1598,7 @@ mod tests {
         assert_eq!(signal2.try_wait().unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_multiple_signals() {
@@ -1559,6 +1620,7 @@ mod tests {
         assert_eq!(signal.try_wait().unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_pair() {
@@ -1570,6 +1632,7 @@ mod tests {
         assert_eq!(receiver.try_wait().unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_different_instances() {
@@ -1587,6 +1650,7 @@ mod tests {
         assert_eq!(signal1.try_wait().unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_close() {
@@ -1617,6 +1681,7 @@ mod tests {
         assert_eq!(signal.try_wait().unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_pair_close() {
@@ -1687,6 +1752,7 @@ mod tests {
         assert_eq!(drained[3], None);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_debug() {
@@ -1701,6 +1767,7 @@ mod tests {
         assert!(debug_str.contains("closed"));
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_v2_try_wait_timeout() {
@@ -1719,6 +1786,7 @@ mod tests {
         assert_eq!(result.unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_receiver_timeout() {
@@ -1737,6 +1805,7 @@ mod tests {
         assert_eq!(result.unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_owned_signal() {
@@ -1753,6 +1822,7 @@ mod tests {
         assert_eq!(signal_clone.try_wait().unwrap(), true);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_owned_signal_with_count() {
@@ -1766,6 +1836,7 @@ mod tests {
         assert_eq!(signal.try_wait().unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_signal_convenience_functions() {
@@ -1781,6 +1852,7 @@ mod tests {
         assert_eq!(sig2.count(), 3);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_mpsc_signal() {
@@ -1801,6 +1873,7 @@ mod tests {
         assert_eq!(receiver.try_wait().unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_broadcast_signal() {
@@ -1818,6 +1891,7 @@ mod tests {
         assert_eq!(receivers[1].try_wait().unwrap(), false);
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     #[serial]
     fn test_owned_signal_conversion() {
@@ -1835,5 +1909,52 @@ mod tests {
         let signal_v2_back = owned.into_signal();
         assert_eq!(signal_v2_back.try_wait().unwrap(), true);
     }
-}
 
+    #[test]
+    #[serial]
+    fn test_static_signal() {
+        use crate::kernel::scheduler::Scheduler;
+        use crate::kernel::task::{Task, TaskState};
+        use crate::sync::event::Event;
+
+        static SIGNAL: StaticSignal = StaticSignal::with_count(1);
+
+        kernel_init();
+        let mut waiter = Task::new("waiter", |_| {}).unwrap();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        assert_eq!(SIGNAL.try_wait(), Ok(true));
+        assert_eq!(SIGNAL.try_wait(), Ok(false));
+
+        // send 唤醒等待者而不是增加计数
+        waiter.block(Event::WaitQueue(SIGNAL.inner.waiters.id()));
+        assert!(SIGNAL.inner.waiters.enqueue(waiter));
+        assert_eq!(SIGNAL.waiter_count(), 1);
+        SIGNAL.send();
+        assert_eq!(waiter.get_state(), TaskState::Ready);
+        assert_eq!(SIGNAL.count(), 0);
+
+        SIGNAL.close();
+        assert_eq!(SIGNAL.wait(), Err(RtosError::SignalClosed));
+        SIGNAL.reset();
+    }
+
+    #[test]
+    #[serial]
+    fn test_static_signal_handle_and_mpsc_from() {
+        static DONE: StaticSignal = StaticSignal::new();
+
+        kernel_init();
+        let handle = DONE.handle();
+        assert_eq!(handle.id(), DONE.handle().id());
+
+        let ([a, b], receiver) = mpsc_signal_from::<2>(handle);
+        a.send();
+        b.send();
+        assert_eq!(DONE.count(), 2);
+        assert_eq!(receiver.try_wait(), Ok(true));
+        assert_eq!(DONE.count(), 1);
+        DONE.reset();
+    }
+}
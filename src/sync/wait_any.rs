@@ -0,0 +1,384 @@
+//! # wait_any - 同时等待多个内核对象
+//!
+//! 阻塞当前任务，直到若干 `Signal`、`Semaphore`、`Mq` 或定时器中的任意一个就绪，
+//! 返回就绪对象在列表中的下标。相当于同步任务中的 `runtime::select`。
+//!
+//! | 对象 | 就绪条件 | 就绪时的动作 |
+//! |------|----------|--------------|
+//! | `Signal` | 有待处理的信号 | 消费一个信号 |
+//! | `Semaphore` | 有可用许可 | 获取一个许可（用完需要 `release`） |
+//! | `Mq` | 队列非空 | 无（由调用者 `pop`） |
+//! | `Timer` | 定时器超时 | 无 |
+//!
+//! 多个对象同时就绪时返回下标最小的一个。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use neon_rtos2::sync::{wait_any, Signal, Semaphore};
+//! use neon_rtos2::ipc::Mq;
+//! use neon_rtos2::error::RtosError;
+//!
+//! let command = Signal::new();
+//! let credits = Semaphore::new(0);
+//! let mut uplink: Mq<u32, 8> = Mq::new().unwrap();
+//!
+//! match wait_any(&[(&command).into(), (&credits).into(), (&uplink).into()], Some(100)) {
+//!     Ok(0) => { /* 收到命令 */ }
+//!     Ok(1) => { /* 获得一个许可 */ credits.release().unwrap(); }
+//!     Ok(_) => { let _frame = uplink.pop(); }
+//!     Err(RtosError::Timeout) => { /* 100ms 内没有任何事件 */ }
+//!     Err(_) => {}
+//! }
+//! ```
+
+use crate::error::{Result, RtosError};
+use crate::hal::trigger_schedule;
+use crate::ipc::queue::Mq;
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::task::{Task, TaskState};
+use crate::kernel::time::systick::Systick;
+use crate::kernel::time::timer::Timer;
+use crate::sync::event::Event;
+use crate::sync::semaphore::Semaphore;
+use crate::sync::signal::Signal;
+use crate::sync::wait_queue::WaitQueue;
+
+/// 可以被 `wait_any` 等待的对象
+///
+/// 通过 `From` 从 `&Signal`、`&Semaphore`、`&Mq` 和 `&Timer` 创建。
+#[derive(Clone, Copy)]
+pub struct Waitable<'a> {
+    kind: WaitableKind<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum WaitableKind<'a> {
+    Signal(&'a Signal),
+    Semaphore(&'a Semaphore),
+    Mq(&'a dyn MqWaitable),
+    Timer(&'a Timer),
+}
+
+/// 消息队列的类型擦除接口
+trait MqWaitable {
+    fn has_messages(&self) -> bool;
+    fn wait_queue(&self) -> &WaitQueue;
+}
+
+impl<T, const N: usize> MqWaitable for Mq<T, N>
+where
+    T: Copy + Default + Sized,
+{
+    fn has_messages(&self) -> bool {
+        !self.is_empty()
+    }
+
+    fn wait_queue(&self) -> &WaitQueue {
+        Mq::wait_queue(self)
+    }
+}
+
+impl<'a> From<&'a Signal> for Waitable<'a> {
+    fn from(signal: &'a Signal) -> Self {
+        Self { kind: WaitableKind::Signal(signal) }
+    }
+}
+
+impl<'a> From<&'a Semaphore> for Waitable<'a> {
+    fn from(semaphore: &'a Semaphore) -> Self {
+        Self { kind: WaitableKind::Semaphore(semaphore) }
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a Mq<T, N>> for Waitable<'a>
+where
+    T: Copy + Default + Sized,
+{
+    fn from(mq: &'a Mq<T, N>) -> Self {
+        Self { kind: WaitableKind::Mq(mq) }
+    }
+}
+
+impl<'a> From<&'a Timer> for Waitable<'a> {
+    fn from(timer: &'a Timer) -> Self {
+        Self { kind: WaitableKind::Timer(timer) }
+    }
+}
+
+impl Waitable<'_> {
+    /// 检查对象是否就绪，就绪时消费信号或获取许可
+    fn poll(&self) -> Result<bool> {
+        match self.kind {
+            WaitableKind::Signal(signal) => signal.try_wait(),
+            WaitableKind::Semaphore(semaphore) => semaphore.try_acquire(),
+            WaitableKind::Mq(mq) => Ok(mq.has_messages()),
+            WaitableKind::Timer(timer) => Ok(timer.is_timeout()),
+        }
+    }
+
+    /// 对象的等待队列（定时器没有等待队列）
+    fn wait_queue(&self) -> Option<&WaitQueue> {
+        match self.kind {
+            WaitableKind::Signal(signal) => Some(signal.wait_queue()),
+            WaitableKind::Semaphore(semaphore) => Some(semaphore.wait_queue()),
+            WaitableKind::Mq(mq) => Some(mq.wait_queue()),
+            WaitableKind::Timer(_) => None,
+        }
+    }
+
+    /// 定时器的超时时刻
+    fn expires_at(&self) -> Option<usize> {
+        match self.kind {
+            WaitableKind::Timer(timer) => timer.expires_at(),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Debug for Waitable<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            WaitableKind::Signal(signal) => f.debug_tuple("Signal").field(&signal.id()).finish(),
+            WaitableKind::Semaphore(semaphore) => f.debug_tuple("Semaphore").field(&semaphore.id()).finish(),
+            WaitableKind::Mq(_) => f.debug_tuple("Mq").finish(),
+            WaitableKind::Timer(timer) => f.debug_tuple("Timer").field(&timer.get_id()).finish(),
+        }
+    }
+}
+
+/// 阻塞当前任务，直到任意一个对象就绪或超时
+///
+/// # 参数
+/// - `objects`: 要等待的对象
+/// - `timeout_ms`: 超时时间（毫秒），`None` 表示一直等待
+///
+/// # 返回值
+/// - `Ok(index)`: 就绪对象在 `objects` 中的下标
+/// - `Err(RtosError::Timeout)`: 等待超时
+/// - `Err(RtosError::InvalidArgument)`: `objects` 为空
+/// - `Err(RtosError::WaiterQueueFull)`: 某个对象的等待队列已满
+/// - `Err(RtosError::TimerSlotsFull)`: 需要超时唤醒时没有可用的定时器
+/// - `Err(RtosError::SignalClosed)` / `Err(RtosError::SemaphoreClosed)`: 等待的对象已关闭
+/// - `Err(RtosError::WaitAborted)`: 阻塞期间任务被挂起
+pub fn wait_any(objects: &[Waitable<'_>], timeout_ms: Option<usize>) -> Result<usize> {
+    if objects.is_empty() {
+        return Err(RtosError::InvalidArgument);
+    }
+
+    let mut current = Scheduler::get_current_task();
+    let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+
+    loop {
+        let now = Systick::get_current_time();
+
+        // 超时和定时器对象由一个内核定时器在最早的时刻唤醒
+        let wake_at = objects
+            .iter()
+            .filter_map(Waitable::expires_at)
+            .chain(deadline)
+            .min();
+        let timer = wake_at.filter(|at| *at > now).map(|at| {
+            Timer::new(at - now).and_then(|mut timer| {
+                timer.start_for(current)?;
+                Ok(timer)
+            })
+        });
+        let event = Event::WaitAny(current.get_taskid());
+
+        // 先进入阻塞状态再加入等待队列：之后的任何唤醒都会把任务设为就绪，不会丢失。
+        // 锁住调度器，避免在加入所有等待队列之前被切换走。
+        let guard = Scheduler::lock_guard();
+        current.block(event);
+        let registered = register(objects, current);
+        let polled = if registered < objects.len() {
+            Err(RtosError::WaiterQueueFull)
+        } else {
+            poll(objects)
+        };
+        // 需要阻塞但没有定时器时不能无限等待
+        let polled = match (polled, &timer) {
+            (Ok(None), Some(Err(e))) => Err(*e),
+            (polled, _) => polled,
+        };
+        if !matches!(polled, Ok(None)) || deadline.is_some_and(|deadline| now >= deadline) {
+            leave(&objects[..registered], current, false);
+            if current.get_state() != TaskState::Running {
+                current.run();
+            }
+            drop(guard);
+            return match polled {
+                Ok(Some(index)) => Ok(index),
+                Ok(None) => Err(RtosError::Timeout),
+                Err(e) => Err(e),
+            };
+        }
+        drop(guard);
+
+        trigger_schedule();
+
+        if current.wait_aborted() {
+            // 挂起前没有对象移交唤醒，直接离开其余的等待队列
+            for queue in objects.iter().filter_map(Waitable::wait_queue) {
+                queue.remove(current);
+            }
+            return Err(RtosError::WaitAborted);
+        }
+
+        // 被唤醒：Signal 的唤醒直接移交信号，其余对象在下一轮重新检查
+        if let Some(index) = leave(objects, current, true) {
+            if let WaitableKind::Signal(signal) = objects[index].kind
+                && signal.is_closed()
+            {
+                return Err(RtosError::SignalClosed);
+            }
+            return Ok(index);
+        }
+    }
+}
+
+/// 将当前任务加入所有对象的等待队列
+///
+/// 返回成功加入的对象数量，小于 `objects.len()` 表示第一个失败的对象等待队列已满。
+fn register(objects: &[Waitable<'_>], current: Task) -> usize {
+    objects
+        .iter()
+        .take_while(|object| object.wait_queue().is_none_or(|queue| queue.enqueue(current)))
+        .count()
+}
+
+/// 检查是否有对象就绪，返回第一个就绪对象的下标
+fn poll(objects: &[Waitable<'_>]) -> Result<Option<usize>> {
+    for (index, object) in objects.iter().enumerate() {
+        if object.poll()? {
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+/// 离开所有对象的等待队列
+///
+/// 已经把当前任务移出队列的对象唤醒过当前任务。`Signal` 的唤醒会移交一个信号：
+/// `keep_signal` 为 `true` 时保留第一个移交的信号并返回其下标，其余信号重新发送；
+/// 其他对象的唤醒转交给下一个等待者。
+fn leave(objects: &[Waitable<'_>], current: Task, keep_signal: bool) -> Option<usize> {
+    let mut handed = None;
+    for (index, object) in objects.iter().enumerate() {
+        let Some(queue) = object.wait_queue() else {
+            continue;
+        };
+        if queue.remove(current) {
+            continue;
+        }
+
+        match object.kind {
+            WaitableKind::Signal(signal) => {
+                if keep_signal && handed.is_none() {
+                    handed = Some(index);
+                } else {
+                    signal.send();
+                }
+            }
+            _ => {
+                queue.wake_one();
+            }
+        }
+    }
+    handed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_wait_any_returns_ready_signal() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let idle = Signal::new();
+        let ready = Signal::with_count(1);
+        assert_eq!(wait_any(&[(&idle).into(), (&ready).into()], None), Ok(1));
+
+        // 消费了信号，并离开了所有等待队列
+        assert_eq!(ready.count(), 0);
+        assert_eq!(idle.waiter_count(), 0);
+        assert_eq!(ready.waiter_count(), 0);
+        assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Running);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_wait_any_semaphore_mq_and_timer() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let credits = Semaphore::new(0);
+        let mut mq: Mq<u32, 4> = Mq::new().unwrap();
+        assert!(mq.push(7));
+        assert_eq!(wait_any(&[(&credits).into(), (&mq).into()], None), Ok(1));
+        assert_eq!(mq.pop(), Some(7));
+
+        credits.release().unwrap();
+        assert_eq!(wait_any(&[(&mq).into(), (&credits).into()], None), Ok(1));
+        assert_eq!(credits.available_permits(), 0);
+
+        let mut timer = Timer::new(5).unwrap();
+        timer.start().unwrap();
+        Systick::add_current_time(5);
+        assert_eq!(wait_any(&[(&credits).into(), (&timer).into()], None), Ok(1));
+        assert_eq!(credits.waiter_count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[serial]
+    fn test_wait_any_timeout_and_errors() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let signal = Signal::new();
+        assert_eq!(wait_any(&[(&signal).into()], Some(0)), Err(RtosError::Timeout));
+        assert_eq!(signal.waiter_count(), 0);
+        assert_eq!(Scheduler::get_current_task().get_state(), TaskState::Running);
+
+        assert_eq!(wait_any(&[], None), Err(RtosError::InvalidArgument));
+
+        signal.close();
+        assert_eq!(wait_any(&[(&signal).into()], None), Err(RtosError::SignalClosed));
+    }
+
+    #[test]
+    #[serial]
+    fn test_wait_any_static_objects() {
+        use crate::sync::{StaticSemaphore, StaticSignal};
+
+        static IDLE: StaticSignal = StaticSignal::new();
+        static READY: StaticSignal = StaticSignal::with_count(1);
+        static CREDITS: StaticSemaphore = StaticSemaphore::new(0);
+
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let (idle, ready, credits) = (IDLE.handle(), READY.handle(), CREDITS.handle());
+        assert_eq!(wait_any(&[(&idle).into(), (&ready).into()], None), Ok(1));
+        assert_eq!(ready.count(), 0);
+
+        credits.release().unwrap();
+        assert_eq!(wait_any(&[(&idle).into(), (&credits).into()], None), Ok(1));
+        assert_eq!(credits.available_permits(), 0);
+        assert_eq!(wait_any(&[(&idle).into(), (&credits).into()], Some(0)), Err(RtosError::Timeout));
+        assert_eq!(idle.waiter_count(), 0);
+        assert_eq!(credits.waiter_count(), 0);
+    }
+}
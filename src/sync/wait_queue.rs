@@ -0,0 +1,556 @@
+//! # WaitQueue - 等待队列
+//!
+//! 每个同步对象拥有自己的等待队列，唤醒时直接取出被阻塞的任务，
+//! 不需要像 `Event::wake_task` 那样遍历所有任务控制块，也不会唤醒无关的任务。
+//!
+//! ## 特性
+//!
+//! - 固定容量（16 个等待者），不需要分配内存
+//! - 支持 FIFO 和按优先级两种唤醒顺序（同优先级按 FIFO）
+//! - 条件检查和入队在队列锁内原子完成，不会丢失唤醒
+//! - 支持超时，超时后任务自动移出队列
+//! - 任务被删除时自动移出队列
+//!
+//! 和条件变量一样，等待可能被虚假唤醒（例如被 `resume` 或其他事件唤醒），
+//! 调用者在等待返回后需要重新检查自己的条件。
+//!
+//! `Signal`、`Semaphore`、`CondVar`、`Mutex`、`RwLock` 和消息队列都基于它实现，
+//! 也可以用它编写自己的同步原语。
+//!
+//! ## 使用示例
+//!
+//! ```rust,no_run
+//! use core::sync::atomic::{AtomicBool, Ordering};
+//! use neon_rtos2::sync::{WaitOrder, WaitQueue};
+//!
+//! /// 一次性的门闩：打开前所有等待者阻塞，打开后全部放行
+//! struct Latch {
+//!     open: AtomicBool,
+//!     waiters: WaitQueue,
+//! }
+//!
+//! impl Latch {
+//!     const fn new() -> Self {
+//!         Self { open: AtomicBool::new(false), waiters: WaitQueue::with_order(WaitOrder::Priority) }
+//!     }
+//!
+//!     fn wait(&self) {
+//!         // 在队列锁内检查条件，避免与 open() 竞争
+//!         let _ = self.waiters.wait_if(None, || Ok(!self.open.load(Ordering::Acquire)));
+//!     }
+//!
+//!     fn open(&self) {
+//!         self.open.store(true, Ordering::Release);
+//!         self.waiters.wake_all();
+//!     }
+//! }
+//! ```
+
+#[cfg(feature = "alloc")]
+use crate::compat::VecDeque;
+#[cfg(not(feature = "alloc"))]
+use crate::config::MAX_ASYNC_WAITERS;
+use crate::error::{Result, RtosError};
+use crate::hal::trigger_schedule;
+use crate::kernel::scheduler::Scheduler;
+use crate::kernel::task::{Priority, Task, TaskState};
+use crate::kernel::time::systick::Systick;
+use crate::kernel::time::timer::Timer;
+use crate::sync::event::Event;
+use crate::sync::signal::WaiterList;
+#[cfg(not(feature = "alloc"))]
+use crate::mem::ring::RingBuffer;
+use core::cmp::Reverse;
+use core::task::Waker;
+use spin::Mutex;
+
+/// 等待队列的唤醒顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitOrder {
+    /// 先等待的任务先被唤醒
+    #[default]
+    Fifo,
+    /// 优先级最高的任务先被唤醒，同优先级按 FIFO
+    Priority,
+}
+
+/// 等待队列
+///
+/// 记录阻塞在某个同步对象上的任务。
+pub struct WaitQueue {
+    /// 等待者列表（存储被阻塞任务的 ID）
+    pub(crate) waiters: Mutex<WaiterList>,
+    /// 唤醒顺序
+    order: WaitOrder,
+}
+
+impl WaitQueue {
+    /// 创建 FIFO 顺序的等待队列
+    pub const fn new() -> Self {
+        Self::with_order(WaitOrder::Fifo)
+    }
+
+    /// 创建指定唤醒顺序的等待队列
+    pub const fn with_order(order: WaitOrder) -> Self {
+        Self {
+            waiters: Mutex::new(WaiterList::new()),
+            order,
+        }
+    }
+
+    /// 获取唤醒顺序
+    pub fn order(&self) -> WaitOrder {
+        self.order
+    }
+
+    /// 阻塞当前任务，直到被唤醒或超时
+    ///
+    /// 可能被虚假唤醒。
+    ///
+    /// # 参数
+    /// - `timeout_ms`: 超时时间（毫秒），`None` 表示一直等待
+    ///
+    /// # 返回值
+    /// - `Ok(())`: 被 `wake_one` / `wake_all` 唤醒
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::WaiterQueueFull)`: 等待队列已满
+    /// - `Err(RtosError::TimerSlotsFull)`: 带超时等待时没有可用的定时器
+    pub fn wait(&self, timeout_ms: Option<usize>) -> Result<()> {
+        self.wait_if(timeout_ms, || Ok(true)).map(|_| ())
+    }
+
+    /// 条件成立时阻塞当前任务，直到被唤醒或超时
+    ///
+    /// `should_block` 在持有队列锁时调用，唤醒方在同一把锁内取出等待者，
+    /// 因此"检查条件 - 入队"和"修改条件 - 唤醒"不会交错而丢失唤醒。
+    /// `should_block` 不能调用本队列的其他方法。
+    ///
+    /// 返回 `Ok(true)` 后调用者需要重新检查条件：任务可能被虚假唤醒，
+    /// 唤醒后条件也可能已经被其他任务改变。
+    ///
+    /// # 参数
+    /// - `timeout_ms`: 超时时间（毫秒），`None` 表示一直等待
+    /// - `should_block`: 返回 `Ok(true)` 时阻塞，`Ok(false)` 时不阻塞，返回错误时原样返回
+    ///
+    /// # 返回值
+    /// - `Ok(true)`: 阻塞后被唤醒（或虚假唤醒）
+    /// - `Ok(false)`: 条件不成立，没有阻塞
+    /// - `Err(RtosError::Timeout)`: 等待超时
+    /// - `Err(RtosError::WaiterQueueFull)`: 等待队列已满
+    /// - `Err(RtosError::TimerSlotsFull)`: 带超时等待时没有可用的定时器
+    /// - `Err(RtosError::WaitAborted)`: 阻塞期间任务被挂起
+    pub fn wait_if<F>(&self, timeout_ms: Option<usize>, should_block: F) -> Result<bool>
+    where
+        F: FnOnce() -> Result<bool>,
+    {
+        self.wait_if_then(timeout_ms, should_block, || {})
+    }
+
+    /// 与 `wait_if` 相同，但在阻塞之后、切换走之前调用 `on_blocked`
+    ///
+    /// `on_blocked` 在释放队列锁之后、持有调度器锁时调用，可以访问本队列，
+    /// 用于在让出 CPU 之前完成依赖等待者列表的工作（例如传递优先级继承）。
+    pub(crate) fn wait_if_then<F, B>(&self, timeout_ms: Option<usize>, should_block: F, on_blocked: B) -> Result<bool>
+    where
+        F: FnOnce() -> Result<bool>,
+        B: FnOnce(),
+    {
+        self.wait_as(Event::WaitQueue(self.id()), timeout_ms, should_block, on_blocked)
+    }
+
+    /// 与 `wait_if` 相同，但以 `event` 作为阻塞原因
+    ///
+    /// 供内嵌等待队列的内核对象使用，使任务状态显示它等待的对象（例如 `Event::Notify`）。
+    pub(crate) fn wait_if_as<F>(&self, event: Event, timeout_ms: Option<usize>, should_block: F) -> Result<bool>
+    where
+        F: FnOnce() -> Result<bool>,
+    {
+        self.wait_as(event, timeout_ms, should_block, || {})
+    }
+
+    fn wait_as<F, B>(&self, event: Event, timeout_ms: Option<usize>, should_block: F, on_blocked: B) -> Result<bool>
+    where
+        F: FnOnce() -> Result<bool>,
+        B: FnOnce(),
+    {
+        let mut current = Scheduler::get_current_task();
+        let task_id = current.get_taskid();
+        let deadline = timeout_ms.map(|ms| Systick::get_current_time().saturating_add(ms));
+
+        // 超时等待由定时器唤醒；没有可用的定时器时只有需要阻塞才返回错误
+        let timer = match timeout_ms {
+            Some(ms) if ms > 0 => Some(Timer::new(ms).and_then(|mut timer| {
+                timer.start_for(current)?;
+                Ok(timer)
+            })),
+            _ => None,
+        };
+
+        {
+            // 阻塞之后、on_blocked 完成之前不能被切换走
+            let _guard = Scheduler::lock_guard();
+            {
+                let mut waiters = self.waiters.lock();
+                if !should_block()? {
+                    return Ok(false);
+                }
+                if timeout_ms == Some(0) {
+                    return Err(RtosError::Timeout);
+                }
+                if let Some(Err(e)) = timer {
+                    return Err(e);
+                }
+                // 上一次等待时调度器没能切换走（例如被锁住）的任务仍在队列中
+                if !waiters.contains(task_id) && !waiters.push(task_id) {
+                    return Err(RtosError::WaiterQueueFull);
+                }
+                current.set_wait_list(&self.waiters);
+                current.block(event);
+            }
+            on_blocked();
+        }
+
+        trigger_schedule();
+
+        if current.wait_aborted() {
+            // 挂起时已经离开等待队列
+            return Err(RtosError::WaitAborted);
+        }
+        let mut waiters = self.waiters.lock();
+        if !waiters.contains(task_id) {
+            // 已被 wake_one / wake_all 移出队列
+            return Ok(true);
+        }
+        if deadline.is_some_and(|deadline| Systick::get_current_time() >= deadline) {
+            waiters.remove(task_id);
+            if current.get_state() != TaskState::Running {
+                current.run();
+            }
+            return Err(RtosError::Timeout);
+        }
+        if !matches!(current.get_state(), TaskState::Blocked(_)) {
+            // 被其他原因唤醒，离开等待队列
+            waiters.remove(task_id);
+        }
+        Ok(true)
+    }
+
+    /// 唤醒一个等待者
+    ///
+    /// 已经不处于阻塞状态的等待者（例如同时等待多个对象时已被其他对象唤醒）
+    /// 会被移出队列并跳过。
+    ///
+    /// # 返回值
+    /// 被唤醒的任务，没有可唤醒的等待者时返回 `None`
+    pub fn wake_one(&self) -> Option<Task> {
+        let mut waiters = self.waiters.lock();
+        loop {
+            let task_id = match self.order {
+                WaitOrder::Fifo => waiters.pop_front()?,
+                WaitOrder::Priority => {
+                    // iter 按 FIFO 顺序返回，min_by_key 在相等时取第一个
+                    let task_id = waiters.iter().min_by_key(|id| Reverse(Task::from_id(*id).get_priority()))?;
+                    waiters.remove(task_id);
+                    task_id
+                }
+            };
+            let task = Task::from_id(task_id);
+            if Self::wake_task(task) {
+                return Some(task);
+            }
+        }
+    }
+
+    /// 唤醒所有等待者
+    ///
+    /// # 返回值
+    /// 被唤醒的任务数量（不包括已经不处于阻塞状态的等待者）
+    pub fn wake_all(&self) -> usize {
+        let mut waiters = self.waiters.lock();
+        waiters
+            .drain()
+            .iter()
+            .flatten()
+            .filter(|task_id| Self::wake_task(Task::from_id(**task_id)))
+            .count()
+    }
+
+    /// 尝试唤醒所有等待者，不等待任何锁
+    ///
+    /// 供不能自旋等待的延迟处理上下文使用。队列、调度器或任务状态的锁被占用时停止，
+    /// 还没有唤醒的等待者留在队列中。
+    ///
+    /// # 返回值
+    /// 被唤醒的任务数量，有锁被占用时返回 `None`
+    pub(crate) fn try_wake_all(&self) -> Option<usize> {
+        let mut waiters = self.waiters.try_lock()?;
+        let mut woken = 0;
+        while let Some(task_id) = waiters.iter().next() {
+            if Scheduler::try_wake_task(&Task::from_id(task_id))? {
+                woken += 1;
+            }
+            waiters.remove(task_id);
+        }
+        Some(woken)
+    }
+
+    /// 将任务加入等待队列（不阻塞）
+    ///
+    /// 用于同时等待多个对象：调用者自行阻塞，并在被唤醒后用 `remove` 离开队列。
+    ///
+    /// # 返回值
+    /// - `true`: 任务在队列中
+    /// - `false`: 等待队列已满
+    pub(crate) fn enqueue(&self, task: Task) -> bool {
+        let mut waiters = self.waiters.lock();
+        if !waiters.contains(task.get_taskid()) && !waiters.push(task.get_taskid()) {
+            return false;
+        }
+        task.set_wait_list(&self.waiters);
+        true
+    }
+
+    /// 将任务移出等待队列（不唤醒）
+    ///
+    /// # 返回值
+    /// 任务是否在队列中
+    pub fn remove(&self, task: Task) -> bool {
+        self.waiters.lock().remove(task.get_taskid())
+    }
+
+    /// 检查任务是否在等待队列中
+    pub fn contains(&self, task: Task) -> bool {
+        self.waiters.lock().contains(task.get_taskid())
+    }
+
+    /// 获取仍处于阻塞状态的等待者中的最高优先级
+    pub(crate) fn highest_priority(&self) -> Option<Priority> {
+        self.waiters
+            .lock()
+            .iter()
+            .map(Task::from_id)
+            .filter(|task| matches!(task.get_state(), TaskState::Blocked(_)))
+            .map(|task| task.get_priority())
+            .max()
+    }
+
+    /// 获取等待者数量
+    pub fn len(&self) -> usize {
+        self.waiters.lock().len()
+    }
+
+    /// 检查等待队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.waiters.lock().is_empty()
+    }
+
+    /// 获取等待队列的唯一标识（地址）
+    pub fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// 将已被移出等待列表的阻塞任务设为就绪并放回调度器的就绪队列
+    ///
+    /// 供自行维护等待列表的内核对象使用，任务不处于阻塞状态时不做任何事。
+    ///
+    /// # 返回值
+    /// 任务是否被唤醒
+    pub(crate) fn wake_task(mut task: Task) -> bool {
+        if let TaskState::Blocked(_) = task.get_state() {
+            task.ready();
+            Scheduler::enqueue_ready_task(&task);
+            return true;
+        }
+        false
+    }
+}
+
+impl Default for WaitQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for WaitQueue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WaitQueue")
+            .field("order", &self.order)
+            .field("waiters", &self.len())
+            .finish()
+    }
+}
+
+// ============================================================================
+// 异步等待者队列
+// ============================================================================
+
+/// 异步等待者（`Waker`）队列
+///
+/// 启用 `alloc` 时容量不限；关闭时最多登记 `config::MAX_ASYNC_WAITERS` 个，
+/// 队列满时 `push_back` 立即唤醒传入的 waker 并返回 `false`，Future 下次被 poll 时重新登记。
+pub(crate) struct WakerQueue {
+    #[cfg(feature = "alloc")]
+    wakers: VecDeque<Waker>,
+    #[cfg(not(feature = "alloc"))]
+    wakers: RingBuffer<Waker, MAX_ASYNC_WAITERS>,
+}
+
+impl WakerQueue {
+    /// 创建空队列
+    pub(crate) const fn new() -> Self {
+        #[cfg(feature = "alloc")]
+        let wakers = VecDeque::new();
+        #[cfg(not(feature = "alloc"))]
+        let wakers = RingBuffer::new();
+        Self { wakers }
+    }
+
+    /// 登记 waker
+    ///
+    /// 返回 `false` 表示队列已满，waker 已经被立即唤醒。
+    pub(crate) fn push_back(&mut self, waker: Waker) -> bool {
+        #[cfg(feature = "alloc")]
+        self.wakers.push_back(waker);
+        #[cfg(not(feature = "alloc"))]
+        if let Err(waker) = self.wakers.push_back(waker) {
+            waker.wake();
+            return false;
+        }
+        true
+    }
+
+    /// 取出最早登记的 waker
+    pub(crate) fn pop_front(&mut self) -> Option<Waker> {
+        self.wakers.pop_front()
+    }
+
+    /// 登记的 waker 数量
+    pub(crate) fn len(&self) -> usize {
+        self.wakers.len()
+    }
+}
+
+impl Default for WakerQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IntoIterator for WakerQueue {
+    type Item = Waker;
+    type IntoIter = WakerQueueIter;
+
+    fn into_iter(self) -> WakerQueueIter {
+        WakerQueueIter { queue: self }
+    }
+}
+
+/// 按登记顺序取出所有 waker 的迭代器
+pub(crate) struct WakerQueueIter {
+    queue: WakerQueue,
+}
+
+impl Iterator for WakerQueueIter {
+    type Item = Waker;
+
+    fn next(&mut self) -> Option<Waker> {
+        self.queue.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::task::Priority;
+    use crate::utils::kernel_init;
+    use serial_test::serial;
+
+    /// 模拟任务 `task` 阻塞在等待队列上
+    fn park(queue: &WaitQueue, mut task: Task) {
+        assert!(queue.waiters.lock().push(task.get_taskid()));
+        task.set_wait_list(&queue.waiters);
+        task.block(Event::WaitQueue(queue.id()));
+    }
+
+    #[test]
+    #[serial]
+    fn test_wait_queue_fifo_order() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let first = Task::new("first", |_| {}).unwrap();
+        let second = Task::builder("second").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::start();
+
+        let queue = WaitQueue::new();
+        park(&queue, first);
+        park(&queue, second);
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.wake_one(), Some(first));
+        assert_eq!(first.get_state(), TaskState::Ready);
+        assert!(matches!(second.get_state(), TaskState::Blocked(_)));
+        assert_eq!(queue.wake_all(), 1);
+        assert_eq!(second.get_state(), TaskState::Ready);
+        assert_eq!(queue.wake_one(), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_wait_queue_priority_order() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let low = Task::builder("low").priority(Priority::Low).spawn(|_| {}).unwrap();
+        let high = Task::builder("high").priority(Priority::High).spawn(|_| {}).unwrap();
+        let high2 = Task::builder("high2").priority(Priority::High).spawn(|_| {}).unwrap();
+        Scheduler::start();
+
+        let queue = WaitQueue::with_order(WaitOrder::Priority);
+        park(&queue, low);
+        park(&queue, high);
+        park(&queue, high2);
+
+        assert_eq!(queue.wake_one(), Some(high));
+        assert_eq!(queue.wake_one(), Some(high2));
+        assert_eq!(queue.wake_one(), Some(low));
+    }
+
+    #[test]
+    #[serial]
+    fn test_wait_queue_wait_if_and_timeout() {
+        kernel_init();
+        let main = Task::new("main", |_| {}).unwrap();
+        Scheduler::start();
+
+        let queue = WaitQueue::new();
+        // 条件不成立时不阻塞
+        assert_eq!(queue.wait_if(None, || Ok(false)), Ok(false));
+        assert_eq!(queue.wait_if(None, || Err(RtosError::SignalClosed)), Err(RtosError::SignalClosed));
+
+        // 超时为 0 时立即返回
+        assert_eq!(queue.wait(Some(0)), Err(RtosError::Timeout));
+        assert!(queue.is_empty());
+        assert_eq!(main.get_state(), TaskState::Running);
+
+        // 没有可用的定时器时返回错误，而不是退化为无限等待
+        let timers: Vec<Timer> = (0..crate::config::MAX_TIMERS).map(|_| Timer::new(1000).unwrap()).collect();
+        assert_eq!(queue.wait(Some(10)), Err(RtosError::TimerSlotsFull));
+        assert!(queue.is_empty());
+        assert_eq!(main.get_state(), TaskState::Running);
+        drop(timers);
+    }
+
+    #[test]
+    #[serial]
+    fn test_wait_queue_delete_waiter() {
+        kernel_init();
+        Task::new("main", |_| {}).unwrap();
+        let worker = Task::new("worker", |_| {}).unwrap();
+        Scheduler::start();
+
+        let queue = WaitQueue::new();
+        park(&queue, worker);
+        worker.delete().unwrap();
+        assert!(!queue.contains(worker));
+        assert_eq!(queue.wake_one(), None);
+    }
+}
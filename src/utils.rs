@@ -7,7 +7,7 @@ use crate::ipc::queue::Mq;
 /// 内核初始化
 /// 
 /// 初始化所有内核子系统，包括：
-/// - 内存分配器
+/// - 内存分配器（启用 `alloc` 时）
 /// - 任务管理
 /// - 调度器
 /// - 定时器
@@ -21,6 +21,7 @@ use crate::ipc::queue::Mq;
 /// 
 /// 此函数会完全重置所有全局状态，适合在测试开始时调用。
 pub fn kernel_init() {
+    #[cfg(feature = "alloc")]
     crate::mem::allocator::init_heap();
     Task::init();
     Scheduler::init();
@@ -28,8 +29,3 @@ pub fn kernel_init() {
     Systick::init();
     Mq::<u8, 1>::init();  // 初始化消息队列槽位
 }
-
-
-pub(crate) fn task_exit_error() -> ! {
-    loop {}
-}
\ No newline at end of file